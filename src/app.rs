@@ -1,22 +1,41 @@
-use crate::copilot::CopilotClient;
+use crate::copilot::{CopilotClient, SessionMode};
 use crate::event::AppEvent;
-use crate::session::store;
-use crate::session::{Message, SessionMeta, SCHEMA_VERSION};
+use crate::session::store::{self, SessionSearchIndex, SessionWriter};
+use crate::session::{
+    default_session_title, format_timestamp, now_timestamp, title_from_prompt, Message,
+    SessionMeta, SessionSearchText, SessionSummary, ToolCallRecord, ToolStatus, SCHEMA_VERSION,
+};
+use crate::settings::{self, Settings};
 use crate::theme::Theme;
-use crate::ui::catalog::{CatalogManager, TemplateDocument, UiIntent};
-use crate::ui::event::{UiEvent, UiEventLog};
+use crate::ui::catalog::{
+    sanitize_template_id, CatalogManager, ResolutionTrace, SharedCatalog, TemplateDocument,
+    TemplateMatch, TemplateMeta, UiIntent,
+};
+use crate::ui::event::{UiEvent, UiEventEntry, UiEventLog, UiFieldValue};
+use crate::ui::export::{canvas_html, ExportBlock};
+use crate::ui::intent;
+use crate::ui::registry::render_copy_button;
 use crate::ui::runtime::UiRuntime;
+use crate::ui::schema::{TreeNode, MAX_TREE_DEPTH};
 use crate::ui::workspace::{
-    CanvasBlockActionStatus, CanvasBlockActionType, CanvasBlockActor, CanvasBlockState,
-    CanvasWorkspaceState,
+    canvas_context_summary, resolve_title_target, CanvasBlockActionStatus, CanvasBlockActionType,
+    CanvasBlockActor, CanvasBlockState, CanvasBlockSummary, CanvasWorkspaceState,
+    SharedCanvasSnapshot, TitleTarget,
 };
+use crate::window::WindowGeometry;
 use copilot_sdk::ConnectionState;
-use eframe::egui::{self, Align, Frame, RichText, ScrollArea, Stroke};
+use eframe::egui::{self, Align, Color32, Frame, RichText, ScrollArea, Stroke};
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{Receiver, TryRecvError};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const CATALOG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const SESSION_PERSIST_INTERVAL: Duration = Duration::from_millis(500);
+const DIAGNOSTICS_LOG_CAP: usize = 500;
+const MAXIMIZED_CHAT_MIN_WIDTH: f32 = 320.0;
 
 #[derive(Debug, Clone)]
 struct TemplateSelectionContext {
@@ -33,6 +52,44 @@ struct CanvasBlock {
     last_touched_at: u128,
 }
 
+impl CanvasBlock {
+    /// Current form values; a minimized block keeps them in `state` rather than a runtime.
+    fn form_state_snapshot(&self) -> BTreeMap<String, UiFieldValue> {
+        if self.ui_runtime.has_schema() {
+            self.ui_runtime.form_state_snapshot()
+        } else {
+            self.state.form_state.clone()
+        }
+    }
+
+    /// Parks the form values in `state` and drops the runtime while the block is minimized.
+    fn release_runtime(&mut self) {
+        self.state.form_state = self.form_state_snapshot();
+        self.ui_runtime =
+            UiRuntime::with_event_log_max_entries(self.ui_runtime.event_log_max_entries());
+        self.synced_event_count = 0;
+    }
+
+    /// Builds the runtime from `state`; a no-op when the schema is already loaded.
+    fn load_runtime(&mut self) -> Result<(), String> {
+        let reloaded = self
+            .ui_runtime
+            .reload_schema_value(&self.state.schema)
+            .map_err(|err| err.to_string())?;
+        if reloaded {
+            if self.state.form_state.is_empty() {
+                // Nothing was saved for this block yet, so its own event log is the only record.
+                self.ui_runtime.replay_event_log();
+            } else {
+                self.ui_runtime
+                    .restore_form_state(self.state.form_state.clone());
+            }
+            self.synced_event_count = self.ui_runtime.event_log_total();
+        }
+        Ok(())
+    }
+}
+
 struct CanvasRenderRequest {
     intent: UiIntent,
     template_id: String,
@@ -40,11 +97,719 @@ struct CanvasRenderRequest {
     provider_id: String,
     provider_kind: String,
     target_block_id: Option<String>,
+    target_title: Option<String>,
     root_path: Option<String>,
     schema: Value,
     provisional_template: Option<TemplateDocument>,
 }
 
+/// A catalog template opened from the browser. It lives outside `canvas_blocks`, so it
+/// is never persisted, published to the assistant, or logged.
+struct TemplatePreview {
+    template_id: String,
+    title: String,
+    runtime: UiRuntime,
+}
+
+/// Loads `template_id` into a throwaway runtime for previewing.
+fn template_preview(
+    catalog: &CatalogManager,
+    template_id: &str,
+) -> Result<TemplatePreview, String> {
+    let schema = catalog.preview(template_id)?;
+    let title = catalog
+        .find_by_id(template_id)
+        .map(|template| template.document.meta.title.clone())
+        .unwrap_or_else(|| template_id.to_string());
+    let mut runtime = UiRuntime::new();
+    runtime.load_validated(schema);
+    Ok(TemplatePreview {
+        template_id: template_id.to_string(),
+        title,
+        runtime,
+    })
+}
+
+#[derive(Debug, Clone)]
+struct SaveTemplateDraft {
+    block_id: String,
+    title: String,
+    template_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PlaygroundResult {
+    NoIntent,
+    /// Intent trace lines, then the catalog resolution.
+    Resolved(Vec<String>, Box<ResolutionTrace>),
+}
+
+fn resolve_playground_prompt(catalog_manager: &CatalogManager, prompt: &str) -> PlaygroundResult {
+    let Some(intent) = intent::intent_from_text(prompt) else {
+        return PlaygroundResult::NoIntent;
+    };
+    let trace_lines = intent::explain_intent(prompt)
+        .map(|detected| detected.trace.lines())
+        .unwrap_or_default();
+    PlaygroundResult::Resolved(
+        trace_lines,
+        Box::new(catalog_manager.resolve(&intent).trace),
+    )
+}
+
+/// The last session, offered for reopening only when it belongs to `workspace` and
+/// has messages.
+fn reopen_offer_for(
+    sessions: &[SessionSummary],
+    last_session_id: Option<&str>,
+    workspace: &Path,
+) -> Option<SessionSummary> {
+    let last_session_id = last_session_id?;
+    let workspace = workspace.to_string_lossy();
+    sessions
+        .iter()
+        .find(|session| {
+            session.session_id == last_session_id
+                && session.workspace == workspace
+                && session.message_count > 0
+        })
+        .cloned()
+}
+
+/// Captures a block's current schema and intent as a user catalog template.
+fn template_from_block(
+    state: &CanvasBlockState,
+    title: &str,
+    template_id: &str,
+) -> TemplateDocument {
+    TemplateDocument {
+        meta: TemplateMeta {
+            id: sanitize_template_id(template_id),
+            title: title.trim().to_string(),
+            version: "1.0.0".to_string(),
+            tags: state.intent.tags.clone(),
+        },
+        match_rules: TemplateMatch {
+            primary: state.intent.primary.clone(),
+            operations: state.intent.operations.clone(),
+            tags: state.intent.tags.clone(),
+        },
+        schema: state.schema.clone(),
+    }
+}
+
+/// Lists ranked candidates (selected one highlighted) and, when nothing won, the
+/// no-match reasons of a resolution trace.
+fn render_resolution_trace(ui: &mut egui::Ui, theme: &Theme, trace: &ResolutionTrace) {
+    for candidate in &trace.ranked_candidates {
+        let color = if candidate.selected {
+            theme.success
+        } else {
+            theme.text_muted
+        };
+        ui.label(
+            RichText::new(format!(
+                "{}:{} score={} ops={} tags={}{}",
+                candidate.provider_id,
+                candidate.template_id,
+                candidate.score,
+                candidate.operation_overlap,
+                candidate.tag_overlap,
+                candidate
+                    .excluded_reason
+                    .as_deref()
+                    .map(|reason| format!(" ({reason})"))
+                    .unwrap_or_default()
+            ))
+            .size(theme.text_size(12.0))
+            .color(color),
+        );
+    }
+    for reason in &trace.no_match_reasons {
+        ui.label(
+            RichText::new(format!("no match: {reason}"))
+                .size(theme.text_size(12.0))
+                .color(theme.warning),
+        );
+    }
+}
+
+/// Appends `entry`, dropping the oldest entries so at most `cap` remain.
+fn push_bounded<T>(log: &mut VecDeque<T>, entry: T, cap: usize) {
+    log.push_back(entry);
+    while log.len() > cap {
+        log.pop_front();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum DiagnosticLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl DiagnosticLevel {
+    const ALL: [Self; 3] = [Self::Info, Self::Warn, Self::Error];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Warn => "Warn",
+            Self::Error => "Error",
+        }
+    }
+
+    fn color(self, theme: &Theme) -> Color32 {
+        match self {
+            Self::Info => theme.text_muted,
+            Self::Warn => theme.warning,
+            Self::Error => theme.danger,
+        }
+    }
+}
+
+/// A diagnostics line; `text` is the machine-readable `[timestamp] message` form.
+#[derive(Debug, Clone)]
+struct DiagnosticEntry {
+    level: DiagnosticLevel,
+    text: String,
+}
+
+fn connection_state_level(state: ConnectionState) -> DiagnosticLevel {
+    match state {
+        ConnectionState::Error => DiagnosticLevel::Error,
+        ConnectionState::Disconnected => DiagnosticLevel::Warn,
+        ConnectionState::Connected | ConnectionState::Connecting => DiagnosticLevel::Info,
+    }
+}
+
+fn canvas_lifecycle_level(status: CanvasBlockActionStatus) -> DiagnosticLevel {
+    match status {
+        CanvasBlockActionStatus::Failed => DiagnosticLevel::Warn,
+        CanvasBlockActionStatus::Requested | CanvasBlockActionStatus::Succeeded => {
+            DiagnosticLevel::Info
+        }
+    }
+}
+
+/// Diagnostic level of a tool outcome. The transcript chip takes its color from it too.
+fn tool_outcome_level(status: &ToolStatus) -> DiagnosticLevel {
+    match status {
+        ToolStatus::Error => DiagnosticLevel::Error,
+        ToolStatus::TextOnly
+        | ToolStatus::NoMatch
+        | ToolStatus::NotFound
+        | ToolStatus::Ambiguous
+        | ToolStatus::Suppressed
+        | ToolStatus::Denied => DiagnosticLevel::Warn,
+        ToolStatus::RenderedCatalog
+        | ToolStatus::RenderedProvisional
+        | ToolStatus::Applied
+        | ToolStatus::Success
+        | ToolStatus::Other(_) => DiagnosticLevel::Info,
+    }
+}
+
+const CANCELLED_RESPONSE_MARKER: &str = "[response cancelled]";
+
+/// Turns the partial text of a cancelled stream into a transcript message that is
+/// visibly marked as truncated. Returns `None` when nothing was streamed yet.
+fn cancelled_assistant_message(partial: &str, timestamp: String) -> Option<Message> {
+    let partial = partial.trim_end();
+    if partial.is_empty() {
+        return None;
+    }
+    Some(Message {
+        role: "assistant".to_string(),
+        content: format!("{partial}\n\n{CANCELLED_RESPONSE_MARKER}"),
+        timestamp,
+        tool: None,
+    })
+}
+
+const TOOL_MESSAGE_ROLE: &str = "tool";
+const TOOL_CHIP_SEPARATOR: &str = " → ";
+
+/// Transcript entry recording a tool call outcome, persisted as a `tool` role message.
+fn tool_chip_message(tool_name: &str, status: ToolStatus, timestamp: String) -> Message {
+    Message {
+        role: TOOL_MESSAGE_ROLE.to_string(),
+        content: format!("{tool_name}{TOOL_CHIP_SEPARATOR}{status}"),
+        timestamp,
+        tool: Some(ToolCallRecord {
+            tool_name: tool_name.to_string(),
+            status,
+        }),
+    }
+}
+
+const ERROR_MESSAGE_ROLE: &str = "error";
+
+/// Transcript entry explaining why a turn failed, persisted as an `error` role
+/// message. Returns `None` when no turn was waiting on the assistant, so errors
+/// outside a turn stay in diagnostics only.
+fn turn_error_message(turn_pending: bool, error: &str, timestamp: String) -> Option<Message> {
+    turn_pending.then(|| Message {
+        role: ERROR_MESSAGE_ROLE.to_string(),
+        content: format!("The response failed: {}", error.trim()),
+        timestamp,
+        tool: None,
+    })
+}
+
+/// Empties the prompt queue after a turn ends without completing, returning an
+/// `error` role note that lists the prompts left unsent. `None` when nothing was queued.
+fn discard_queued_prompts(
+    queue: &mut VecDeque<String>,
+    reason: &str,
+    timestamp: String,
+) -> Option<Message> {
+    if queue.is_empty() {
+        return None;
+    }
+    let count = queue.len();
+    let prompts = queue
+        .drain(..)
+        .map(|prompt| format!("\n- {}", prompt.trim()))
+        .collect::<String>();
+    Some(Message {
+        role: ERROR_MESSAGE_ROLE.to_string(),
+        content: format!(
+            "{count} queued {} not sent because {reason}:{prompts}",
+            if count == 1 {
+                "prompt was"
+            } else {
+                "prompts were"
+            }
+        ),
+        timestamp,
+        tool: None,
+    })
+}
+
+/// Chip label and color for a tool message. The color follows `tool_outcome_level`,
+/// except that known successes are green rather than muted. Messages saved before
+/// outcomes were recorded show their text in the muted color.
+fn tool_chip_style(theme: &Theme, message: &Message) -> (String, Color32) {
+    let Some(record) = &message.tool else {
+        return (format!("🔧 {}", message.content), theme.text_muted);
+    };
+    let color = match (&record.status, tool_outcome_level(&record.status)) {
+        (ToolStatus::Other(_), level) => level.color(theme),
+        (_, DiagnosticLevel::Info) => theme.success,
+        (_, level) => level.color(theme),
+    };
+    (
+        format!(
+            "🔧 {}{TOOL_CHIP_SEPARATOR}{}",
+            record.tool_name, record.status
+        ),
+        color,
+    )
+}
+
+/// Rough token count for a prompt, at about four characters per token.
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Composer counter color: muted below `warning_tokens`, amber from there, red from
+/// `danger_tokens`.
+fn composer_budget_color(
+    theme: &Theme,
+    tokens: usize,
+    warning_tokens: usize,
+    danger_tokens: usize,
+) -> Color32 {
+    if tokens >= danger_tokens {
+        theme.danger
+    } else if tokens >= warning_tokens {
+        theme.warning
+    } else {
+        theme.text_muted
+    }
+}
+
+/// Whether the "canvas was not rendered" banner should show after a tool outcome.
+/// Only `query_ui_catalog` outcomes move it: text-only or error raises it, a render
+/// clears it, and anything else leaves it as it was.
+fn canvas_warning_after_outcome(showing: bool, tool_name: &str, status: &ToolStatus) -> bool {
+    if tool_name != "query_ui_catalog" {
+        return showing;
+    }
+    match status {
+        ToolStatus::TextOnly | ToolStatus::Error => true,
+        ToolStatus::RenderedCatalog | ToolStatus::RenderedProvisional => false,
+        _ => showing,
+    }
+}
+
+/// Drops the trailing assistant reply, or the error bubble of a failed turn, so the
+/// user turn before it can be resent. Returns that user prompt, or `None` (leaving
+/// `messages` untouched) when the transcript is empty or does not end with a reply
+/// or error after a user turn.
+fn trim_for_regenerate(messages: &mut Vec<Message>) -> Option<String> {
+    let last_role = messages.last()?.role.as_str();
+    if last_role != "assistant" && last_role != ERROR_MESSAGE_ROLE {
+        return None;
+    }
+    let user_index = messages
+        .iter()
+        .rposition(|message| message.role == "user")?;
+    let prompt = messages[user_index].content.clone();
+    messages.truncate(user_index + 1);
+    Some(prompt)
+}
+
+/// Drops every message after `index`, keeping the message at `index` itself.
+/// Returns `false` (leaving `messages` untouched) when `index` is out of range.
+fn truncate_transcript_after(messages: &mut Vec<Message>, index: usize) -> bool {
+    if index >= messages.len() {
+        return false;
+    }
+    messages.truncate(index + 1);
+    true
+}
+
+/// Preview of a long message: at most `max_lines` lines and `max_chars` characters,
+/// cut back to a word boundary and ending in "…". A zero limit is ignored. `None`
+/// when nothing would be hidden.
+fn collapsed_preview(text: &str, max_lines: usize, max_chars: usize) -> Option<String> {
+    let mut end = text.len();
+    if max_lines > 0 {
+        if let Some((index, _)) = text.match_indices('\n').nth(max_lines - 1) {
+            end = index;
+        }
+    }
+    if max_chars > 0 {
+        if let Some((index, _)) = text[..end].char_indices().nth(max_chars) {
+            end = if text[index..].starts_with(char::is_whitespace) {
+                index
+            } else {
+                text[..index].rfind(char::is_whitespace).unwrap_or(index)
+            };
+        }
+    }
+    if text[end..].trim().is_empty() {
+        return None;
+    }
+    Some(format!("{}…", text[..end].trim_end()))
+}
+
+/// Indices of chat messages containing `query`, ignoring case. Tool chips never
+/// match, and a blank query matches nothing.
+fn transcript_search(messages: &[Message], query: &str) -> Vec<usize> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| {
+            message.role != TOOL_MESSAGE_ROLE && message.content.to_lowercase().contains(&needle)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Next prompt to send from the queue, but only once no assistant turn is in flight.
+fn next_queued_prompt(queue: &mut VecDeque<String>, turn_in_flight: bool) -> Option<String> {
+    if turn_in_flight {
+        return None;
+    }
+    queue.pop_front()
+}
+
+/// Writes an export to a new `.html` file; existing files are never overwritten.
+fn write_canvas_export(target: &Path, html: &str) -> std::io::Result<()> {
+    let is_html = target
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("html"));
+    if !is_html {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "canvas exports must be .html files",
+        ));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(target)
+        .map_err(|err| match err.kind() {
+            std::io::ErrorKind::AlreadyExists => {
+                std::io::Error::new(err.kind(), "file already exists; choose another name")
+            }
+            _ => err,
+        })?;
+    std::io::Write::write_all(&mut file, html.as_bytes())
+}
+
+/// Tool call waiting on the user's Allow/Deny answer.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingPermission {
+    tool_call_id: String,
+    tool_name: String,
+    details: String,
+}
+
+/// Drops the request the backend gave up waiting on, wherever it sits in the queue.
+fn remove_pending_permission(
+    permissions: &mut VecDeque<PendingPermission>,
+    tool_call_id: &str,
+) -> Option<PendingPermission> {
+    let index = permissions
+        .iter()
+        .position(|request| request.tool_call_id == tool_call_id)?;
+    permissions.remove(index)
+}
+
+/// Whether the composer accepts input: connected, not editing mid-turn, and no tool
+/// permission request left unanswered.
+fn composer_enabled(
+    connected: bool,
+    turn_in_flight: bool,
+    editing: bool,
+    permissions: &VecDeque<PendingPermission>,
+) -> bool {
+    connected && !(turn_in_flight && editing) && permissions.is_empty()
+}
+
+/// Coalesces session writes so a dirty session is saved at most once per interval.
+#[derive(Debug, Default)]
+struct PersistDebounce {
+    dirty: bool,
+    last_write: Option<Instant>,
+}
+
+impl PersistDebounce {
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn mark_written(&mut self, now: Instant) {
+        self.dirty = false;
+        self.last_write = Some(now);
+    }
+
+    /// Time left before a pending write is due; `None` when nothing is pending.
+    fn wait_for(&self, now: Instant, interval: Duration) -> Option<Duration> {
+        if !self.dirty {
+            return None;
+        }
+        let elapsed = self
+            .last_write
+            .map(|last| now.saturating_duration_since(last))
+            .unwrap_or(interval);
+        Some(interval.saturating_sub(elapsed))
+    }
+
+    fn is_due(&self, now: Instant, interval: Duration) -> bool {
+        self.wait_for(now, interval) == Some(Duration::ZERO)
+    }
+}
+
+const FILE_TREE_MAX_ENTRIES: usize = 500;
+const FILE_TREE_TRUNCATED_LABEL: &str = "… (truncated)";
+
+/// Walks `root` into nested tree nodes, directories first, skipping `.git`, the
+/// configured `skip_dirs`, and anything excluded by `.gitignore`.
+/// Directories deeper than `max_depth` are listed without their contents. Past
+/// `max_entries` the deepest entries are dropped and a pathless marker node is appended.
+/// Node paths are relative to `base` (normally the workspace) so clicks can be resolved.
+/// With `with_metadata`, files get their size and modified time, and walked directories
+/// the number of entries the walk kept in them; otherwise nothing is stat'ed.
+fn build_file_tree(
+    root: &Path,
+    base: &Path,
+    max_depth: usize,
+    max_entries: usize,
+    skip_dirs: &[String],
+    with_metadata: bool,
+) -> std::io::Result<Vec<TreeNode>> {
+    fn sort_nodes(nodes: &mut [TreeNode]) {
+        nodes.sort_by(|left, right| {
+            right
+                .is_dir
+                .cmp(&left.is_dir)
+                .then_with(|| left.name.cmp(&right.name))
+        });
+    }
+
+    // Surface a missing or unreadable root as an error rather than an empty tree.
+    fs::read_dir(root)?;
+
+    let mut entries = crate::workspace_walker(root, skip_dirs)
+        .max_depth(Some(max_depth))
+        .build()
+        .flatten()
+        .filter(|entry| entry.depth() > 0)
+        .collect::<Vec<_>>();
+    // Keep the shallowest entries when capping, so the top of the tree stays complete.
+    entries.sort_by(|left, right| {
+        left.depth()
+            .cmp(&right.depth())
+            .then_with(|| left.path().cmp(right.path()))
+    });
+    // Counted before the cap so a directory reports everything the walk kept in it.
+    let mut entry_counts: HashMap<PathBuf, usize> = HashMap::new();
+    if with_metadata {
+        for entry in &entries {
+            if let Some(parent) = entry.path().parent() {
+                *entry_counts.entry(parent.to_path_buf()).or_default() += 1;
+            }
+        }
+    }
+    let truncated = entries.len() > max_entries;
+    entries.truncate(max_entries);
+    // Deepest first, so every directory's children are complete before it is built.
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.depth()));
+
+    let mut children_by_dir: HashMap<PathBuf, Vec<TreeNode>> = HashMap::new();
+    for entry in entries {
+        let path = entry.path();
+        let is_dir = entry.file_type().is_some_and(|kind| kind.is_dir());
+        let (size, modified) = if with_metadata {
+            file_tree_metadata(path, is_dir)
+        } else {
+            (None, None)
+        };
+        // Directories at the depth limit were not walked, so their count is unknown.
+        let entry_count = (with_metadata && is_dir && entry.depth() < max_depth)
+            .then(|| entry_counts.get(path).copied().unwrap_or(0));
+        let mut children = children_by_dir.remove(path).unwrap_or_default();
+        sort_nodes(&mut children);
+        let Some(parent) = path.parent() else {
+            continue;
+        };
+        children_by_dir
+            .entry(parent.to_path_buf())
+            .or_default()
+            .push(TreeNode {
+                name: entry.file_name().to_string_lossy().to_string(),
+                path: crate::to_workspace_relative(path, base),
+                is_dir,
+                children,
+                size,
+                modified,
+                entry_count,
+            });
+    }
+
+    let mut nodes = children_by_dir.remove(root).unwrap_or_default();
+    sort_nodes(&mut nodes);
+    if truncated {
+        nodes.push(TreeNode {
+            name: FILE_TREE_TRUNCATED_LABEL.to_string(),
+            path: String::new(),
+            is_dir: false,
+            children: Vec::new(),
+            size: None,
+            modified: None,
+            entry_count: None,
+        });
+    }
+    Ok(nodes)
+}
+
+const FILE_PREVIEW_MAX_BYTES: u64 = 512 * 1024;
+const FILE_PREVIEW_TEMPLATE_ID: &str = "workspace.file_preview";
+const FILE_LISTING_TEMPLATE_ID: &str = "builtin.file_listing.default";
+const WORKSPACE_TREE_COMPONENT_ID: &str = "workspace_tree";
+
+/// Whether a template is the builtin file listing, the only one whose tree the host
+/// fills with the workspace.
+fn is_workspace_explorer_template(template_id: &str, provider_kind: &str) -> bool {
+    template_id == FILE_LISTING_TEMPLATE_ID && provider_kind == "builtin"
+}
+
+/// Whether a block is the host-materialized workspace explorer, the only tree whose
+/// clicks may open files.
+fn is_workspace_explorer(state: &CanvasBlockState) -> bool {
+    is_workspace_explorer_template(&state.template_id, &state.provider_kind)
+}
+
+/// Resolves an explorer path inside the workspace. Both sides are canonicalized, so
+/// absolute paths, `..` segments and symlinks that leave the workspace are refused.
+fn resolve_workspace_file(workspace: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let workspace = workspace.canonicalize().map_err(|err| err.to_string())?;
+    let path = workspace
+        .join(relative_path)
+        .canonicalize()
+        .map_err(|err| err.to_string())?;
+    if !path.starts_with(&workspace) {
+        return Err("path is outside the workspace".to_string());
+    }
+    Ok(path)
+}
+
+/// Code-block language for a file, guessed from its extension.
+fn language_for_path(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" => "cpp",
+        "toml" => "toml",
+        "js" | "mjs" | "cjs" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "json" => "json",
+        "md" => "markdown",
+        "yml" | "yaml" => "yaml",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "sh" | "bash" => "bash",
+        "go" => "go",
+        "java" => "java",
+        "sql" => "sql",
+        _ => "text",
+    }
+}
+
+/// Reads a file for the preview block, refusing anything over `max_bytes` or that
+/// looks binary (contains a NUL byte). Invalid UTF-8 is replaced, not rejected.
+fn read_file_preview(path: &Path, max_bytes: u64) -> Result<String, String> {
+    let metadata = fs::metadata(path).map_err(|err| err.to_string())?;
+    if metadata.is_dir() {
+        return Err("is a directory".to_string());
+    }
+    if metadata.len() > max_bytes {
+        return Err(format!(
+            "file is too large to preview ({} bytes, limit {max_bytes})",
+            metadata.len()
+        ));
+    }
+    let bytes = fs::read(path).map_err(|err| err.to_string())?;
+    if bytes.contains(&0) {
+        return Err("binary file cannot be previewed".to_string());
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Size (files only) and modified time for an explorer node. Anything that cannot be
+/// read is left as `None` rather than failing the listing.
+fn file_tree_metadata(path: &Path, is_dir: bool) -> (Option<u64>, Option<String>) {
+    let metadata = fs::metadata(path).ok();
+    let modified = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.modified().ok())
+        .map(|modified| {
+            chrono::DateTime::<chrono::Utc>::from(modified)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        });
+    let size = metadata.filter(|_| !is_dir).map(|metadata| metadata.len());
+    (size, modified)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum BlockTargetResolution {
     Existing(usize),
@@ -95,6 +860,17 @@ fn resolve_block_target_for_template(
     BlockTargetResolution::Ambiguous(block_ids)
 }
 
+fn resolve_block_target_by_title(blocks: &[CanvasBlock], title: &str) -> BlockTargetResolution {
+    let titles = blocks
+        .iter()
+        .map(|block| (block.state.block_id.as_str(), block.state.title.as_str()));
+    match resolve_title_target(titles, title) {
+        TitleTarget::Unique(index) => BlockTargetResolution::Existing(index),
+        TitleTarget::NotFound => BlockTargetResolution::NotFound,
+        TitleTarget::Ambiguous(block_ids) => BlockTargetResolution::Ambiguous(block_ids),
+    }
+}
+
 fn apply_focus_transition(
     blocks: &mut [CanvasBlock],
     active_block_id: &mut Option<String>,
@@ -122,10 +898,99 @@ fn apply_toggle_minimize_transition(
         .position(|block| block.state.block_id == block_id)?;
     let block = &mut blocks[index];
     block.state.minimized = !block.state.minimized;
+    if block.state.minimized {
+        block.release_runtime();
+    }
     block.last_touched_at = touched_at;
     Some(block.state.minimized)
 }
 
+/// Moves the block at `from` so it ends up at index `to`; other blocks keep their
+/// relative order and every block's state is untouched.
+fn apply_move_transition(blocks: &mut Vec<CanvasBlock>, from: usize, to: usize) -> bool {
+    if from == to || from >= blocks.len() || to >= blocks.len() {
+        return false;
+    }
+    let block = blocks.remove(from);
+    blocks.insert(to, block);
+    true
+}
+
+/// Deep copy of `source` under `block_id` with its own runtime, seeded with the
+/// source's current form values so later edits stay independent.
+fn duplicate_canvas_block(
+    source: &CanvasBlock,
+    block_id: String,
+    touched_at: u128,
+) -> Result<CanvasBlock, String> {
+    let mut state = source.state.clone();
+    state.block_id = block_id;
+    state.minimized = false;
+    state.form_state = source.form_state_snapshot();
+
+    let mut runtime =
+        UiRuntime::with_event_log_max_entries(source.ui_runtime.event_log_max_entries());
+    runtime
+        .load_schema_value(&state.schema)
+        .map_err(|err| err.to_string())?;
+    runtime.restore_form_state(state.form_state.clone());
+    Ok(CanvasBlock {
+        state,
+        synced_event_count: runtime.event_log_total(),
+        ui_runtime: runtime,
+        last_touched_at: touched_at,
+    })
+}
+
+fn apply_maximize_toggle(maximized_block_id: &mut Option<String>, block_id: &str) {
+    if maximized_block_id.as_deref() == Some(block_id) {
+        *maximized_block_id = None;
+    } else {
+        *maximized_block_id = Some(block_id.to_string());
+    }
+}
+
+/// Clears the maximized block once it is no longer open.
+fn retain_maximized_block(maximized_block_id: &mut Option<String>, blocks: &[CanvasBlock]) {
+    if let Some(block_id) = maximized_block_id.as_deref() {
+        if !blocks.iter().any(|block| block.state.block_id == block_id) {
+            *maximized_block_id = None;
+        }
+    }
+}
+
+/// Minimizes every expanded block and returns the ids that changed.
+fn apply_minimize_all_transition(blocks: &mut [CanvasBlock], touched_at: u128) -> Vec<String> {
+    let mut minimized = Vec::new();
+    for block in blocks.iter_mut().filter(|block| !block.state.minimized) {
+        block.state.minimized = true;
+        block.release_runtime();
+        block.last_touched_at = touched_at;
+        minimized.push(block.state.block_id.clone());
+    }
+    minimized
+}
+
+/// Removes every block, clears the active block and returns the closed ids in order.
+fn apply_close_all_transition(
+    blocks: &mut Vec<CanvasBlock>,
+    active_block_id: &mut Option<String>,
+) -> Vec<String> {
+    *active_block_id = None;
+    blocks.drain(..).map(|block| block.state.block_id).collect()
+}
+
+/// Drops every canvas block and its event history so a new session starts empty.
+fn reset_canvas_for_new_session(
+    blocks: &mut Vec<CanvasBlock>,
+    active_block_id: &mut Option<String>,
+    event_log: &mut UiEventLog,
+) {
+    blocks.clear();
+    *active_block_id = None;
+    event_log.clear();
+}
+
 fn apply_close_transition(
     blocks: &mut Vec<CanvasBlock>,
     active_block_id: &mut Option<String>,
@@ -143,101 +1008,269 @@ fn apply_close_transition(
     true
 }
 
+/// Replaces the shared snapshot with the current blocks, in canvas order.
+/// Returns the new summaries when they differ from what was published.
+fn publish_canvas_snapshot(
+    snapshot: &SharedCanvasSnapshot,
+    blocks: &[CanvasBlock],
+) -> Option<Vec<CanvasBlockSummary>> {
+    let summaries = blocks
+        .iter()
+        .map(|block| CanvasBlockSummary::from(&block.state))
+        .collect::<Vec<_>>();
+    let mut published = snapshot.write().ok()?;
+    if *published == summaries {
+        return None;
+    }
+    *published = summaries.clone();
+    Some(summaries)
+}
+
 pub struct BrownieApp {
     rx: Receiver<AppEvent>,
     copilot: CopilotClient,
     connection_state: ConnectionState,
     transcript: Vec<Message>,
-    sessions: Vec<SessionMeta>,
+    sessions: Vec<SessionSummary>,
+    session_search_index: Option<SessionSearchIndex>,
+    session_search_loading: Option<Receiver<(SessionSearchIndex, Vec<String>)>>,
+    /// Last query (trimmed, lowercased) and its hits over `session_search_index`.
+    session_search_cache: Option<(String, HashSet<String>)>,
     current_session: Option<SessionMeta>,
+    renaming_session: Option<(String, String)>,
+    session_search: String,
+    settings: Settings,
+    /// Set once the restored window position has been checked against the monitor.
+    window_position_checked: bool,
+    reopen_offer: Option<SessionSummary>,
     input_buffer: String,
     in_progress_assistant: String,
+    /// Reasoning streamed for the latest turn, shown under "Thinking" until the next prompt.
+    in_progress_reasoning: String,
     is_streaming: bool,
-    diagnostics_log: Vec<String>,
+    cancel_requested: bool,
+    editing_message_index: Option<usize>,
+    prompt_queue: VecDeque<String>,
+    diagnostics_log: VecDeque<DiagnosticEntry>,
+    diagnostics_min_level: DiagnosticLevel,
     workspace: PathBuf,
     instruction_files: Vec<String>,
     scroll_to_bottom: bool,
+    /// Transcript indices of long messages the user expanded with "Show more".
+    expanded_messages: HashSet<usize>,
+    /// Find bar query over the transcript; `None` while the bar is closed.
+    transcript_query: Option<String>,
+    scroll_to_search_match: bool,
     session_unavailable: bool,
     theme: Theme,
-    catalog_manager: CatalogManager,
+    catalog_manager: SharedCatalog,
+    last_catalog_poll: Instant,
+    session_persist: PersistDebounce,
+    session_writer: SessionWriter,
+    session_mode: SessionMode,
+    pending_permissions: VecDeque<PendingPermission>,
     active_intent: Option<UiIntent>,
     selected_template: Option<TemplateSelectionContext>,
+    last_resolution_trace: Option<ResolutionTrace>,
     no_matching_template: bool,
+    canvas_not_rendered: bool,
     pending_provisional_template: Option<TemplateDocument>,
+    save_template_draft: Option<SaveTemplateDraft>,
+    /// Destination typed into the "Export Canvas" card; `None` while it is closed.
+    export_canvas_path: Option<String>,
+    import_session_path: Option<String>,
+    template_preview: Option<TemplatePreview>,
     canvas_blocks: Vec<CanvasBlock>,
     active_block_id: Option<String>,
+    maximized_block_id: Option<String>,
+    canvas_snapshot: SharedCanvasSnapshot,
     canvas_event_log: UiEventLog,
     block_nonce: u64,
     awaiting_assistant_turn: bool,
     pending_canvas_renders: Vec<CanvasRenderRequest>,
+    playground_prompt: String,
+    playground_result: Option<PlaygroundResult>,
 }
 
 impl BrownieApp {
     pub fn new(
         rx: Receiver<AppEvent>,
         copilot: CopilotClient,
+        session_writer: SessionWriter,
         workspace: PathBuf,
         instruction_files: Vec<String>,
+        settings: Settings,
+        settings_warning: Option<String>,
     ) -> Self {
-        let user_catalog_dir = workspace.join(".brownie").join("catalog");
-        let catalog_manager = CatalogManager::with_default_providers(user_catalog_dir, false);
-        let (sessions, warnings) = store::load_all();
-        let mut app = Self {
+        let migration_warnings = store::migrate_legacy();
+        let (sessions, warnings) = store::load_summaries();
+        let mut app = Self::with_sessions(
             rx,
             copilot,
-            connection_state: ConnectionState::Disconnected,
-            transcript: Vec::new(),
-            sessions,
-            current_session: None,
-            input_buffer: String::new(),
-            in_progress_assistant: String::new(),
-            is_streaming: false,
-            diagnostics_log: Vec::new(),
+            session_writer,
             workspace,
             instruction_files,
-            scroll_to_bottom: false,
-            session_unavailable: false,
-            theme: Theme::default(),
-            catalog_manager,
-            active_intent: None,
-            selected_template: None,
-            no_matching_template: false,
-            pending_provisional_template: None,
-            canvas_blocks: Vec::new(),
-            active_block_id: None,
-            canvas_event_log: UiEventLog::default(),
-            block_nonce: 0,
-            awaiting_assistant_turn: false,
-            pending_canvas_renders: Vec::new(),
-        };
+            settings,
+            sessions,
+        );
 
         let catalog_diagnostics = app
-            .catalog_manager
+            .catalog()
             .load_diagnostics()
             .iter()
             .map(|diagnostic| diagnostic.to_log_line())
             .collect::<Vec<_>>();
         for diagnostic in catalog_diagnostics {
-            app.log_diagnostic(diagnostic);
+            app.log_diagnostic(DiagnosticLevel::Warn, diagnostic);
         }
+        app.copilot.refresh_remote_catalog();
 
-        for warning in warnings {
-            app.apply_event(AppEvent::SdkError(warning), None);
+        let (intent_rules, intent_warning) = intent::load_rules(&intent::rules_path());
+        intent::install_rules(intent_rules);
+        if let Some(warning) = intent_warning {
+            app.log_diagnostic(DiagnosticLevel::Warn, warning);
+        }
+        if let Some(warning) = settings_warning {
+            app.log_diagnostic(DiagnosticLevel::Warn, warning);
         }
 
-        app
+        for warning in migration_warnings {
+            app.log_diagnostic(
+                DiagnosticLevel::Warn,
+                format!("session migration warning: {warning}"),
+            );
+        }
+        for warning in warnings {
+            app.apply_event(AppEvent::SdkError(warning), None);
+        }
+
+        app
+    }
+
+    /// App state around an already loaded session list, without touching the disk.
+    fn with_sessions(
+        rx: Receiver<AppEvent>,
+        copilot: CopilotClient,
+        session_writer: SessionWriter,
+        workspace: PathBuf,
+        instruction_files: Vec<String>,
+        settings: Settings,
+        sessions: Vec<SessionSummary>,
+    ) -> Self {
+        let catalog_manager = copilot.catalog();
+        let reopen_offer =
+            reopen_offer_for(&sessions, settings.last_session_id.as_deref(), &workspace);
+        let canvas_snapshot = copilot.canvas_snapshot();
+        let canvas_event_log = UiEventLog::with_max_entries(settings.canvas_event_log_max_entries);
+        Self {
+            rx,
+            copilot,
+            connection_state: ConnectionState::Disconnected,
+            transcript: Vec::new(),
+            sessions,
+            session_search_index: None,
+            session_search_loading: None,
+            session_search_cache: None,
+            current_session: None,
+            input_buffer: String::new(),
+            in_progress_assistant: String::new(),
+            in_progress_reasoning: String::new(),
+            is_streaming: false,
+            cancel_requested: false,
+            editing_message_index: None,
+            prompt_queue: VecDeque::new(),
+            renaming_session: None,
+            session_search: String::new(),
+            window_position_checked: false,
+            reopen_offer,
+            diagnostics_log: VecDeque::new(),
+            diagnostics_min_level: DiagnosticLevel::Info,
+            workspace,
+            instruction_files,
+            scroll_to_bottom: false,
+            expanded_messages: HashSet::new(),
+            transcript_query: None,
+            scroll_to_search_match: false,
+            session_unavailable: false,
+            theme: Theme::default().with_font_scale(settings.font_scale),
+            settings,
+            catalog_manager,
+            last_catalog_poll: Instant::now(),
+            session_persist: PersistDebounce::default(),
+            session_writer,
+            session_mode: SessionMode::default(),
+            pending_permissions: VecDeque::new(),
+            active_intent: None,
+            selected_template: None,
+            last_resolution_trace: None,
+            no_matching_template: false,
+            canvas_not_rendered: false,
+            pending_provisional_template: None,
+            save_template_draft: None,
+            export_canvas_path: None,
+            import_session_path: None,
+            template_preview: None,
+            canvas_blocks: Vec::new(),
+            active_block_id: None,
+            maximized_block_id: None,
+            canvas_snapshot,
+            canvas_event_log,
+            block_nonce: 0,
+            awaiting_assistant_turn: false,
+            pending_canvas_renders: Vec::new(),
+            playground_prompt: String::new(),
+            playground_result: None,
+        }
     }
 
     fn timestamp() -> String {
-        match SystemTime::now().duration_since(UNIX_EPOCH) {
-            Ok(duration) => duration.as_secs().to_string(),
-            Err(_) => "0".to_string(),
+        now_timestamp()
+    }
+
+    fn log_diagnostic(&mut self, level: DiagnosticLevel, message: impl Into<String>) {
+        let entry = DiagnosticEntry {
+            level,
+            text: format!(
+                "[{}] {}",
+                format_timestamp(&Self::timestamp()),
+                message.into()
+            ),
+        };
+        push_bounded(&mut self.diagnostics_log, entry, DIAGNOSTICS_LOG_CAP);
+    }
+
+    fn catalog(&self) -> std::sync::MutexGuard<'_, CatalogManager> {
+        self.catalog_manager
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn poll_catalog_changes(&mut self) {
+        if self.last_catalog_poll.elapsed() < CATALOG_POLL_INTERVAL {
+            return;
+        }
+        self.last_catalog_poll = Instant::now();
+        if !self.catalog().reload_if_changed() {
+            return;
         }
+        self.log_catalog_reload();
     }
 
-    fn log_diagnostic(&mut self, message: impl Into<String>) {
-        self.diagnostics_log
-            .push(format!("[{}] {}", Self::timestamp(), message.into()));
+    fn log_catalog_reload(&mut self) {
+        let templates = self.catalog().templates().len();
+        self.log_diagnostic(
+            DiagnosticLevel::Info,
+            format!("catalog reloaded templates={templates}"),
+        );
+        let rejected = self
+            .catalog()
+            .load_diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.to_log_line())
+            .collect::<Vec<_>>();
+        for line in rejected {
+            self.log_diagnostic(DiagnosticLevel::Warn, line);
+        }
     }
 
     fn connection_label(&self) -> (&'static str, egui::Color32) {
@@ -261,7 +1294,7 @@ impl BrownieApp {
     fn primary_button(&self, label: &str) -> egui::Button<'static> {
         egui::Button::new(
             RichText::new(label.to_string())
-                .size(13.0)
+                .size(self.theme.text_size(13.0))
                 .color(self.theme.text_on_accent),
         )
         .fill(self.theme.accent_primary)
@@ -272,7 +1305,7 @@ impl BrownieApp {
     fn secondary_button(&self, label: &str) -> egui::Button<'static> {
         egui::Button::new(
             RichText::new(label.to_string())
-                .size(13.0)
+                .size(self.theme.text_size(13.0))
                 .color(self.theme.text_primary),
         )
         .fill(self.theme.surface_2)
@@ -280,11 +1313,94 @@ impl BrownieApp {
         .corner_radius(egui::CornerRadius::same(self.theme.radius_8))
     }
 
+    /// Ids of sessions matching the search query, or `None` when it is blank. The
+    /// first search starts building the message index off the UI thread; until it
+    /// arrives only titles are matched. Hits over the index are cached per query.
+    fn session_search_hits(&mut self) -> Option<HashSet<String>> {
+        let query = self.session_search.trim().to_lowercase();
+        if query.is_empty() {
+            return None;
+        }
+        self.poll_session_search_index();
+        if let Some((cached_query, hits)) = &self.session_search_cache {
+            if *cached_query == query {
+                return Some(hits.clone());
+            }
+        }
+        let Some(index) = &self.session_search_index else {
+            return Some(
+                self.sessions
+                    .iter()
+                    .filter(|session| {
+                        session
+                            .title
+                            .as_deref()
+                            .is_some_and(|title| title.to_lowercase().contains(&query))
+                    })
+                    .map(|session| session.session_id.clone())
+                    .collect(),
+            );
+        };
+        let hits = index
+            .iter()
+            .filter(|(_, text)| text.matches(&query))
+            .map(|(session_id, _)| session_id.clone())
+            .collect::<HashSet<_>>();
+        self.session_search_cache = Some((query, hits.clone()));
+        Some(hits)
+    }
+
+    /// Starts the background index build, or takes its result once it is ready.
+    fn poll_session_search_index(&mut self) {
+        if self.session_search_index.is_some() {
+            return;
+        }
+        let Some(loading) = &self.session_search_loading else {
+            self.session_search_loading =
+                Some(self.session_writer.load_search_index_in_background());
+            return;
+        };
+        match loading.try_recv() {
+            Ok((index, warnings)) => {
+                self.session_search_loading = None;
+                self.session_search_index = Some(index);
+                self.session_search_cache = None;
+                self.index_current_session_for_search();
+                for warning in warnings {
+                    self.log_diagnostic(
+                        DiagnosticLevel::Warn,
+                        format!("session load warning: {warning}"),
+                    );
+                }
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.session_search_loading = None,
+        }
+    }
+
+    /// Refreshes the active session's search text after it changed in memory.
+    fn index_current_session_for_search(&mut self) {
+        let (Some(index), Some(session)) = (
+            self.session_search_index.as_mut(),
+            self.current_session.as_ref(),
+        ) else {
+            return;
+        };
+        index.insert(session.session_id.clone(), SessionSearchText::new(session));
+        self.session_search_cache = None;
+    }
+
     fn refresh_sessions(&mut self) {
-        let (sessions, warnings) = store::load_all();
+        let (sessions, warnings) = store::load_summaries();
         self.sessions = sessions;
+        self.session_search_index = None;
+        self.session_search_loading = None;
+        self.session_search_cache = None;
         for warning in warnings {
-            self.log_diagnostic(format!("session load warning: {warning}"));
+            self.log_diagnostic(
+                DiagnosticLevel::Warn,
+                format!("session load warning: {warning}"),
+            );
         }
     }
 
@@ -293,33 +1409,164 @@ impl BrownieApp {
         if prompt.is_empty() {
             return;
         }
+        if self.editing_message_index.is_none()
+            && (self.is_streaming || self.awaiting_assistant_turn)
+        {
+            self.prompt_queue.push_back(prompt);
+            self.input_buffer.clear();
+            ctx.request_repaint();
+            return;
+        }
+        if let Some(index) = self.editing_message_index.take() {
+            if self.resubmit_edited_message(index, prompt.clone(), ctx) {
+                return;
+            }
+        }
+
+        self.input_buffer.clear();
+        self.send_user_prompt(prompt);
+        ctx.request_repaint();
+    }
 
+    fn send_user_prompt(&mut self, prompt: String) {
         let message = Message {
             role: "user".to_string(),
             content: prompt.clone(),
             timestamp: Self::timestamp(),
+            tool: None,
         };
 
         self.transcript.push(message.clone());
+        let mut retitled = false;
+        let mut first_message_in: Option<String> = None;
         if let Some(meta) = self.current_session.as_mut() {
+            let is_first_user_message = !meta.messages.iter().any(|message| message.role == "user");
+            if is_first_user_message {
+                first_message_in = Some(meta.session_id.clone());
+            }
+            if is_first_user_message && meta.has_default_title() {
+                if let Some(title) = title_from_prompt(&prompt) {
+                    meta.title = Some(title);
+                    retitled = true;
+                }
+            }
             meta.messages.push(message);
         }
+        if retitled {
+            self.flush_current_session();
+            self.refresh_sessions();
+        } else {
+            self.persist_current_session();
+        }
+        if let Some(session_id) = first_message_in {
+            self.reopen_offer = None;
+            self.remember_last_session(&session_id);
+        }
+
+        self.in_progress_reasoning.clear();
+        self.copilot.send(prompt);
+        self.awaiting_assistant_turn = true;
+        self.cancel_requested = false;
+        self.scroll_to_bottom = true;
+    }
+
+    fn start_editing_message(&mut self, index: usize) {
+        let Some(message) = self.transcript.get(index) else {
+            return;
+        };
+        if message.role != "user" {
+            return;
+        }
+        self.input_buffer = message.content.clone();
+        self.editing_message_index = Some(index);
+    }
+
+    /// Rewrites the user message at `index`, drops everything after it, and sends
+    /// the edited prompt. Returns `false` when `index` no longer names a user turn.
+    fn resubmit_edited_message(
+        &mut self,
+        index: usize,
+        prompt: String,
+        ctx: &egui::Context,
+    ) -> bool {
+        if self
+            .transcript
+            .get(index)
+            .map(|message| message.role.as_str())
+            != Some("user")
+        {
+            return false;
+        }
+
+        let timestamp = Self::timestamp();
+        truncate_transcript_after(&mut self.transcript, index);
+        self.expanded_messages.retain(|expanded| *expanded < index);
+        self.transcript[index].content = prompt.clone();
+        self.transcript[index].timestamp = timestamp.clone();
+        if let Some(meta) = self.current_session.as_mut() {
+            if truncate_transcript_after(&mut meta.messages, index) {
+                meta.messages[index].content = prompt.clone();
+                meta.messages[index].timestamp = timestamp;
+            }
+        }
+        self.in_progress_assistant.clear();
+        self.in_progress_reasoning.clear();
+        self.is_streaming = false;
+        self.pending_canvas_renders.clear();
         self.persist_current_session();
 
         self.copilot.send(prompt);
         self.awaiting_assistant_turn = true;
+        self.cancel_requested = false;
         self.input_buffer.clear();
         self.scroll_to_bottom = true;
         ctx.request_repaint();
+        true
+    }
+
+    fn regenerate_last_response(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = trim_for_regenerate(&mut self.transcript) else {
+            return;
+        };
+        let kept = self.transcript.len();
+        self.expanded_messages.retain(|expanded| *expanded < kept);
+        if let Some(meta) = self.current_session.as_mut() {
+            trim_for_regenerate(&mut meta.messages);
+        }
+        self.persist_current_session();
+        self.in_progress_reasoning.clear();
+
+        self.copilot.send(prompt);
+        self.awaiting_assistant_turn = true;
+        self.cancel_requested = false;
+        self.scroll_to_bottom = true;
+        ctx.request_repaint();
+    }
+
+    /// Shows `template_id` in the preview slot; the canvas and session stay untouched.
+    fn open_template_preview(&mut self, template_id: &str) {
+        let preview = template_preview(&self.catalog(), template_id);
+        match preview {
+            Ok(preview) => self.template_preview = Some(preview),
+            Err(err) => {
+                self.template_preview = None;
+                self.log_diagnostic(
+                    DiagnosticLevel::Warn,
+                    format!("template {template_id} could not be previewed: {err}"),
+                );
+            }
+        }
     }
 
     fn clear_canvas_intent(&mut self) {
         self.active_intent = None;
         self.selected_template = None;
+        self.last_resolution_trace = None;
         self.no_matching_template = false;
         self.pending_provisional_template = None;
         self.canvas_blocks.clear();
         self.active_block_id = None;
+        self.publish_canvas_state();
     }
 
     fn now_millis() -> u128 {
@@ -361,7 +1608,7 @@ impl BrownieApp {
         let mut blocks = Vec::with_capacity(self.canvas_blocks.len());
         for block in &self.canvas_blocks {
             let mut state = block.state.clone();
-            state.form_state = block.ui_runtime.form_state_snapshot();
+            state.form_state = block.form_state_snapshot();
             blocks.push(state);
         }
         CanvasWorkspaceState {
@@ -370,41 +1617,103 @@ impl BrownieApp {
         }
     }
 
+    /// Shares the open blocks with the tool handler and the assistant's context.
+    fn publish_canvas_state(&mut self) {
+        if let Some(summaries) = publish_canvas_snapshot(&self.canvas_snapshot, &self.canvas_blocks)
+        {
+            self.copilot
+                .update_context(canvas_context_summary(&summaries));
+        }
+    }
+
+    /// Schedules a write of the current session; see `persist_session_if_due`.
     fn persist_current_session(&mut self) {
+        self.publish_canvas_state();
+        self.session_persist.mark_dirty();
+    }
+
+    /// Writes the current session immediately, dropping any pending scheduled write.
+    fn flush_current_session(&mut self) {
         let snapshot = self.snapshot_canvas_workspace();
         if let Some(meta) = self.current_session.as_mut() {
             meta.canvas_workspace = snapshot;
-            if let Err(err) = store::save(meta) {
-                self.log_diagnostic(format!("failed to persist session: {err}"));
+            if let Err(err) = self.session_writer.save_now(meta) {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to persist session: {err}"),
+                );
             }
         }
+        self.index_current_session_for_search();
+        self.session_persist.mark_written(Instant::now());
     }
 
-    fn restore_canvas_workspace(&mut self, workspace: &CanvasWorkspaceState) {
-        self.canvas_blocks.clear();
-        self.canvas_event_log = UiEventLog::default();
+    /// Serializes the current session here and writes it on the tokio runtime;
+    /// failures come back as `AppEvent::SessionWriteFailed`.
+    fn write_current_session_in_background(&mut self) {
+        let snapshot = self.snapshot_canvas_workspace();
+        if let Some(meta) = self.current_session.as_mut() {
+            meta.canvas_workspace = snapshot;
+            self.session_writer.save_in_background(meta);
+        }
+        self.index_current_session_for_search();
+        self.session_persist.mark_written(Instant::now());
+    }
+
+    fn persist_session_if_due(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        if self.session_persist.is_due(now, SESSION_PERSIST_INTERVAL) {
+            self.write_current_session_in_background();
+        } else if let Some(wait) = self.session_persist.wait_for(now, SESSION_PERSIST_INTERVAL) {
+            ctx.request_repaint_after(wait);
+        }
+    }
+
+    /// Rebuilds the canvas from `workspace`, the saved canvas of `session_id`.
+    fn restore_canvas_workspace(
+        &mut self,
+        session_id: Option<&str>,
+        workspace: &CanvasWorkspaceState,
+    ) {
+        // Reopening the current session reuses its runtimes by block id, so an unchanged
+        // schema is not re-validated; another session's block ids mean nothing here.
+        let same_session = session_id.is_some()
+            && self
+                .current_session
+                .as_ref()
+                .map(|session| session.session_id.as_str())
+                == session_id;
+        let mut previous: HashMap<String, UiRuntime> = self
+            .canvas_blocks
+            .drain(..)
+            .filter(|_| same_session)
+            .map(|block| (block.state.block_id, block.ui_runtime))
+            .collect();
+        self.maximized_block_id = None;
+        self.canvas_event_log.clear();
         self.active_block_id = workspace.active_block_id.clone();
 
+        let event_log_max_entries = self.settings.canvas_event_log_max_entries;
         for state in &workspace.blocks {
-            let mut runtime = UiRuntime::new();
-            let mut synced_event_count = 0usize;
-            if let Err(err) = runtime.load_schema_value(&state.schema) {
-                self.log_diagnostic(format!(
-                    "failed to restore canvas block {}: {err}",
-                    state.block_id
-                ));
-            } else {
-                runtime.restore_form_state(state.form_state.clone());
-                synced_event_count = runtime.event_log().len();
-            }
-
-            let touched = Self::now_millis();
-            self.canvas_blocks.push(CanvasBlock {
+            let runtime = previous
+                .remove(&state.block_id)
+                .unwrap_or_else(|| UiRuntime::with_event_log_max_entries(event_log_max_entries));
+            let mut block = CanvasBlock {
                 state: state.clone(),
+                synced_event_count: runtime.event_log_total(),
                 ui_runtime: runtime,
-                synced_event_count,
-                last_touched_at: touched,
-            });
+                last_touched_at: Self::now_millis(),
+            };
+            // Minimized blocks get their runtime when they are expanded.
+            if state.minimized {
+                block.release_runtime();
+            } else if let Err(err) = block.load_runtime() {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to restore canvas block {}: {err}", state.block_id),
+                );
+            }
+            self.canvas_blocks.push(block);
         }
 
         if self.active_block_index().is_none() {
@@ -424,6 +1733,7 @@ impl BrownieApp {
         self.block_nonce = highest_nonce;
 
         self.sync_active_selection_context();
+        self.publish_canvas_state();
     }
 
     fn emit_canvas_lifecycle(
@@ -442,6 +1752,7 @@ impl BrownieApp {
             message: message.clone(),
         });
 
+        let level = canvas_lifecycle_level(status);
         let mut line = format!(
             "canvas lifecycle action={:?} actor={:?} status={:?} block_id={}",
             action,
@@ -452,7 +1763,7 @@ impl BrownieApp {
         if let Some(message) = message {
             line.push_str(&format!(" message={}", message.replace('\n', " ")));
         }
-        self.log_diagnostic(line);
+        self.log_diagnostic(level, line);
     }
 
     fn resolve_canvas_for_intent(
@@ -462,10 +1773,11 @@ impl BrownieApp {
         target_block_id: Option<String>,
     ) {
         self.active_intent = Some(intent.clone());
-        let resolution = self.catalog_manager.resolve(&intent);
+        let resolution = self.catalog().resolve(&intent);
         for line in resolution.trace.diagnostic_lines() {
-            self.log_diagnostic(line);
+            self.log_diagnostic(DiagnosticLevel::Info, line);
         }
+        self.last_resolution_trace = Some(resolution.trace.clone());
 
         if let Some(template) = resolution.selected {
             self.no_matching_template = false;
@@ -479,6 +1791,7 @@ impl BrownieApp {
 
             let schema = self.materialize_template_schema(
                 template.document.meta.id.as_str(),
+                template.source.kind.as_str(),
                 template.schema_value(),
                 None,
             );
@@ -491,6 +1804,7 @@ impl BrownieApp {
                 schema,
                 actor,
                 target_block_id,
+                None,
             );
         } else {
             self.selected_template = None;
@@ -516,6 +1830,7 @@ impl BrownieApp {
         schema: Value,
         actor: CanvasBlockActor,
         target_block_id: Option<String>,
+        target_title: Option<String>,
     ) {
         enum UpdateTarget {
             Existing(usize),
@@ -540,6 +1855,33 @@ impl BrownieApp {
                     return;
                 }
             }
+        } else if let Some(target_title) = target_title {
+            match resolve_block_target_by_title(&self.canvas_blocks, &target_title) {
+                BlockTargetResolution::Existing(index) => UpdateTarget::Existing(index),
+                BlockTargetResolution::NotFound => {
+                    self.emit_canvas_lifecycle(
+                        CanvasBlockActionType::Update,
+                        actor,
+                        CanvasBlockActionStatus::Failed,
+                        None,
+                        Some(format!("target title '{target_title}' not found")),
+                    );
+                    return;
+                }
+                BlockTargetResolution::Ambiguous(block_ids) => {
+                    self.emit_canvas_lifecycle(
+                        CanvasBlockActionType::Update,
+                        actor,
+                        CanvasBlockActionStatus::Failed,
+                        None,
+                        Some(format!(
+                            "ambiguous target title; specify block_id (candidates: {})",
+                            block_ids.join(", ")
+                        )),
+                    );
+                    return;
+                }
+            }
         } else {
             match self.resolve_target_block(&template_id) {
                 BlockTargetResolution::Existing(index) => UpdateTarget::Existing(index),
@@ -591,7 +1933,8 @@ impl BrownieApp {
             self.canvas_blocks[index].state.intent = intent;
             self.canvas_blocks[index].state.minimized = false;
             self.canvas_blocks[index].last_touched_at = Self::now_millis();
-            self.canvas_blocks[index].synced_event_count = 0;
+            self.canvas_blocks[index].synced_event_count =
+                self.canvas_blocks[index].ui_runtime.event_log_total();
             self.active_block_id = Some(self.canvas_blocks[index].state.block_id.clone());
             self.sync_active_selection_context();
             self.persist_current_session();
@@ -613,7 +1956,8 @@ impl BrownieApp {
             Some(format!("template_id={template_id}")),
         );
 
-        let mut runtime = UiRuntime::new();
+        let mut runtime =
+            UiRuntime::with_event_log_max_entries(self.settings.canvas_event_log_max_entries);
         if let Err(err) = runtime.load_schema_value(&schema) {
             self.emit_canvas_lifecycle(
                 CanvasBlockActionType::Open,
@@ -712,6 +2056,20 @@ impl BrownieApp {
             );
             return;
         };
+        if !minimized {
+            if let Some(index) = self
+                .canvas_blocks
+                .iter()
+                .position(|block| block.state.block_id == block_id)
+            {
+                if let Err(err) = self.canvas_blocks[index].load_runtime() {
+                    self.log_diagnostic(
+                        DiagnosticLevel::Error,
+                        format!("failed to expand canvas block {block_id}: {err}"),
+                    );
+                }
+            }
+        }
 
         self.persist_current_session();
         self.emit_canvas_lifecycle(
@@ -727,75 +2085,359 @@ impl BrownieApp {
         );
     }
 
-    fn close_block(&mut self, block_id: &str, actor: CanvasBlockActor) {
-        self.emit_canvas_lifecycle(
-            CanvasBlockActionType::Close,
-            actor,
-            CanvasBlockActionStatus::Requested,
-            Some(block_id.to_string()),
-            None,
-        );
+    fn minimize_all_blocks(&mut self) {
+        let actor = CanvasBlockActor::User;
+        let minimized = apply_minimize_all_transition(&mut self.canvas_blocks, Self::now_millis());
+        if minimized.is_empty() {
+            return;
+        }
+        for block_id in minimized {
+            self.emit_canvas_lifecycle(
+                CanvasBlockActionType::Minimize,
+                actor,
+                CanvasBlockActionStatus::Requested,
+                Some(block_id.clone()),
+                None,
+            );
+            self.emit_canvas_lifecycle(
+                CanvasBlockActionType::Minimize,
+                actor,
+                CanvasBlockActionStatus::Succeeded,
+                Some(block_id),
+                Some("minimized".to_string()),
+            );
+        }
+        self.persist_current_session();
+    }
 
-        if !apply_close_transition(&mut self.canvas_blocks, &mut self.active_block_id, block_id) {
+    fn close_all_blocks(&mut self) {
+        let actor = CanvasBlockActor::User;
+        let closed = apply_close_all_transition(&mut self.canvas_blocks, &mut self.active_block_id);
+        if closed.is_empty() {
+            return;
+        }
+        self.maximized_block_id = None;
+        for block_id in closed {
             self.emit_canvas_lifecycle(
                 CanvasBlockActionType::Close,
                 actor,
-                CanvasBlockActionStatus::Failed,
-                Some(block_id.to_string()),
-                Some("block not found".to_string()),
+                CanvasBlockActionStatus::Requested,
+                Some(block_id.clone()),
+                None,
+            );
+            self.emit_canvas_lifecycle(
+                CanvasBlockActionType::Close,
+                actor,
+                CanvasBlockActionStatus::Succeeded,
+                Some(block_id),
+                None,
             );
-            return;
         }
-
         self.sync_active_selection_context();
         self.persist_current_session();
+    }
+
+    fn duplicate_block(&mut self, block_id: &str) {
+        let actor = CanvasBlockActor::User;
         self.emit_canvas_lifecycle(
-            CanvasBlockActionType::Close,
+            CanvasBlockActionType::Open,
             actor,
-            CanvasBlockActionStatus::Succeeded,
-            Some(block_id.to_string()),
+            CanvasBlockActionStatus::Requested,
             None,
+            Some(format!("duplicate_of={block_id}")),
         );
-    }
 
-    fn save_pending_provisional_template(&mut self) {
-        let Some(template) = self.pending_provisional_template.clone() else {
+        let Some(index) = self
+            .canvas_blocks
+            .iter()
+            .position(|block| block.state.block_id == block_id)
+        else {
+            self.emit_canvas_lifecycle(
+                CanvasBlockActionType::Open,
+                actor,
+                CanvasBlockActionStatus::Failed,
+                None,
+                Some(format!("duplicate source {block_id} not found")),
+            );
             return;
         };
 
-        match self.catalog_manager.upsert_user_template(&template) {
-            Ok(()) => {
-                self.log_diagnostic(format!(
-                    "saved provisional template to user catalog: {}",
-                    template.meta.id
-                ));
-                self.pending_provisional_template = None;
-                let intent = UiIntent::new(
-                    template.match_rules.primary,
+        let new_block_id = self.next_block_id();
+        match duplicate_canvas_block(
+            &self.canvas_blocks[index],
+            new_block_id.clone(),
+            Self::now_millis(),
+        ) {
+            Ok(block) => {
+                self.canvas_blocks.insert(index + 1, block);
+                self.active_block_id = Some(new_block_id.clone());
+                self.sync_active_selection_context();
+                self.persist_current_session();
+                self.emit_canvas_lifecycle(
+                    CanvasBlockActionType::Open,
+                    actor,
+                    CanvasBlockActionStatus::Succeeded,
+                    Some(new_block_id),
+                    Some(format!("duplicate_of={block_id}")),
+                );
+            }
+            Err(err) => {
+                self.emit_canvas_lifecycle(
+                    CanvasBlockActionType::Open,
+                    actor,
+                    CanvasBlockActionStatus::Failed,
+                    None,
+                    Some(err),
+                );
+            }
+        }
+    }
+
+    fn close_block(&mut self, block_id: &str, actor: CanvasBlockActor) {
+        self.emit_canvas_lifecycle(
+            CanvasBlockActionType::Close,
+            actor,
+            CanvasBlockActionStatus::Requested,
+            Some(block_id.to_string()),
+            None,
+        );
+
+        if !apply_close_transition(&mut self.canvas_blocks, &mut self.active_block_id, block_id) {
+            self.emit_canvas_lifecycle(
+                CanvasBlockActionType::Close,
+                actor,
+                CanvasBlockActionStatus::Failed,
+                Some(block_id.to_string()),
+                Some("block not found".to_string()),
+            );
+            return;
+        }
+        retain_maximized_block(&mut self.maximized_block_id, &self.canvas_blocks);
+
+        self.sync_active_selection_context();
+        self.persist_current_session();
+        self.emit_canvas_lifecycle(
+            CanvasBlockActionType::Close,
+            actor,
+            CanvasBlockActionStatus::Succeeded,
+            Some(block_id.to_string()),
+            None,
+        );
+    }
+
+    /// Every canvas block with a loaded schema as one standalone HTML page.
+    fn export_canvas_html(&self) -> String {
+        // Minimized blocks hold no runtime, so their schema is validated just for the export.
+        let released = self
+            .canvas_blocks
+            .iter()
+            .map(|block| {
+                block.state.minimized.then(|| {
+                    let mut runtime = UiRuntime::new();
+                    let _ = runtime.load_schema_value(&block.state.schema);
+                    runtime
+                })
+            })
+            .collect::<Vec<_>>();
+        let form_states = self
+            .canvas_blocks
+            .iter()
+            .map(CanvasBlock::form_state_snapshot)
+            .collect::<Vec<_>>();
+        let blocks = self
+            .canvas_blocks
+            .iter()
+            .zip(&released)
+            .zip(&form_states)
+            .filter_map(|((block, released), form_state)| {
+                let runtime = released.as_ref().unwrap_or(&block.ui_runtime);
+                Some(ExportBlock {
+                    title: &block.state.title,
+                    template_id: &block.state.template_id,
+                    schema: runtime.validated_schema()?,
+                    form_state,
+                })
+            })
+            .collect::<Vec<_>>();
+        canvas_html(&blocks)
+    }
+
+    /// Writes `export_canvas_html` to `path`, resolved against the workspace when relative.
+    fn export_canvas_to(&mut self, path: &str) {
+        let target = self.workspace.join(path.trim());
+        let result = write_canvas_export(&target, &self.export_canvas_html());
+        match result {
+            Ok(()) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!("exported canvas to {}", target.display()),
+                );
+                self.export_canvas_path = None;
+            }
+            Err(err) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to export canvas to {}: {err}", target.display()),
+                );
+            }
+        }
+    }
+
+    fn start_save_block_as_template(&mut self, block_id: &str) {
+        let Some(block) = self
+            .canvas_blocks
+            .iter()
+            .find(|block| block.state.block_id == block_id)
+        else {
+            return;
+        };
+        self.save_template_draft = Some(SaveTemplateDraft {
+            block_id: block_id.to_string(),
+            title: block.state.title.clone(),
+            template_id: sanitize_template_id(&format!("user.{}", block.state.intent.primary)),
+        });
+    }
+
+    fn save_block_as_template(&mut self) {
+        let Some(draft) = self.save_template_draft.clone() else {
+            return;
+        };
+        let Some(block) = self
+            .canvas_blocks
+            .iter()
+            .find(|block| block.state.block_id == draft.block_id)
+        else {
+            self.save_template_draft = None;
+            return;
+        };
+
+        let template = template_from_block(&block.state, &draft.title, &draft.template_id);
+        if self.catalog().find_by_id(&template.meta.id).is_some() {
+            self.log_diagnostic(
+                DiagnosticLevel::Warn,
+                format!(
+                    "template {} already exists; choose another id",
+                    template.meta.id
+                ),
+            );
+            return;
+        }
+        let saved = self.catalog().upsert_user_template(&template);
+        match saved {
+            Ok(()) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!(
+                        "saved block {} as user template: {}",
+                        draft.block_id, template.meta.id
+                    ),
+                );
+                self.save_template_draft = None;
+            }
+            Err(err) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to save block as template: {err}"),
+                );
+            }
+        }
+    }
+
+    fn delete_user_template(&mut self, template_id: &str) {
+        let deleted = self.catalog().delete_user_template(template_id);
+        match deleted {
+            Ok(()) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!("deleted user template: {template_id}"),
+                );
+            }
+            Err(err) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to delete template {template_id}: {err}"),
+                );
+            }
+        }
+    }
+
+    fn save_pending_provisional_template(&mut self) {
+        let Some(template) = self.pending_provisional_template.clone() else {
+            return;
+        };
+
+        let saved = self.catalog().upsert_user_template(&template);
+        match saved {
+            Ok(()) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!(
+                        "saved provisional template to user catalog: {}",
+                        template.meta.id
+                    ),
+                );
+                self.pending_provisional_template = None;
+                let intent = UiIntent::new(
+                    template.match_rules.primary,
                     template.match_rules.operations,
                     template.match_rules.tags,
                 );
                 self.resolve_canvas_for_intent(intent, CanvasBlockActor::System, None);
             }
             Err(err) => {
-                self.log_diagnostic(format!("failed to save provisional template: {err}"));
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to save provisional template: {err}"),
+                );
             }
         }
     }
 
+    /// Fills the builtin file listing's tree with `root_path` (the workspace by default).
+    /// Every other template's schema is returned unchanged.
     fn materialize_template_schema(
-        &self,
+        &mut self,
         template_id: &str,
+        provider_kind: &str,
         schema: &Value,
         root_path: Option<&str>,
     ) -> Value {
-        if template_id != "builtin.file_listing.default" {
+        if !is_workspace_explorer_template(template_id, provider_kind) {
             return schema.clone();
         }
 
         let mut materialized = schema.clone();
-        let listing = self.file_explorer_listing(root_path);
+        let root = self.file_explorer_root_path(root_path);
         let root_label = self.file_explorer_root_label(root_path);
+        let show_metadata = materialized
+            .get("components")
+            .and_then(|value| value.as_array())
+            .and_then(|components| {
+                components.iter().find(|component| {
+                    component.get("id").and_then(|value| value.as_str())
+                        == Some(WORKSPACE_TREE_COMPONENT_ID)
+                })
+            })
+            .and_then(|component| component.get("show_metadata"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        let (nodes, read_error) = match build_file_tree(
+            &root,
+            &self.workspace,
+            self.settings.explorer_max_depth.clamp(1, MAX_TREE_DEPTH),
+            FILE_TREE_MAX_ENTRIES,
+            &self.settings.skip_dirs,
+            show_metadata,
+        ) {
+            Ok(nodes) => (nodes, None),
+            Err(err) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("file explorer cannot read {}: {err}", root.display()),
+                );
+                (Vec::new(), Some(err.to_string()))
+            }
+        };
         if let Some(components) = materialized
             .get_mut("components")
             .and_then(|value| value.as_array_mut())
@@ -803,30 +2445,35 @@ impl BrownieApp {
             components.retain(|component| {
                 matches!(
                     component.get("id").and_then(|value| value.as_str()),
-                    Some("explorer_intro") | Some("workspace_tree")
+                    Some("explorer_intro") | Some(WORKSPACE_TREE_COMPONENT_ID)
                 )
             });
             for component in components {
-                let is_workspace_tree = component
-                    .get("id")
-                    .and_then(|value| value.as_str())
-                    .map(|id| id == "workspace_tree")
-                    .unwrap_or(false);
-                let is_intro = component
+                let id = component
                     .get("id")
                     .and_then(|value| value.as_str())
-                    .map(|id| id == "explorer_intro")
-                    .unwrap_or(false);
-                if is_workspace_tree {
-                    if let Some(code) = component.get_mut("code") {
-                        *code = Value::String(listing.clone());
+                    .unwrap_or_default()
+                    .to_string();
+                if id == WORKSPACE_TREE_COMPONENT_ID {
+                    if let Some(object) = component.as_object_mut() {
+                        object.remove("code");
+                        object.remove("language");
+                        object.insert("kind".to_string(), Value::String("tree".to_string()));
+                        object.insert(
+                            "nodes".to_string(),
+                            serde_json::to_value(&nodes).unwrap_or(Value::Array(Vec::new())),
+                        );
                     }
                 }
-                if is_intro {
+                if id == "explorer_intro" {
                     if let Some(text) = component.get_mut("text") {
+                        let status = read_error
+                            .as_deref()
+                            .map(|err| format!("\nFailed to read root: {err}"))
+                            .unwrap_or_default();
                         *text = Value::String(
                             format!(
-                                "### File Explorer\nRoot: `{root_label}`\nPersistent session block. Use focus/minimize/close controls."
+                                "### File Explorer\nRoot: `{root_label}`\nPersistent session block. Use focus/minimize/close controls.{status}"
                             ),
                         );
                     }
@@ -850,64 +2497,358 @@ impl BrownieApp {
         }
     }
 
+    /// Renders a workspace file into the shared file-preview code block.
+    fn open_file_in_canvas(&mut self, relative_path: &str) {
+        let code = match resolve_workspace_file(&self.workspace, relative_path)
+            .and_then(|path| read_file_preview(&path, FILE_PREVIEW_MAX_BYTES))
+        {
+            Ok(code) => code,
+            Err(err) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("cannot open {relative_path}: {err}"),
+                );
+                return;
+            }
+        };
+        let schema = serde_json::json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "file_contents",
+                "kind": "code",
+                "language": language_for_path(Path::new(relative_path)),
+                "code": code
+            }]
+        });
+        self.apply_canvas_block_from_schema(
+            UiIntent::new(
+                "file_preview",
+                vec!["view".to_string()],
+                vec!["files".to_string()],
+            ),
+            FILE_PREVIEW_TEMPLATE_ID.to_string(),
+            relative_path.to_string(),
+            "workspace".to_string(),
+            "workspace".to_string(),
+            schema,
+            CanvasBlockActor::User,
+            None,
+            None,
+        );
+    }
+
     fn file_explorer_root_label(&self, root_path: Option<&str>) -> String {
         self.file_explorer_root_path(root_path)
             .display()
             .to_string()
     }
 
-    fn file_explorer_listing(&self, root_path: Option<&str>) -> String {
-        let root = self.file_explorer_root_path(root_path);
-        let root_name = root
-            .file_name()
-            .and_then(|value| value.to_str())
-            .map(ToOwned::to_owned)
-            .unwrap_or_else(|| root.display().to_string());
-
-        let mut entries = Vec::new();
-        match fs::read_dir(&root) {
-            Ok(read_dir) => {
-                for entry in read_dir.flatten() {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    let is_dir = entry
-                        .file_type()
-                        .map(|value| value.is_dir())
-                        .unwrap_or(false);
-                    entries.push((name, is_dir));
-                }
+    fn rename_session(&mut self, session_id: &str, title: &str) {
+        let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+        if title.is_empty() {
+            return;
+        }
+        let is_active = self
+            .current_session
+            .as_ref()
+            .is_some_and(|session| session.session_id == session_id);
+        if is_active {
+            if let Some(session) = self.current_session.as_mut() {
+                session.title = Some(title);
+            }
+            self.flush_current_session();
+        } else if let (Some(mut session), _) = store::load_one(session_id) {
+            session.title = Some(title);
+            if let Err(err) = self.session_writer.save_now(&session) {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to rename session: {err}"),
+                );
+            }
+        }
+        self.refresh_sessions();
+    }
+
+    fn persist_window_geometry(&mut self, ctx: &egui::Context) {
+        let (inner_rect, outer_rect) = ctx.input(|input| {
+            let viewport = input.viewport();
+            (viewport.inner_rect, viewport.outer_rect)
+        });
+        let Some(inner_rect) = inner_rect else {
+            return;
+        };
+        self.settings.window = WindowGeometry {
+            inner_size: [inner_rect.width(), inner_rect.height()],
+            position: outer_rect.map(|rect| [rect.min.x, rect.min.y]),
+        };
+        self.save_settings();
+    }
+
+    /// Moves a window restored off-screen back onto its monitor, once the monitor
+    /// size is known.
+    fn keep_window_on_screen(&mut self, ctx: &egui::Context) {
+        if self.window_position_checked {
+            return;
+        }
+        let (outer_rect, monitor_size) = ctx.input(|input| {
+            let viewport = input.viewport();
+            (viewport.outer_rect, viewport.monitor_size)
+        });
+        let (Some(outer_rect), Some(monitor_size)) = (outer_rect, monitor_size) else {
+            return;
+        };
+        self.window_position_checked = true;
+        let position = [outer_rect.min.x, outer_rect.min.y];
+        let geometry = WindowGeometry {
+            inner_size: [outer_rect.width(), outer_rect.height()],
+            position: Some(position),
+        };
+        let Some(on_screen) = geometry.position_on_monitor([monitor_size.x, monitor_size.y]) else {
+            return;
+        };
+        if on_screen != position {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(
+                on_screen[0],
+                on_screen[1],
+            )));
+            self.log_diagnostic(
+                DiagnosticLevel::Info,
+                format!(
+                    "window was off-screen at {:?}; moved to {:?}",
+                    position, on_screen
+                ),
+            );
+        }
+    }
+
+    fn adjust_font_scale(&mut self, step: f32) {
+        let previous = self.theme.font_scale;
+        self.theme.set_font_scale(previous + step);
+        if self.theme.font_scale == previous {
+            return;
+        }
+        self.settings.font_scale = self.theme.font_scale;
+        self.save_settings();
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(err) = settings::save(&settings::config_path(), &self.settings) {
+            self.log_diagnostic(
+                DiagnosticLevel::Error,
+                format!("failed to save settings: {err}"),
+            );
+        }
+    }
+
+    fn toggle_session_mode(&mut self) {
+        let mode = match self.session_mode {
+            SessionMode::Passive => SessionMode::Active,
+            SessionMode::Active => SessionMode::Passive,
+        };
+        if self.session_persist.dirty {
+            self.flush_current_session();
+        }
+        self.deny_pending_permissions();
+        self.session_mode = mode;
+        self.log_diagnostic(
+            DiagnosticLevel::Info,
+            format!("session mode changed: {}", mode.label()),
+        );
+        self.copilot.set_mode(mode);
+    }
+
+    fn resolve_permission(&mut self, approved: bool) {
+        let Some(request) = self.pending_permissions.pop_front() else {
+            return;
+        };
+        self.copilot
+            .resolve_permission(&request.tool_call_id, approved);
+        self.log_diagnostic(
+            DiagnosticLevel::Info,
+            format!(
+                "tool permission {} tool={} id={}",
+                if approved { "allowed" } else { "denied" },
+                request.tool_name,
+                request.tool_call_id
+            ),
+        );
+    }
+
+    fn deny_pending_permissions(&mut self) {
+        while !self.pending_permissions.is_empty() {
+            self.resolve_permission(false);
+        }
+    }
+
+    fn discard_queued_prompts(&mut self, reason: &str) {
+        let Some(note) = discard_queued_prompts(&mut self.prompt_queue, reason, Self::timestamp())
+        else {
+            return;
+        };
+        self.transcript.push(note.clone());
+        if let Some(meta) = self.current_session.as_mut() {
+            meta.messages.push(note);
+        }
+        self.persist_current_session();
+        self.scroll_to_bottom = true;
+    }
+
+    fn push_tool_chip(&mut self, tool_name: &str, status: ToolStatus) {
+        let message = tool_chip_message(tool_name, status, Self::timestamp());
+        self.transcript.push(message.clone());
+        if let Some(meta) = self.current_session.as_mut() {
+            meta.messages.push(message);
+        }
+        self.persist_current_session();
+        self.scroll_to_bottom = true;
+    }
+
+    /// Asks the SDK to abort the turn. Deltas are dropped from here on, and whichever
+    /// of `StreamEnd` or `ResponseCancelled` arrives first closes the turn as cancelled.
+    fn cancel_turn(&mut self) {
+        self.cancel_requested = true;
+        self.copilot.cancel();
+    }
+
+    /// Commits the partial reply with the cancelled marker and drops the rest of the turn.
+    fn finish_cancelled_turn(&mut self, ctx: Option<&egui::Context>) {
+        let partial = std::mem::take(&mut self.in_progress_assistant);
+        if let Some(message) = cancelled_assistant_message(&partial, Self::timestamp()) {
+            self.transcript.push(message.clone());
+            if let Some(meta) = self.current_session.as_mut() {
+                meta.messages.push(message);
+            }
+            self.persist_current_session();
+        }
+
+        self.log_diagnostic(
+            DiagnosticLevel::Info,
+            "assistant response cancelled by user",
+        );
+        self.is_streaming = false;
+        self.awaiting_assistant_turn = false;
+        self.discard_queued_prompts("the response was cancelled");
+        if !self.pending_canvas_renders.is_empty() {
+            self.log_diagnostic(
+                DiagnosticLevel::Info,
+                "dropped deferred canvas renders for cancelled turn",
+            );
+            self.pending_canvas_renders.clear();
+        }
+        self.scroll_to_bottom = true;
+        if let Some(ctx) = ctx {
+            ctx.request_repaint();
+        }
+    }
+
+    fn start_new_session(&mut self) {
+        if self.session_persist.dirty {
+            self.flush_current_session();
+        }
+        // The client aborts the running turn before it creates the new session.
+        let cancel_turn = self.is_streaming || self.awaiting_assistant_turn;
+        self.cancel_requested = cancel_turn;
+        self.renaming_session = None;
+        self.log_diagnostic(DiagnosticLevel::Info, "new session requested");
+        self.copilot.new_session(cancel_turn);
+    }
+
+    /// Imports a session file from `path`, resolved against the workspace when relative.
+    fn import_session_from(&mut self, path: &str) {
+        let source = self.workspace.join(path.trim());
+        match store::import(&source) {
+            Ok(session) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!(
+                        "imported session id={} from {}",
+                        session.session_id,
+                        source.display()
+                    ),
+                );
+                self.import_session_path = None;
+                self.refresh_sessions();
             }
             Err(err) => {
-                return format!("{root_name}/\n└── <failed to read root: {err}>");
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to import session: {err}"),
+                );
             }
         }
+    }
 
-        entries.sort_by(|left, right| left.0.cmp(&right.0));
-        let mut lines = vec![format!("{root_name}/")];
-        for (index, (name, is_dir)) in entries.iter().enumerate() {
-            let branch = if index + 1 == entries.len() {
-                "└──"
-            } else {
-                "├──"
-            };
-            let suffix = if *is_dir { "/" } else { "" };
-            lines.push(format!("{branch} {name}{suffix}"));
+    fn delete_session(&mut self, session_id: &str) {
+        if let Err(err) = self.session_writer.delete(session_id) {
+            self.log_diagnostic(
+                DiagnosticLevel::Error,
+                format!("failed to delete session {session_id}: {err}"),
+            );
+            return;
+        }
+        let was_active = self
+            .current_session
+            .as_ref()
+            .is_some_and(|session| session.session_id == session_id);
+        if was_active {
+            self.current_session = None;
+            self.transcript.clear();
+            self.expanded_messages.clear();
+            self.restore_canvas_workspace(None, &CanvasWorkspaceState::default());
+            self.editing_message_index = None;
+            self.prompt_queue.clear();
+        }
+        if self
+            .renaming_session
+            .as_ref()
+            .is_some_and(|(renaming_id, _)| renaming_id == session_id)
+        {
+            self.renaming_session = None;
         }
+        if self
+            .reopen_offer
+            .as_ref()
+            .is_some_and(|offer| offer.session_id == session_id)
+        {
+            self.reopen_offer = None;
+        }
+        self.log_diagnostic(
+            DiagnosticLevel::Info,
+            format!("session deleted id={session_id}"),
+        );
+        self.refresh_sessions();
+    }
 
-        lines.join("\n")
+    fn remember_last_session(&mut self, session_id: &str) {
+        if self.settings.last_session_id.as_deref() == Some(session_id) {
+            return;
+        }
+        self.settings.last_session_id = Some(session_id.to_string());
+        self.save_settings();
     }
 
     fn open_session(&mut self, session_id: &str) {
+        if self.session_persist.dirty {
+            self.flush_current_session();
+        }
+        self.reopen_offer = None;
+        self.editing_message_index = None;
+        self.prompt_queue.clear();
         let (session, warning) = store::load_one(session_id);
         if let Some(warning) = warning {
             self.apply_event(AppEvent::SdkError(warning), None);
         }
 
         if let Some(session) = session {
+            self.remember_last_session(&session.session_id);
             self.transcript = session.messages.clone();
-            self.restore_canvas_workspace(&session.canvas_workspace);
+            self.expanded_messages.clear();
+            self.restore_canvas_workspace(Some(&session.session_id), &session.canvas_workspace);
             self.current_session = Some(session);
             self.is_streaming = false;
             self.in_progress_assistant.clear();
+            self.in_progress_reasoning.clear();
             self.scroll_to_bottom = true;
             self.session_unavailable = false;
             self.awaiting_assistant_turn = false;
@@ -915,7 +2856,7 @@ impl BrownieApp {
         } else {
             self.session_unavailable = true;
             self.clear_canvas_intent();
-            self.canvas_event_log = UiEventLog::default();
+            self.canvas_event_log.clear();
             self.awaiting_assistant_turn = false;
             self.pending_canvas_renders.clear();
         }
@@ -928,10 +2869,12 @@ impl BrownieApp {
     ) {
         self.active_intent = Some(request.intent.clone());
         self.no_matching_template = false;
+        self.canvas_not_rendered = false;
         self.pending_provisional_template = request.provisional_template;
 
         let schema = self.materialize_template_schema(
             &request.template_id,
+            &request.provider_kind,
             &request.schema,
             request.root_path.as_deref(),
         );
@@ -944,6 +2887,7 @@ impl BrownieApp {
             schema,
             CanvasBlockActor::Assistant,
             request.target_block_id,
+            request.target_title,
         );
         if let Some(ctx) = ctx {
             ctx.request_repaint();
@@ -963,7 +2907,7 @@ impl BrownieApp {
                 Ok(event) => self.apply_event(event, Some(ctx)),
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => {
-                    self.log_diagnostic("event channel disconnected");
+                    self.log_diagnostic(DiagnosticLevel::Error, "event channel disconnected");
                     break;
                 }
             }
@@ -973,6 +2917,9 @@ impl BrownieApp {
     fn apply_event(&mut self, event: AppEvent, ctx: Option<&egui::Context>) {
         match event {
             AppEvent::StreamDelta(text) => {
+                if self.cancel_requested {
+                    return;
+                }
                 self.in_progress_assistant.push_str(&text);
                 self.is_streaming = true;
                 self.scroll_to_bottom = true;
@@ -980,12 +2927,28 @@ impl BrownieApp {
                     ctx.request_repaint();
                 }
             }
+            AppEvent::ReasoningDelta(text) => {
+                if self.cancel_requested {
+                    return;
+                }
+                self.in_progress_reasoning.push_str(&text);
+                self.scroll_to_bottom = true;
+                if let Some(ctx) = ctx {
+                    ctx.request_repaint();
+                }
+            }
             AppEvent::StreamEnd => {
+                // An abort ends the stream before `ResponseCancelled` arrives.
+                if std::mem::take(&mut self.cancel_requested) {
+                    self.finish_cancelled_turn(ctx);
+                    return;
+                }
                 if !self.in_progress_assistant.is_empty() {
                     let message = Message {
                         role: "assistant".to_string(),
                         content: std::mem::take(&mut self.in_progress_assistant),
                         timestamp: Self::timestamp(),
+                        tool: None,
                     };
                     self.transcript.push(message.clone());
                     if let Some(meta) = self.current_session.as_mut() {
@@ -997,33 +2960,83 @@ impl BrownieApp {
                 self.is_streaming = false;
                 self.awaiting_assistant_turn = false;
                 self.flush_pending_canvas_renders(ctx);
+                if self.session_persist.dirty {
+                    self.flush_current_session();
+                }
+                // Cancelled turns return above, so the queue is never drained into a
+                // send that a late `ResponseCancelled` would mute.
+                let turn_in_flight = self.is_streaming || self.awaiting_assistant_turn;
+                if let Some(prompt) = next_queued_prompt(&mut self.prompt_queue, turn_in_flight) {
+                    self.send_user_prompt(prompt);
+                }
                 self.scroll_to_bottom = true;
                 if let Some(ctx) = ctx {
                     ctx.request_repaint();
                 }
             }
+            AppEvent::ResponseCancelled => {
+                // Already closed by `StreamEnd` when the cancel flag is gone.
+                if std::mem::take(&mut self.cancel_requested) {
+                    self.finish_cancelled_turn(ctx);
+                }
+            }
+            AppEvent::CancelFailed(err) => {
+                // The turn keeps running, so its `StreamEnd` must close it normally.
+                self.cancel_requested = false;
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to cancel response: {err}"),
+                );
+            }
             AppEvent::StatusChanged(state) => {
                 self.connection_state = state;
-                self.log_diagnostic(format!(
-                    "connection state changed: {}",
-                    Self::connection_state_name(state)
-                ));
+                self.log_diagnostic(
+                    connection_state_level(state),
+                    format!(
+                        "connection state changed: {}",
+                        Self::connection_state_name(state)
+                    ),
+                );
             }
             AppEvent::SdkError(message) => {
-                self.log_diagnostic(format!("sdk error: {message}"));
+                self.log_diagnostic(DiagnosticLevel::Error, format!("sdk error: {message}"));
+                let turn_pending = self.is_streaming || self.awaiting_assistant_turn;
+                if let Some(error) = turn_error_message(turn_pending, &message, Self::timestamp()) {
+                    self.transcript.push(error.clone());
+                    if let Some(meta) = self.current_session.as_mut() {
+                        meta.messages.push(error);
+                    }
+                    self.persist_current_session();
+                    self.scroll_to_bottom = true;
+                    self.discard_queued_prompts("the response failed");
+                }
+                self.in_progress_assistant.clear();
                 self.is_streaming = false;
                 self.awaiting_assistant_turn = false;
                 self.flush_pending_canvas_renders(ctx);
             }
+            AppEvent::CatalogRemoteLoaded(load) => {
+                if self.catalog().apply_remote_load(load) {
+                    self.log_catalog_reload();
+                }
+            }
+            AppEvent::SessionWriteFailed(err) => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("failed to persist session: {err}"),
+                );
+            }
             AppEvent::SessionCreated(session_id) => {
+                if self.session_persist.dirty {
+                    self.flush_current_session();
+                }
+                self.deny_pending_permissions();
+                self.canvas_not_rendered = false;
                 let meta = SessionMeta {
                     schema_version: SCHEMA_VERSION,
                     session_id: session_id.clone(),
                     workspace: self.workspace.to_string_lossy().to_string(),
-                    title: Some(format!(
-                        "Session {}",
-                        session_id.chars().take(8).collect::<String>()
-                    )),
+                    title: Some(default_session_title(&session_id)),
                     created_at: Self::timestamp(),
                     canvas_workspace: CanvasWorkspaceState::default(),
                     messages: Vec::new(),
@@ -1031,22 +3044,89 @@ impl BrownieApp {
 
                 self.current_session = Some(meta.clone());
                 self.transcript.clear();
+                self.expanded_messages.clear();
+                self.editing_message_index = None;
+                self.prompt_queue.clear();
                 self.in_progress_assistant.clear();
+                self.in_progress_reasoning.clear();
                 self.is_streaming = false;
                 self.session_unavailable = false;
                 self.awaiting_assistant_turn = false;
                 self.pending_canvas_renders.clear();
                 self.clear_canvas_intent();
-                self.canvas_event_log = UiEventLog::default();
+                reset_canvas_for_new_session(
+                    &mut self.canvas_blocks,
+                    &mut self.active_block_id,
+                    &mut self.canvas_event_log,
+                );
+                self.maximized_block_id = None;
+                self.sync_active_selection_context();
 
-                if let Err(err) = store::save(&meta) {
-                    self.log_diagnostic(format!("failed to persist new session: {err}"));
+                if let Err(err) = self.session_writer.save_now(&meta) {
+                    self.log_diagnostic(
+                        DiagnosticLevel::Error,
+                        format!("failed to persist new session: {err}"),
+                    );
                 }
+                self.session_persist.mark_written(Instant::now());
 
                 self.refresh_sessions();
             }
+            AppEvent::SessionReconnected(session_id) => {
+                if self.current_session.is_none() {
+                    self.apply_event(AppEvent::SessionCreated(session_id), ctx);
+                    return;
+                }
+                // Prompts raised by the dropped session can no longer be answered.
+                self.deny_pending_permissions();
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!("reconnected with SDK session {session_id}; conversation kept"),
+                );
+            }
+            AppEvent::ToolPermissionRequested {
+                tool_call_id,
+                tool_name,
+                details,
+            } => {
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!("tool permission requested tool={tool_name} id={tool_call_id}"),
+                );
+                self.pending_permissions.push_back(PendingPermission {
+                    tool_call_id,
+                    tool_name,
+                    details,
+                });
+                if let Some(ctx) = ctx {
+                    ctx.request_repaint();
+                }
+            }
+            AppEvent::ToolPermissionTimedOut { tool_call_id } => {
+                if let Some(request) =
+                    remove_pending_permission(&mut self.pending_permissions, &tool_call_id)
+                {
+                    self.log_diagnostic(
+                        DiagnosticLevel::Warn,
+                        format!(
+                            "tool permission timed out and was denied tool={} id={}",
+                            request.tool_name, request.tool_call_id
+                        ),
+                    );
+                }
+                if let Some(ctx) = ctx {
+                    ctx.request_repaint();
+                }
+            }
+            AppEvent::IntentAlternatives(note) => {
+                self.log_diagnostic(DiagnosticLevel::Info, note);
+            }
             AppEvent::ToolCallSuppressed(tool_name) => {
-                self.log_diagnostic(format!("tool call suppressed (passive mode): {tool_name}"));
+                self.log_diagnostic(
+                    DiagnosticLevel::Info,
+                    format!("tool call suppressed (passive mode): {tool_name}"),
+                );
+                self.push_tool_chip(&tool_name, ToolStatus::Suppressed);
             }
             AppEvent::ToolExecutionOutcome {
                 tool_name,
@@ -1054,14 +3134,19 @@ impl BrownieApp {
                 message,
             } => {
                 let mut diagnostic = format!("tool outcome tool={} status={}", tool_name, status);
-                if tool_name == "query_ui_catalog" && (status == "text_only" || status == "error") {
+                let raises_canvas_warning =
+                    canvas_warning_after_outcome(false, &tool_name, &status);
+                if raises_canvas_warning {
                     diagnostic.push_str(" canvas_not_rendered=true");
                 }
                 if let Some(message) = message {
                     let compact = message.replace('\n', " ");
                     diagnostic.push_str(&format!(" message={compact}"));
                 }
-                self.log_diagnostic(diagnostic);
+                self.log_diagnostic(tool_outcome_level(&status), diagnostic);
+                self.canvas_not_rendered =
+                    canvas_warning_after_outcome(self.canvas_not_rendered, &tool_name, &status);
+                self.push_tool_chip(&tool_name, status);
             }
             AppEvent::CanvasToolRender {
                 intent,
@@ -1070,6 +3155,7 @@ impl BrownieApp {
                 provider_id,
                 provider_kind,
                 target_block_id,
+                target_title,
                 root_path,
                 schema,
                 provisional_template,
@@ -1081,18 +3167,96 @@ impl BrownieApp {
                     provider_id,
                     provider_kind,
                     target_block_id,
+                    target_title,
                     root_path,
                     schema,
                     provisional_template,
                 };
                 if self.awaiting_assistant_turn || self.is_streaming {
-                    self.log_diagnostic("deferred canvas render until assistant turn completed");
+                    self.log_diagnostic(
+                        DiagnosticLevel::Info,
+                        "deferred canvas render until assistant turn completed",
+                    );
                     self.pending_canvas_renders.push(request);
                 } else {
                     self.apply_canvas_render_request(request, ctx);
                 }
             }
+            AppEvent::CanvasToolFocus {
+                target_block_id,
+                target_title,
+            } => {
+                if let Some(block_id) = self.resolve_assistant_block_target(
+                    CanvasBlockActionType::Focus,
+                    target_block_id,
+                    target_title,
+                ) {
+                    self.focus_block(&block_id, CanvasBlockActor::Assistant);
+                }
+            }
+            AppEvent::CanvasToolMinimize {
+                target_block_id,
+                target_title,
+            } => {
+                if let Some(block_id) = self.resolve_assistant_block_target(
+                    CanvasBlockActionType::Minimize,
+                    target_block_id,
+                    target_title,
+                ) {
+                    let already_minimized = self
+                        .canvas_blocks
+                        .iter()
+                        .any(|block| block.state.block_id == block_id && block.state.minimized);
+                    if !already_minimized {
+                        self.toggle_minimize_block(&block_id, CanvasBlockActor::Assistant);
+                    }
+                }
+            }
+            AppEvent::CanvasToolClose {
+                target_block_id,
+                target_title,
+            } => {
+                if let Some(block_id) = self.resolve_assistant_block_target(
+                    CanvasBlockActionType::Close,
+                    target_block_id,
+                    target_title,
+                ) {
+                    self.close_block(&block_id, CanvasBlockActor::Assistant);
+                }
+            }
+        }
+    }
+
+    /// Block id named by an assistant block operation, by id or else by title.
+    /// Title lookups that miss or match several blocks are reported as failures.
+    fn resolve_assistant_block_target(
+        &mut self,
+        action: CanvasBlockActionType,
+        target_block_id: Option<String>,
+        target_title: Option<String>,
+    ) -> Option<String> {
+        if let Some(block_id) = target_block_id {
+            return Some(block_id);
         }
+        let title = target_title?;
+        let message = match resolve_block_target_by_title(&self.canvas_blocks, &title) {
+            BlockTargetResolution::Existing(index) => {
+                return Some(self.canvas_blocks[index].state.block_id.clone());
+            }
+            BlockTargetResolution::NotFound => format!("target title '{title}' not found"),
+            BlockTargetResolution::Ambiguous(block_ids) => format!(
+                "ambiguous target title; specify block_id (candidates: {})",
+                block_ids.join(", ")
+            ),
+        };
+        self.emit_canvas_lifecycle(
+            action,
+            CanvasBlockActor::Assistant,
+            CanvasBlockActionStatus::Failed,
+            None,
+            Some(message),
+        );
+        None
     }
 
     fn render_top_bar(&mut self, ctx: &egui::Context) {
@@ -1103,6 +3267,9 @@ impl BrownieApp {
                 self.theme.spacing_8 as i8,
             ))
             .fill(self.theme.surface_1);
+        let mut toggle_mode = false;
+        let mut font_step: Option<f32> = None;
+        let mut reconnect = false;
 
         egui::TopBottomPanel::top("top_bar")
             .exact_height(44.0)
@@ -1125,7 +3292,7 @@ impl BrownieApp {
                     columns[0].with_layout(egui::Layout::left_to_right(Align::Center), |ui| {
                         ui.label(
                             RichText::new("Brownie")
-                                .size(14.0)
+                                .size(self.theme.text_size(14.0))
                                 .color(self.theme.text_primary),
                         );
                     });
@@ -1134,26 +3301,129 @@ impl BrownieApp {
                         egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                         |ui| {
                             ui.horizontal(|ui| {
-                                ui.label(RichText::new("●").color(status_color).size(10.0));
+                                ui.label(
+                                    RichText::new("●")
+                                        .color(status_color)
+                                        .size(self.theme.text_size(10.0)),
+                                );
                                 ui.label(
                                     RichText::new(status_label)
-                                        .size(13.0)
+                                        .size(self.theme.text_size(13.0))
                                         .color(self.theme.text_primary),
                                 );
+                                if matches!(
+                                    self.connection_state,
+                                    ConnectionState::Error | ConnectionState::Disconnected
+                                ) && ui
+                                    .add(self.secondary_button("Reconnect"))
+                                    .on_hover_text("Restart the Copilot client and session")
+                                    .clicked()
+                                {
+                                    reconnect = true;
+                                }
                             });
                         },
                     );
 
                     columns[2].with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
-                        ui.add_enabled(false, self.secondary_button("Active Mode"));
+                        let (toggle_label, mode_color) = match self.session_mode {
+                            SessionMode::Passive => ("Active Mode", self.theme.success),
+                            SessionMode::Active => ("Passive Mode", self.theme.warning),
+                        };
+                        let toggle_hint = match self.session_mode {
+                            SessionMode::Passive if !self.settings.active_excluded_tools.is_empty() => {
+                                format!(
+                                    "Recreates the session with the other tool policy; still excluded: {}",
+                                    self.settings.active_excluded_tools.join(", ")
+                                )
+                            }
+                            _ => "Recreates the session with the other tool policy".to_string(),
+                        };
+                        if ui
+                            .add(self.secondary_button(toggle_label))
+                            .on_hover_text(toggle_hint)
+                            .clicked()
+                        {
+                            toggle_mode = true;
+                        }
                         ui.label(
-                            RichText::new("Passive Mode")
-                                .size(12.0)
-                                .color(self.theme.success),
+                            RichText::new(self.session_mode.label())
+                                .size(self.theme.text_size(12.0))
+                                .color(mode_color),
                         );
+                        if ui.small_button("A+").on_hover_text("Larger text").clicked() {
+                            font_step = Some(Theme::FONT_SCALE_STEP);
+                        }
+                        if ui
+                            .small_button("A-")
+                            .on_hover_text("Smaller text")
+                            .clicked()
+                        {
+                            font_step = Some(-Theme::FONT_SCALE_STEP);
+                        }
                     });
                 });
             });
+        if toggle_mode {
+            self.toggle_session_mode();
+        }
+        if let Some(step) = font_step {
+            self.adjust_font_scale(step);
+        }
+        if reconnect {
+            self.log_diagnostic(DiagnosticLevel::Info, "manual reconnect requested");
+            self.copilot.restart();
+        }
+    }
+
+    fn render_permission_dialog(&mut self, ctx: &egui::Context) {
+        let Some(request) = self.pending_permissions.front() else {
+            return;
+        };
+        let mut decision: Option<bool> = None;
+        egui::Window::new("Tool Permission")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .frame(self.theme.card_frame())
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("Copilot wants to run `{}`", request.tool_name))
+                        .strong()
+                        .size(self.theme.text_size(14.0))
+                        .color(self.theme.text_primary),
+                );
+                if !request.details.is_empty() {
+                    ui.label(
+                        RichText::new(&request.details)
+                            .size(self.theme.text_size(12.0))
+                            .monospace()
+                            .color(self.theme.text_muted),
+                    );
+                }
+                if self.pending_permissions.len() > 1 {
+                    ui.label(
+                        RichText::new(format!(
+                            "{} more request(s) waiting",
+                            self.pending_permissions.len() - 1
+                        ))
+                        .size(self.theme.text_size(12.0))
+                        .color(self.theme.text_muted),
+                    );
+                }
+                ui.add_space(Theme::P8);
+                ui.horizontal(|ui| {
+                    if ui.add(self.primary_button("Allow")).clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.add(self.secondary_button("Deny")).clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+        if let Some(approved) = decision {
+            self.resolve_permission(approved);
+        }
     }
 
     fn render_left_panel(&mut self, ctx: &egui::Context) {
@@ -1168,14 +3438,14 @@ impl BrownieApp {
                 ui.label(
                     RichText::new("Workspace")
                         .strong()
-                        .size(16.0)
+                        .size(self.theme.text_size(16.0))
                         .color(self.theme.text_primary),
                 );
 
                 self.theme.card_frame().show(ui, |ui| {
                     ui.label(
                         RichText::new(self.workspace.display().to_string())
-                            .size(12.0)
+                            .size(self.theme.text_size(12.0))
                             .color(self.theme.text_muted),
                     );
                 });
@@ -1184,31 +3454,101 @@ impl BrownieApp {
                     ui.label(
                         RichText::new("Copilot Instructions")
                             .strong()
-                            .size(14.0)
+                            .size(self.theme.text_size(14.0))
                             .color(self.theme.text_primary),
                     );
                     ui.add_space(Theme::P8);
                     if self.instruction_files.is_empty() {
                         ui.label(
                             RichText::new("No instruction files detected")
-                                .size(12.0)
+                                .size(self.theme.text_size(12.0))
                                 .color(self.theme.text_muted),
                         );
                     } else {
                         for path in &self.instruction_files {
-                            ui.label(RichText::new(path).size(12.0).color(self.theme.text_muted));
+                            ui.label(
+                                RichText::new(path)
+                                    .size(self.theme.text_size(12.0))
+                                    .color(self.theme.text_muted),
+                            );
                         }
                     }
                 });
 
                 ui.add_space(Theme::P8);
-                ui.label(
-                    RichText::new("Recent Sessions")
-                        .strong()
-                        .size(14.0)
-                        .color(self.theme.text_primary),
+                let mut new_session = false;
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new("Recent Sessions")
+                            .strong()
+                            .size(self.theme.text_size(14.0))
+                            .color(self.theme.text_primary),
+                    );
+                    ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                        if ui
+                            .small_button("New Session")
+                            .on_hover_text("Start a fresh Copilot session")
+                            .clicked()
+                        {
+                            new_session = true;
+                        }
+                        if ui
+                            .small_button("Import Session")
+                            .on_hover_text("Add a session from an exported JSON file")
+                            .clicked()
+                        {
+                            self.import_session_path.get_or_insert_with(String::new);
+                        }
+                    });
+                });
+                if new_session {
+                    self.start_new_session();
+                }
+                let mut confirm_import = false;
+                let mut cancel_import = false;
+                let import_button = self.primary_button("Import");
+                let cancel_import_button = self.secondary_button("Cancel");
+                if let Some(path) = &mut self.import_session_path {
+                    self.theme.card_frame().show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(path)
+                                .hint_text("Session JSON path, relative to the workspace")
+                                .desired_width(f32::INFINITY),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(!path.trim().is_empty(), import_button)
+                                .clicked()
+                            {
+                                confirm_import = true;
+                            }
+                            if ui.add(cancel_import_button).clicked() {
+                                cancel_import = true;
+                            }
+                        });
+                    });
+                }
+                if confirm_import {
+                    if let Some(path) = self.import_session_path.clone() {
+                        self.import_session_from(&path);
+                    }
+                } else if cancel_import {
+                    self.import_session_path = None;
+                }
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.session_search)
+                        .hint_text("Search sessions")
+                        .desired_width(f32::INFINITY),
                 );
+                let search_hits = self.session_search_hits();
+                if self.session_search_loading.is_some() {
+                    ctx.request_repaint_after(Duration::from_millis(100));
+                }
                 let mut clicked_session: Option<String> = None;
+                let mut start_rename: Option<(String, String)> = None;
+                let mut commit_rename = false;
+                let mut cancel_rename = false;
+                let mut delete_session: Option<String> = None;
                 let active_session_id = self
                     .current_session
                     .as_ref()
@@ -1221,11 +3561,36 @@ impl BrownieApp {
                         .max_height(sessions_height)
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
+                            let mut shown = 0usize;
                             for session in &self.sessions {
+                                if search_hits
+                                    .as_ref()
+                                    .is_some_and(|hits| !hits.contains(&session.session_id))
+                                {
+                                    continue;
+                                }
+                                shown += 1;
                                 let label = session
                                     .title
                                     .clone()
                                     .unwrap_or_else(|| session.session_id.clone());
+                                if let Some((renaming_id, draft)) = self.renaming_session.as_mut() {
+                                    if *renaming_id == session.session_id {
+                                        let edit = ui.add(
+                                            egui::TextEdit::singleline(draft)
+                                                .desired_width(f32::INFINITY),
+                                        );
+                                        if !edit.has_focus() && !edit.lost_focus() {
+                                            edit.request_focus();
+                                        }
+                                        if ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+                                            cancel_rename = true;
+                                        } else if edit.lost_focus() {
+                                            commit_rename = true;
+                                        }
+                                        continue;
+                                    }
+                                }
                                 let is_active = active_session_id
                                     .map(|current| current == &session.session_id)
                                     .unwrap_or(false);
@@ -1235,16 +3600,29 @@ impl BrownieApp {
                                 } else {
                                     self.theme.surface_2
                                 };
-                                let button = egui::Button::new(
-                                    RichText::new(label)
-                                        .size(13.0)
-                                        .color(self.theme.text_primary),
-                                )
-                                .fill(base_fill)
-                                .stroke(Stroke::NONE)
-                                .corner_radius(egui::CornerRadius::same(self.theme.radius_10))
-                                .min_size(egui::vec2(ui.available_width(), 34.0));
-                                let response = ui.add(button);
+                                let response = ui
+                                    .with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                                        if ui
+                                            .small_button("x")
+                                            .on_hover_text("Delete session")
+                                            .clicked()
+                                        {
+                                            delete_session = Some(session.session_id.clone());
+                                        }
+                                        let button = egui::Button::new(
+                                            RichText::new(label.clone())
+                                                .size(self.theme.text_size(13.0))
+                                                .color(self.theme.text_primary),
+                                        )
+                                        .fill(base_fill)
+                                        .stroke(Stroke::NONE)
+                                        .corner_radius(egui::CornerRadius::same(
+                                            self.theme.radius_10,
+                                        ))
+                                        .min_size(egui::vec2(ui.available_width(), 34.0));
+                                        ui.add(button)
+                                    })
+                                    .inner;
 
                                 if !is_active && response.hovered() {
                                     ui.painter().rect_filled(
@@ -1268,22 +3646,48 @@ impl BrownieApp {
                                     );
                                 }
 
-                                if response.clicked() {
+                                let response = response.on_hover_text("Double-click to rename");
+                                if response.double_clicked() {
+                                    start_rename = Some((session.session_id.clone(), label));
+                                } else if response.clicked() {
                                     clicked_session = Some(session.session_id.clone());
                                 }
                             }
+                            if shown == 0 && !self.sessions.is_empty() {
+                                ui.label(
+                                    RichText::new("No sessions match the search")
+                                        .size(self.theme.text_size(12.0))
+                                        .color(self.theme.text_muted),
+                                );
+                            }
                         });
                 });
 
-                if let Some(session_id) = clicked_session {
+                if cancel_rename {
+                    self.renaming_session = None;
+                } else if commit_rename {
+                    if let Some((session_id, draft)) = self.renaming_session.take() {
+                        self.rename_session(&session_id, &draft);
+                    }
+                }
+                if let Some(session_id) = delete_session {
+                    self.delete_session(&session_id);
+                } else if let Some(rename) = start_rename {
+                    self.renaming_session = Some(rename);
+                } else if let Some(session_id) = clicked_session {
                     self.open_session(&session_id);
                 }
             });
     }
 
     fn render_right_panel(&mut self, ctx: &egui::Context) {
-        egui::SidePanel::right("actions_panel")
-            .resizable(true)
+        let mut panel = egui::SidePanel::right("actions_panel").resizable(true);
+        if self.maximized_block_id.is_some() {
+            // Leave the chat a narrow strip; the maximized block takes the rest.
+            let width = (ctx.available_rect().width() - MAXIMIZED_CHAT_MIN_WIDTH).max(320.0);
+            panel = panel.resizable(false).exact_width(width);
+        }
+        panel
             .frame(
                 self.theme
                     .panel_frame(self.theme.surface_1, self.theme.spacing_24 as i8),
@@ -1293,16 +3697,64 @@ impl BrownieApp {
                 ui.label(
                     RichText::new("Canvas")
                         .strong()
-                        .size(16.0)
+                        .size(self.theme.text_size(16.0))
                         .color(self.theme.text_primary),
                 );
 
+                if self.canvas_not_rendered {
+                    let mut dismiss = false;
+                    Frame::new()
+                        .fill(self.theme.surface_2)
+                        .stroke(Stroke::new(1.0, self.theme.warning))
+                        .corner_radius(egui::CornerRadius::same(self.theme.radius_10))
+                        .inner_margin(egui::Margin::same(self.theme.spacing_8 as i8))
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(
+                                        "Canvas was not rendered; see chat for text fallback",
+                                    )
+                                    .size(self.theme.text_size(12.0))
+                                    .color(self.theme.warning),
+                                );
+                                ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                                    if ui
+                                        .small_button("Dismiss")
+                                        .on_hover_text("Hide this warning")
+                                        .clicked()
+                                    {
+                                        dismiss = true;
+                                    }
+                                });
+                            });
+                        });
+                    if dismiss {
+                        self.canvas_not_rendered = false;
+                    }
+                }
+
                 let mut focus_block: Option<String> = None;
                 let mut toggle_block: Option<String> = None;
                 let mut close_block: Option<String> = None;
-                let mut new_events: Vec<UiEvent> = Vec::new();
+                let mut move_block: Option<(usize, usize)> = None;
+                let mut maximize_block: Option<String> = None;
+                let mut duplicate_block: Option<String> = None;
+                let mut minimize_all = false;
+                let mut close_all = false;
+                let mut confirm_export = false;
+                let mut cancel_export = false;
+                let mut preview_template: Option<String> = None;
+                let mut reload_catalog = false;
+                let mut close_preview = false;
+                let mut new_events: Vec<UiEventEntry> = Vec::new();
+                let mut opened_files: Vec<String> = Vec::new();
                 let mut save_provisional = false;
                 let mut dismiss_provisional = false;
+                let mut save_block_template: Option<String> = None;
+                let mut confirm_save_template = false;
+                let mut cancel_save_template = false;
+                let mut delete_template: Option<String> = None;
+                let mut run_playground = false;
 
                 ScrollArea::vertical()
                     .id_salt("canvas_panel_scroll")
@@ -1312,7 +3764,7 @@ impl BrownieApp {
                             egui::CollapsingHeader::new(
                                 RichText::new("Selection Context")
                                     .strong()
-                                    .size(14.0)
+                                    .size(self.theme.text_size(14.0))
                                     .color(self.theme.text_primary),
                             )
                             .id_salt("selection_context")
@@ -1324,16 +3776,23 @@ impl BrownieApp {
                                         Some(intent) => format!("Intent: {}", intent.summary()),
                                         None => "Intent: none".to_string(),
                                     })
-                                    .size(12.0)
+                                    .size(self.theme.text_size(12.0))
                                     .color(self.theme.text_muted),
                                 );
                                 if let Some(selection) = &self.selected_template {
+                                    let version = self
+                                        .catalog()
+                                        .find_by_id(&selection.template_id)
+                                        .map(|template| {
+                                            format!(" v{}", template.document.meta.version)
+                                        })
+                                        .unwrap_or_default();
                                     ui.label(
                                         RichText::new(format!(
-                                            "Template: {} ({})",
-                                            selection.title, selection.template_id
+                                            "Template: {} ({}){}",
+                                            selection.title, selection.template_id, version
                                         ))
-                                        .size(13.0)
+                                        .size(self.theme.text_size(13.0))
                                         .color(self.theme.text_primary),
                                     );
                                     ui.label(
@@ -1341,38 +3800,103 @@ impl BrownieApp {
                                             "Source: {} [{}]",
                                             selection.provider_id, selection.provider_kind
                                         ))
-                                        .size(12.0)
+                                        .size(self.theme.text_size(12.0))
                                         .color(self.theme.text_muted),
                                     );
                                 }
+                                if let Some(trace) = &self.last_resolution_trace {
+                                    ui.add_space(Theme::P8);
+                                    egui::CollapsingHeader::new(
+                                        RichText::new("Resolution Trace")
+                                            .size(self.theme.text_size(13.0))
+                                            .color(self.theme.text_primary),
+                                    )
+                                    .id_salt("resolution_trace")
+                                    .default_open(false)
+                                    .show(ui, |ui| {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Precedence: {}",
+                                                trace
+                                                    .provider_precedence
+                                                    .iter()
+                                                    .map(|kind| kind.as_str())
+                                                    .collect::<Vec<_>>()
+                                                    .join(" > ")
+                                            ))
+                                            .size(self.theme.text_size(12.0))
+                                            .color(self.theme.text_muted),
+                                        );
+                                        render_resolution_trace(ui, &self.theme, trace);
+                                    });
+                                }
                             });
                         });
 
                         self.theme.card_frame().show(ui, |ui| {
-                            ui.label(
-                                RichText::new("Workspace Blocks")
-                                    .strong()
-                                    .size(14.0)
-                                    .color(self.theme.text_primary),
-                            );
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new("Workspace Blocks")
+                                        .strong()
+                                        .size(self.theme.text_size(14.0))
+                                        .color(self.theme.text_primary),
+                                );
+                                if !self.canvas_blocks.is_empty() {
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(Align::Center),
+                                        |ui| {
+                                            if ui
+                                                .small_button("Close all")
+                                                .on_hover_text("Close every block")
+                                                .clicked()
+                                            {
+                                                close_all = true;
+                                            }
+                                            if ui
+                                                .small_button("Minimize all")
+                                                .on_hover_text("Minimize every block")
+                                                .clicked()
+                                            {
+                                                minimize_all = true;
+                                            }
+                                            if ui
+                                                .small_button("Export Canvas")
+                                                .on_hover_text(
+                                                    "Save every block as a standalone HTML page",
+                                                )
+                                                .clicked()
+                                            {
+                                                self.export_canvas_path.get_or_insert_with(|| {
+                                                    "brownie-canvas.html".to_string()
+                                                });
+                                            }
+                                        },
+                                    );
+                                }
+                            });
                             ui.add_space(Theme::P8);
                             if self.canvas_blocks.is_empty() {
                                 if self.no_matching_template {
                                     ui.label(
                                         RichText::new("No matching UI template found")
-                                            .size(13.0)
+                                            .size(self.theme.text_size(13.0))
                                             .color(self.theme.danger),
                                     );
                                 } else {
                                     ui.label(
                                         RichText::new("No open Canvas blocks")
-                                            .size(13.0)
+                                            .size(self.theme.text_size(13.0))
                                             .color(self.theme.text_muted),
                                     );
                                 }
                             } else {
                                 for index in 0..self.canvas_blocks.len() {
                                     let block_id = self.canvas_blocks[index].state.block_id.clone();
+                                    let is_maximized = self.maximized_block_id.as_deref()
+                                        == Some(block_id.as_str());
+                                    if self.maximized_block_id.is_some() && !is_maximized {
+                                        continue;
+                                    }
                                     let block_title = self.canvas_blocks[index].state.title.clone();
                                     let provider_id =
                                         self.canvas_blocks[index].state.provider_id.clone();
@@ -1386,7 +3910,7 @@ impl BrownieApp {
                                     } else {
                                         self.theme.border_subtle
                                     };
-                                    Frame::new()
+                                    let block_frame = Frame::new()
                                         .fill(self.theme.surface_2)
                                         .stroke(Stroke::new(1.0, border_color))
                                         .corner_radius(egui::CornerRadius::same(
@@ -1397,12 +3921,27 @@ impl BrownieApp {
                                         ))
                                         .show(ui, |ui| {
                                             ui.horizontal(|ui| {
+                                                ui.dnd_drag_source(
+                                                    egui::Id::new((
+                                                        "canvas_block_drag",
+                                                        block_id.as_str(),
+                                                    )),
+                                                    index,
+                                                    |ui| {
+                                                        ui.label(
+                                                            RichText::new("☰")
+                                                                .size(self.theme.text_size(13.0))
+                                                                .color(self.theme.text_muted),
+                                                        )
+                                                        .on_hover_text("Drag to reorder");
+                                                    },
+                                                );
                                                 ui.label(
                                                     RichText::new(format!(
                                                         "{} ({})",
                                                         block_title, block_id
                                                     ))
-                                                    .size(13.0)
+                                                    .size(self.theme.text_size(13.0))
                                                     .color(self.theme.text_primary),
                                                 );
                                                 ui.with_layout(
@@ -1415,6 +3954,21 @@ impl BrownieApp {
                                                         {
                                                             close_block = Some(block_id.clone());
                                                         }
+                                                        if ui
+                                                            .small_button(if is_maximized {
+                                                                "Restore"
+                                                            } else {
+                                                                "Maximize"
+                                                            })
+                                                            .on_hover_text(if is_maximized {
+                                                                "Return the block to the stack"
+                                                            } else {
+                                                                "Fill the canvas panel with this block"
+                                                            })
+                                                            .clicked()
+                                                        {
+                                                            maximize_block = Some(block_id.clone());
+                                                        }
                                                         if ui
                                                             .small_button(if is_minimized {
                                                                 "+"
@@ -1438,6 +3992,26 @@ impl BrownieApp {
                                                         {
                                                             focus_block = Some(block_id.clone());
                                                         }
+                                                        if ui
+                                                            .small_button("Save as Template")
+                                                            .on_hover_text(
+                                                                "Save this block to your user catalog",
+                                                            )
+                                                            .clicked()
+                                                        {
+                                                            save_block_template =
+                                                                Some(block_id.clone());
+                                                        }
+                                                        if ui
+                                                            .small_button("Duplicate")
+                                                            .on_hover_text(
+                                                                "Open a copy of this block with its current values",
+                                                            )
+                                                            .clicked()
+                                                        {
+                                                            duplicate_block =
+                                                                Some(block_id.clone());
+                                                        }
                                                     },
                                                 );
                                             });
@@ -1446,86 +4020,406 @@ impl BrownieApp {
                                                     "Source: {} [{}]",
                                                     provider_id, provider_kind
                                                 ))
-                                                .size(12.0)
+                                                .size(self.theme.text_size(12.0))
                                                 .color(self.theme.text_muted),
                                             );
                                             if is_minimized {
                                                 ui.label(
                                                     RichText::new("Block is minimized")
-                                                        .size(12.0)
+                                                        .size(self.theme.text_size(12.0))
                                                         .color(self.theme.text_muted),
                                                 );
                                             } else {
                                                 ui.add_space(Theme::P8);
                                                 let block = &mut self.canvas_blocks[index];
                                                 block.ui_runtime.render_canvas(ui, &self.theme);
-                                                let events = block.ui_runtime.event_log();
-                                                if block.synced_event_count < events.len() {
-                                                    new_events.extend_from_slice(
-                                                        &events[block.synced_event_count..],
-                                                    );
-                                                    block.synced_event_count = events.len();
+                                                let events = block
+                                                    .ui_runtime
+                                                    .events_since(block.synced_event_count);
+                                                if is_workspace_explorer(&block.state) {
+                                                    for entry in events.clone() {
+                                                        if let UiEvent::TreeNodeClicked {
+                                                            component_id,
+                                                            path,
+                                                        } = &entry.event
+                                                        {
+                                                            if component_id
+                                                                == WORKSPACE_TREE_COMPONENT_ID
+                                                            {
+                                                                opened_files.push(path.clone());
+                                                            }
+                                                        }
+                                                    }
                                                 }
+                                                new_events.extend(events.cloned());
+                                                block.synced_event_count =
+                                                    block.ui_runtime.event_log_total();
                                             }
-                                        });
+                                        })
+                                        .response;
+                                    if block_frame.dnd_hover_payload::<usize>().is_some() {
+                                        ui.painter().hline(
+                                            block_frame.rect.x_range(),
+                                            block_frame.rect.top(),
+                                            Stroke::new(2.0, self.theme.accent_primary),
+                                        );
+                                    }
+                                    if let Some(from) = block_frame.dnd_release_payload::<usize>() {
+                                        move_block = Some((*from, index));
+                                    }
                                     ui.add_space(Theme::P8);
                                 }
                             }
                         });
 
-                        if let Some(template) = &self.pending_provisional_template {
+                        if let Some(preview) = &mut self.template_preview {
                             self.theme.card_frame().show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        RichText::new(format!("Preview: {}", preview.title))
+                                            .strong()
+                                            .size(self.theme.text_size(14.0))
+                                            .color(self.theme.text_primary),
+                                    );
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(Align::Center),
+                                        |ui| {
+                                            if ui
+                                                .small_button("Close")
+                                                .on_hover_text("Close the preview")
+                                                .clicked()
+                                            {
+                                                close_preview = true;
+                                            }
+                                        },
+                                    );
+                                });
                                 ui.label(
-                                    RichText::new("Provisional Template")
+                                    RichText::new(format!(
+                                        "{} · not saved to the session",
+                                        preview.template_id
+                                    ))
+                                    .size(self.theme.text_size(11.0))
+                                    .color(self.theme.text_muted),
+                                );
+                                ui.add_space(Theme::P8);
+                                preview.runtime.render_canvas(ui, &self.theme);
+                            });
+                        }
+
+                        let export_button = self.primary_button("Export");
+                        let cancel_export_button = self.secondary_button("Cancel");
+                        if let Some(path) = &mut self.export_canvas_path {
+                            self.theme.card_frame().show(ui, |ui| {
+                                ui.label(
+                                    RichText::new("Export Canvas")
                                         .strong()
-                                        .size(14.0)
+                                        .size(self.theme.text_size(14.0))
                                         .color(self.theme.text_primary),
                                 );
                                 ui.add_space(Theme::P8);
-                                ui.label(
-                                    RichText::new(format!(
-                                        "Save '{}' to your user UI catalog?",
-                                        template.meta.title
-                                    ))
-                                    .size(12.0)
-                                    .color(self.theme.text_muted),
+                                ui.add(
+                                    egui::TextEdit::singleline(path)
+                                        .hint_text("HTML file path, relative to the workspace")
+                                        .desired_width(f32::INFINITY),
                                 );
                                 ui.add_space(Theme::P8);
                                 ui.horizontal(|ui| {
-                                    if ui.add(self.primary_button("Save to Catalog")).clicked() {
-                                        save_provisional = true;
+                                    if ui
+                                        .add_enabled(!path.trim().is_empty(), export_button)
+                                        .clicked()
+                                    {
+                                        confirm_export = true;
                                     }
-                                    if ui.add(self.secondary_button("Not Now")).clicked() {
-                                        dismiss_provisional = true;
+                                    if ui.add(cancel_export_button).clicked() {
+                                        cancel_export = true;
                                     }
                                 });
                             });
                         }
 
-                        self.theme.card_frame().show(ui, |ui| {
-                            egui::CollapsingHeader::new(
-                                RichText::new("UI Event Log")
-                                    .color(self.theme.text_primary)
-                                    .size(13.0),
-                            )
-                            .id_salt("ui_event_log")
-                            .default_open(false)
-                            .show(ui, |ui| {
+                        let save_template_button = self.primary_button("Save Template");
+                        let cancel_template_button = self.secondary_button("Cancel");
+                        if let Some(draft) = &mut self.save_template_draft {
+                            self.theme.card_frame().show(ui, |ui| {
+                                ui.label(
+                                    RichText::new("Save Block as Template")
+                                        .strong()
+                                        .size(self.theme.text_size(14.0))
+                                        .color(self.theme.text_primary),
+                                );
                                 ui.add_space(Theme::P8);
-                                for event in self.canvas_event_log.entries() {
-                                    ui.label(
-                                        RichText::new(event.to_log_line())
-                                            .color(self.theme.text_muted)
-                                            .size(12.0),
-                                    );
+                                ui.label(
+                                    RichText::new(format!("Block: {}", draft.block_id))
+                                        .size(self.theme.text_size(12.0))
+                                        .color(self.theme.text_muted),
+                                );
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut draft.title)
+                                        .hint_text("Template title")
+                                        .desired_width(f32::INFINITY),
+                                );
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut draft.template_id)
+                                        .hint_text("Template id")
+                                        .desired_width(f32::INFINITY),
+                                );
+                                let sanitized_id = sanitize_template_id(&draft.template_id);
+                                let id_taken = self
+                                    .catalog_manager
+                                    .lock()
+                                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                    .find_by_id(&sanitized_id)
+                                    .is_some();
+                                if id_taken {
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "A template with id {sanitized_id} already exists"
+                                        ))
+                                        .size(self.theme.text_size(12.0))
+                                        .color(self.theme.warning),
+                                    );
+                                } else {
+                                    ui.label(
+                                        RichText::new(format!("Saved as: {sanitized_id}"))
+                                            .size(self.theme.text_size(12.0))
+                                            .color(self.theme.text_muted),
+                                    );
+                                }
+                                ui.add_space(Theme::P8);
+                                let can_save = !draft.title.trim().is_empty()
+                                    && !sanitized_id.is_empty()
+                                    && !id_taken;
+                                ui.horizontal(|ui| {
+                                    if ui.add_enabled(can_save, save_template_button).clicked() {
+                                        confirm_save_template = true;
+                                    }
+                                    if ui.add(cancel_template_button).clicked() {
+                                        cancel_save_template = true;
+                                    }
+                                });
+                            });
+                        }
+
+                        if let Some(template) = &self.pending_provisional_template {
+                            self.theme.card_frame().show(ui, |ui| {
+                                ui.label(
+                                    RichText::new("Provisional Template")
+                                        .strong()
+                                        .size(self.theme.text_size(14.0))
+                                        .color(self.theme.text_primary),
+                                );
+                                ui.add_space(Theme::P8);
+                                ui.label(
+                                    RichText::new(format!(
+                                        "Save '{}' to your user UI catalog?",
+                                        template.meta.title
+                                    ))
+                                    .size(self.theme.text_size(12.0))
+                                    .color(self.theme.text_muted),
+                                );
+                                ui.add_space(Theme::P8);
+                                ui.horizontal(|ui| {
+                                    if ui.add(self.primary_button("Save to Catalog")).clicked() {
+                                        save_provisional = true;
+                                    }
+                                    if ui.add(self.secondary_button("Not Now")).clicked() {
+                                        dismiss_provisional = true;
+                                    }
+                                });
+                            });
+                        }
+
+                        self.theme.card_frame().show(ui, |ui| {
+                            egui::CollapsingHeader::new(
+                                RichText::new("UI Event Log")
+                                    .color(self.theme.text_primary)
+                                    .size(self.theme.text_size(13.0)),
+                            )
+                            .id_salt("ui_event_log")
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                ui.add_space(Theme::P8);
+                                for event in self.canvas_event_log.entries() {
+                                    ui.label(
+                                        RichText::new(event.to_log_line())
+                                            .color(self.theme.text_muted)
+                                            .size(self.theme.text_size(12.0)),
+                                    );
+                                }
+                            });
+                        });
+
+                        let resolve_button = self.secondary_button("Resolve");
+                        self.theme.card_frame().show(ui, |ui| {
+                            egui::CollapsingHeader::new(
+                                RichText::new("Resolver Playground")
+                                    .color(self.theme.text_primary)
+                                    .size(self.theme.text_size(13.0)),
+                            )
+                            .id_salt("resolver_playground")
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                ui.add_space(Theme::P8);
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(&mut self.playground_prompt)
+                                        .hint_text("Type a prompt to resolve...")
+                                        .desired_width(f32::INFINITY),
+                                );
+                                let submitted = response.lost_focus()
+                                    && ui.input(|input| input.key_pressed(egui::Key::Enter));
+                                if ui.add(resolve_button).clicked() || submitted {
+                                    run_playground = true;
+                                }
+                                match &self.playground_result {
+                                    Some(PlaygroundResult::NoIntent) => {
+                                        ui.label(
+                                            RichText::new("No UI intent detected")
+                                                .size(self.theme.text_size(12.0))
+                                                .color(self.theme.warning),
+                                        );
+                                    }
+                                    Some(PlaygroundResult::Resolved(intent_trace, trace)) => {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Intent: {}",
+                                                trace.intent.summary()
+                                            ))
+                                            .size(self.theme.text_size(12.0))
+                                            .color(self.theme.text_primary),
+                                        );
+                                        for line in intent_trace
+                                            .iter()
+                                            .cloned()
+                                            .chain(trace.diagnostic_lines())
+                                        {
+                                            ui.label(
+                                                RichText::new(line)
+                                                    .size(self.theme.text_size(12.0))
+                                                    .color(self.theme.text_muted),
+                                            );
+                                        }
+                                        render_resolution_trace(ui, &self.theme, trace);
+                                    }
+                                    None => {}
+                                }
+                            });
+                        });
+
+                        self.theme.card_frame().show(ui, |ui| {
+                            egui::CollapsingHeader::new(
+                                RichText::new("Catalog Health")
+                                    .color(self.theme.text_primary)
+                                    .size(self.theme.text_size(13.0)),
+                            )
+                            .id_salt("catalog_health")
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                ui.add_space(Theme::P8);
+                                if ui
+                                    .small_button("Reload")
+                                    .on_hover_text(
+                                        "Reload local templates now and fetch remote catalogs in the background",
+                                    )
+                                    .clicked()
+                                {
+                                    reload_catalog = true;
+                                }
+                                let provider_health = self.catalog().provider_health();
+                                for health in provider_health {
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "{} [{}] loaded={} rejected={}",
+                                            health.provider_id,
+                                            health.kind,
+                                            health.loaded,
+                                            health.rejected
+                                        ))
+                                        .size(self.theme.text_size(12.0))
+                                        .color(if health.rejected > 0 {
+                                            self.theme.warning
+                                        } else {
+                                            self.theme.text_primary
+                                        }),
+                                    );
+                                    for rejection in &health.rejections {
+                                        ui.label(
+                                            RichText::new(format!("  {rejection}"))
+                                                .size(self.theme.text_size(11.0))
+                                                .color(self.theme.text_muted),
+                                        );
+                                    }
+                                }
+                            });
+                        });
+
+                        self.theme.card_frame().show(ui, |ui| {
+                            egui::CollapsingHeader::new(
+                                RichText::new("Template Catalog")
+                                    .color(self.theme.text_primary)
+                                    .size(self.theme.text_size(13.0)),
+                            )
+                            .id_salt("template_catalog")
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                ui.add_space(Theme::P8);
+                                for template in self.catalog().templates() {
+                                    ui.horizontal(|ui| {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "{} ({})",
+                                                template.document.meta.title,
+                                                template.template_id()
+                                            ))
+                                            .size(self.theme.text_size(12.0))
+                                            .color(self.theme.text_primary),
+                                        );
+                                        ui.with_layout(
+                                            egui::Layout::right_to_left(Align::Center),
+                                            |ui| {
+                                                if !template.source.read_only
+                                                    && ui
+                                                        .small_button("Delete")
+                                                        .on_hover_text("Delete this user template")
+                                                        .clicked()
+                                                {
+                                                    delete_template =
+                                                        Some(template.template_id().to_string());
+                                                }
+                                                if ui
+                                                    .small_button("Preview")
+                                                    .on_hover_text(
+                                                        "Show this template without adding it to the canvas",
+                                                    )
+                                                    .clicked()
+                                                {
+                                                    preview_template =
+                                                        Some(template.template_id().to_string());
+                                                }
+                                            },
+                                        );
+                                    });
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "{} [{}] primary={}",
+                                            template.source.provider_id,
+                                            template.source.kind,
+                                            template.document.match_rules.primary
+                                        ))
+                                        .size(self.theme.text_size(11.0))
+                                        .color(self.theme.text_muted),
+                                    );
                                 }
                             });
                         });
                     });
 
                 let had_new_events = !new_events.is_empty();
-                for event in new_events {
-                    self.canvas_event_log.push(event);
+                for entry in new_events {
+                    self.canvas_event_log.push_entry(entry);
+                }
+                for path in opened_files {
+                    self.open_file_in_canvas(&path);
                 }
                 if had_new_events {
                     self.persist_current_session();
@@ -1540,12 +4434,71 @@ impl BrownieApp {
                 if let Some(block_id) = close_block {
                     self.close_block(&block_id, CanvasBlockActor::User);
                 }
+                if let Some(block_id) = duplicate_block {
+                    self.duplicate_block(&block_id);
+                }
+                if minimize_all {
+                    self.minimize_all_blocks();
+                }
+                if close_all {
+                    self.close_all_blocks();
+                }
+                if reload_catalog {
+                    self.catalog().reload();
+                    self.log_catalog_reload();
+                    let remote = self.copilot.refresh_remote_catalog();
+                    if remote > 0 {
+                        self.log_diagnostic(
+                            DiagnosticLevel::Info,
+                            format!("fetching {remote} remote catalog(s) in the background"),
+                        );
+                    }
+                }
+                if close_preview {
+                    self.template_preview = None;
+                }
+                if let Some(template_id) = preview_template {
+                    self.open_template_preview(&template_id);
+                }
+                if confirm_export {
+                    if let Some(path) = self.export_canvas_path.clone() {
+                        self.export_canvas_to(&path);
+                    }
+                } else if cancel_export {
+                    self.export_canvas_path = None;
+                }
+                if let Some(block_id) = maximize_block {
+                    apply_maximize_toggle(&mut self.maximized_block_id, &block_id);
+                }
+                if let Some((from, to)) = move_block {
+                    if apply_move_transition(&mut self.canvas_blocks, from, to) {
+                        self.persist_current_session();
+                    }
+                }
+
+                if let Some(block_id) = save_block_template {
+                    self.start_save_block_as_template(&block_id);
+                }
+                if confirm_save_template {
+                    self.save_block_as_template();
+                } else if cancel_save_template {
+                    self.save_template_draft = None;
+                }
+
+                if let Some(template_id) = delete_template {
+                    self.delete_user_template(&template_id);
+                }
 
                 if save_provisional {
                     self.save_pending_provisional_template();
                 } else if dismiss_provisional {
                     self.pending_provisional_template = None;
                 }
+
+                if run_playground {
+                    let result = resolve_playground_prompt(&self.catalog(), &self.playground_prompt);
+                    self.playground_result = Some(result);
+                }
             });
     }
 
@@ -1560,27 +4513,173 @@ impl BrownieApp {
                 ui.label(
                     RichText::new("Chat")
                         .strong()
-                        .size(16.0)
+                        .size(self.theme.text_size(16.0))
                         .color(self.theme.text_primary),
                 );
 
+                let mut reopen: Option<String> = None;
+                let mut dismiss_reopen = false;
+                if let (Some(offer), true) = (&self.reopen_offer, self.current_session.is_some()) {
+                    self.theme.card_frame().show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            let title = offer
+                                .title
+                                .clone()
+                                .unwrap_or_else(|| offer.session_id.clone());
+                            ui.label(
+                                RichText::new(format!("Reopen your last session \"{title}\"?"))
+                                    .size(self.theme.text_size(13.0))
+                                    .color(self.theme.text_primary),
+                            );
+                            ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                                if ui.add(self.secondary_button("Dismiss")).clicked() {
+                                    dismiss_reopen = true;
+                                }
+                                if ui.add(self.primary_button("Reopen")).clicked() {
+                                    reopen = Some(offer.session_id.clone());
+                                }
+                            });
+                        });
+                    });
+                }
+                if dismiss_reopen {
+                    self.reopen_offer = None;
+                }
+                if let Some(session_id) = reopen {
+                    self.open_session(&session_id);
+                }
+
+                let focus_find =
+                    ui.input_mut(|input| input.consume_key(egui::Modifiers::COMMAND, egui::Key::F));
+                if focus_find {
+                    self.transcript_query.get_or_insert_with(String::new);
+                }
+                let mut close_find = false;
+                let mut search_matches = Vec::new();
+                if let Some(query) = self.transcript_query.as_mut() {
+                    ui.horizontal(|ui| {
+                        let response = ui.add(
+                            egui::TextEdit::singleline(query)
+                                .hint_text("Find in transcript")
+                                .desired_width(240.0),
+                        );
+                        if focus_find {
+                            response.request_focus();
+                        }
+                        if response.changed() {
+                            self.scroll_to_search_match = true;
+                        }
+                        search_matches = transcript_search(&self.transcript, query);
+                        if !query.trim().is_empty() {
+                            ui.label(
+                                RichText::new(match search_matches.len() {
+                                    1 => "1 match".to_string(),
+                                    count => format!("{count} matches"),
+                                })
+                                .size(self.theme.text_size(12.0))
+                                .color(self.theme.text_muted),
+                            );
+                        }
+                        let escaped = response.lost_focus()
+                            && ui.input(|input| input.key_pressed(egui::Key::Escape));
+                        if ui.small_button("Close").clicked() || escaped {
+                            close_find = true;
+                        }
+                    });
+                }
+                if close_find {
+                    self.transcript_query = None;
+                    search_matches.clear();
+                }
+                let searching = self
+                    .transcript_query
+                    .as_deref()
+                    .is_some_and(|query| !query.trim().is_empty());
+                let scroll_target = if self.scroll_to_search_match {
+                    search_matches.first().copied()
+                } else {
+                    None
+                };
+                if self.scroll_to_search_match && !searching {
+                    // Query cleared: return to the live end of the conversation.
+                    self.scroll_to_bottom = true;
+                }
+                if close_find {
+                    self.scroll_to_bottom = true;
+                }
+
                 let transcript_height = (ui.available_height() - 260.0).max(140.0);
+                let mut regenerate = false;
+                let mut edit_message: Option<usize> = None;
+                let mut toggle_expanded: Option<usize> = None;
+                let (collapse_lines, collapse_chars) = (
+                    self.settings.collapse_message_lines,
+                    self.settings.collapse_message_chars,
+                );
                 ScrollArea::vertical()
                     .id_salt("chat_transcript")
                     .max_height(transcript_height)
-                    .stick_to_bottom(true)
+                    .stick_to_bottom(!searching)
                     .show(ui, |ui| {
                         if self.session_unavailable {
                             ui.label(
                                 RichText::new("Session unavailable")
-                                    .size(12.0)
+                                    .size(self.theme.text_size(12.0))
                                     .color(self.theme.danger),
                             );
                         }
 
                         ui.spacing_mut().item_spacing.y = Theme::P12;
-                        for message in &self.transcript {
+                        let can_regenerate = self.connection_state == ConnectionState::Connected
+                            && !self.is_streaming
+                            && !self.awaiting_assistant_turn;
+                        let last_index = self.transcript.len().saturating_sub(1);
+                        for (index, message) in self.transcript.iter().enumerate() {
+                            if message.role == TOOL_MESSAGE_ROLE {
+                                let (label, color) = tool_chip_style(&self.theme, message);
+                                Frame::new()
+                                    .stroke(Stroke::new(1.0, color))
+                                    .corner_radius(egui::CornerRadius::same(self.theme.radius_10))
+                                    .inner_margin(egui::Margin::symmetric(
+                                        self.theme.spacing_8 as i8,
+                                        2,
+                                    ))
+                                    .show(ui, |ui| {
+                                        ui.label(
+                                            RichText::new(label)
+                                                .size(self.theme.text_size(12.0))
+                                                .color(color),
+                                        )
+                                        .on_hover_text(format_timestamp(&message.timestamp));
+                                    });
+                                continue;
+                            }
+                            if message.role == ERROR_MESSAGE_ROLE {
+                                Frame::new()
+                                    .stroke(Stroke::new(1.0, self.theme.danger))
+                                    .corner_radius(egui::CornerRadius::same(self.theme.radius_12))
+                                    .inner_margin(egui::Margin::same(self.theme.spacing_12 as i8))
+                                    .show(ui, |ui| {
+                                        ui.label(
+                                            RichText::new(&message.content)
+                                                .size(self.theme.text_size(13.0))
+                                                .color(self.theme.danger),
+                                        )
+                                        .on_hover_text(format_timestamp(&message.timestamp));
+                                    });
+                                if index == last_index
+                                    && can_regenerate
+                                    && ui
+                                        .small_button("Retry")
+                                        .on_hover_text("Send the last prompt again")
+                                        .clicked()
+                                {
+                                    regenerate = true;
+                                }
+                                continue;
+                            }
                             let is_user = message.role == "user";
+                            let is_match = search_matches.contains(&index);
                             let bubble = Frame::new()
                                 .fill(if is_user {
                                     self.theme.surface_2
@@ -1588,31 +4687,113 @@ impl BrownieApp {
                                     self.theme.surface_3
                                 })
                                 .corner_radius(egui::CornerRadius::same(self.theme.radius_12))
-                                .stroke(Stroke::NONE)
+                                .stroke(if is_match {
+                                    Stroke::new(1.5, self.theme.accent_primary)
+                                } else {
+                                    Stroke::NONE
+                                })
                                 .inner_margin(egui::Margin::same(self.theme.spacing_12 as i8));
+                            let scroll_here = scroll_target == Some(index);
+                            // A find match is shown in full while the query is active.
+                            let expanded = is_match || self.expanded_messages.contains(&index);
+                            let preview =
+                                collapsed_preview(&message.content, collapse_lines, collapse_chars);
+                            let shown_text = match &preview {
+                                Some(preview) if !expanded => preview.as_str(),
+                                _ => message.content.as_str(),
+                            };
+                            let toggle_label = (preview.is_some() && !is_match)
+                                .then_some(if expanded { "Show less" } else { "Show more" });
 
                             if is_user {
                                 ui.horizontal(|ui| {
                                     ui.add_space(self.theme.spacing_24);
                                     bubble.show(ui, |ui| {
+                                        if scroll_here {
+                                            ui.scroll_to_cursor(Some(Align::Center));
+                                        }
                                         ui.label(
-                                            RichText::new(format!("[You] {}", message.content))
-                                                .size(14.0)
+                                            RichText::new(format!("[You] {shown_text}"))
+                                                .size(self.theme.text_size(14.0))
                                                 .color(self.theme.text_primary),
                                         );
+                                        if let Some(label) = toggle_label {
+                                            if ui.small_button(label).clicked() {
+                                                toggle_expanded = Some(index);
+                                            }
+                                        }
+                                        ui.horizontal(|ui| {
+                                            ui.label(
+                                                RichText::new(format_timestamp(&message.timestamp))
+                                                    .size(self.theme.text_size(11.0))
+                                                    .color(self.theme.text_muted),
+                                            );
+                                            render_copy_button(ui, &self.theme, &message.content);
+                                        });
                                     });
+                                    if can_regenerate
+                                        && ui
+                                            .small_button("Edit")
+                                            .on_hover_text("Edit and resend from here")
+                                            .clicked()
+                                    {
+                                        edit_message = Some(index);
+                                    }
                                 });
                             } else {
                                 bubble.show(ui, |ui| {
+                                    if scroll_here {
+                                        ui.scroll_to_cursor(Some(Align::Center));
+                                    }
                                     ui.label(
-                                        RichText::new(format!("[Copilot] {}", message.content))
-                                            .size(14.0)
+                                        RichText::new(format!("[Copilot] {shown_text}"))
+                                            .size(self.theme.text_size(14.0))
                                             .color(self.theme.text_primary),
                                     );
+                                    if let Some(label) = toggle_label {
+                                        if ui.small_button(label).clicked() {
+                                            toggle_expanded = Some(index);
+                                        }
+                                    }
+                                    ui.horizontal(|ui| {
+                                        ui.label(
+                                            RichText::new(format_timestamp(&message.timestamp))
+                                                .size(self.theme.text_size(11.0))
+                                                .color(self.theme.text_muted),
+                                        );
+                                        render_copy_button(ui, &self.theme, &message.content);
+                                    });
                                 });
+                                if index == last_index
+                                    && can_regenerate
+                                    && ui
+                                        .small_button("Regenerate")
+                                        .on_hover_text("Ask for a fresh answer to the last prompt")
+                                        .clicked()
+                                {
+                                    regenerate = true;
+                                }
                             }
                         }
 
+                        if !self.in_progress_reasoning.is_empty() {
+                            egui::CollapsingHeader::new(
+                                RichText::new("Thinking")
+                                    .size(self.theme.text_size(12.0))
+                                    .color(self.theme.text_muted),
+                            )
+                            .id_salt("assistant_thinking")
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                ui.label(
+                                    RichText::new(&self.in_progress_reasoning)
+                                        .size(self.theme.text_size(12.0))
+                                        .italics()
+                                        .color(self.theme.text_muted),
+                                );
+                            });
+                        }
+
                         if self.is_streaming && !self.in_progress_assistant.is_empty() {
                             Frame::new()
                                 .fill(self.theme.surface_3)
@@ -1625,9 +4806,14 @@ impl BrownieApp {
                                             "[Copilot] {}",
                                             self.in_progress_assistant
                                         ))
-                                        .size(14.0)
+                                        .size(self.theme.text_size(14.0))
                                         .color(self.theme.text_primary),
                                     );
+                                    render_copy_button(
+                                        ui,
+                                        &self.theme,
+                                        &self.in_progress_assistant,
+                                    );
                                 });
                         }
 
@@ -1636,26 +4822,77 @@ impl BrownieApp {
                         }
                     });
                 self.scroll_to_bottom = false;
+                self.scroll_to_search_match = false;
+                if let Some(index) = toggle_expanded {
+                    if !self.expanded_messages.remove(&index) {
+                        self.expanded_messages.insert(index);
+                    }
+                }
+                if regenerate {
+                    self.regenerate_last_response(ctx);
+                }
+                if let Some(index) = edit_message {
+                    self.start_editing_message(index);
+                }
 
                 self.theme.card_frame().show(ui, |ui| {
                     egui::CollapsingHeader::new(
                         RichText::new("Diagnostics")
-                            .size(14.0)
+                            .size(self.theme.text_size(14.0))
                             .strong()
                             .color(self.theme.text_primary),
                     )
                     .default_open(false)
                     .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt("diagnostics_level_filter")
+                                .selected_text(format!(
+                                    "{} and above",
+                                    self.diagnostics_min_level.label()
+                                ))
+                                .show_ui(ui, |ui| {
+                                    for level in DiagnosticLevel::ALL {
+                                        ui.selectable_value(
+                                            &mut self.diagnostics_min_level,
+                                            level,
+                                            format!("{} and above", level.label()),
+                                        );
+                                    }
+                                });
+                            let has_entries = !self.diagnostics_log.is_empty();
+                            if ui
+                                .add_enabled(has_entries, egui::Button::new("Copy all"))
+                                .clicked()
+                            {
+                                let text = self
+                                    .diagnostics_log
+                                    .iter()
+                                    .map(|entry| entry.text.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join("\n");
+                                ui.ctx().copy_text(text);
+                            }
+                            if ui
+                                .add_enabled(has_entries, egui::Button::new("Clear"))
+                                .clicked()
+                            {
+                                self.diagnostics_log.clear();
+                            }
+                        });
                         ScrollArea::vertical()
                             .id_salt("diagnostics_log")
                             .max_height(100.0)
                             .stick_to_bottom(true)
                             .show(ui, |ui| {
-                                for entry in &self.diagnostics_log {
+                                for entry in self
+                                    .diagnostics_log
+                                    .iter()
+                                    .filter(|entry| entry.level >= self.diagnostics_min_level)
+                                {
                                     ui.label(
-                                        RichText::new(entry)
-                                            .size(12.0)
-                                            .color(self.theme.text_muted),
+                                        RichText::new(&entry.text)
+                                            .size(self.theme.text_size(12.0))
+                                            .color(entry.level.color(&self.theme)),
                                     );
                                 }
                             });
@@ -1663,16 +4900,26 @@ impl BrownieApp {
                 });
 
                 let connected = self.connection_state == ConnectionState::Connected;
-                let input_enabled = connected && !self.is_streaming;
+                let turn_in_flight = self.is_streaming || self.awaiting_assistant_turn;
+                let input_enabled = composer_enabled(
+                    connected,
+                    turn_in_flight,
+                    self.editing_message_index.is_some(),
+                    &self.pending_permissions,
+                );
                 let hint = if !connected {
                     "Not connected"
-                } else if self.is_streaming {
-                    "Waiting for response..."
+                } else if !self.pending_permissions.is_empty() {
+                    "Answer the tool permission request to continue"
+                } else if turn_in_flight {
+                    "Response in progress; Ctrl+Enter queues your next message"
                 } else {
                     "Type a message..."
                 };
-
                 let mut send_now = false;
+                let mut cancel_edit = false;
+                let mut cancel_now =
+                    turn_in_flight && ui.input(|input| input.key_pressed(egui::Key::Escape));
                 self.theme.composer_frame().show(ui, |ui| {
                     ui.spacing_mut().item_spacing = egui::vec2(Theme::P8, Theme::P8);
                     let response = ui
@@ -1705,11 +4952,45 @@ impl BrownieApp {
                     }
 
                     ui.horizontal(|ui| {
+                        if let Some(index) = self.editing_message_index {
+                            ui.label(
+                                RichText::new(format!(
+                                    "Editing message {} (later turns will be replaced)",
+                                    index + 1
+                                ))
+                                .size(self.theme.text_size(12.0))
+                                .color(self.theme.warning),
+                            );
+                            if ui.small_button("Cancel edit").clicked() {
+                                cancel_edit = true;
+                            }
+                        }
                         ui.label(
                             RichText::new("Ctrl+Enter to send")
-                                .size(12.0)
+                                .size(self.theme.text_size(12.0))
                                 .color(self.theme.text_muted),
                         );
+                        if !self.prompt_queue.is_empty() {
+                            ui.label(
+                                RichText::new(format!("{} queued", self.prompt_queue.len()))
+                                    .size(self.theme.text_size(12.0))
+                                    .color(self.theme.accent_primary),
+                            );
+                        }
+                        let tokens = estimate_tokens(&self.input_buffer);
+                        ui.label(
+                            RichText::new(format!(
+                                "{} chars · ~{tokens} tokens",
+                                self.input_buffer.chars().count()
+                            ))
+                            .size(self.theme.text_size(12.0))
+                            .color(composer_budget_color(
+                                &self.theme,
+                                tokens,
+                                self.settings.composer_warning_tokens,
+                                self.settings.composer_danger_tokens,
+                            )),
+                        );
                         ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
                             let clicked = ui
                                 .add_enabled_ui(
@@ -1717,18 +4998,39 @@ impl BrownieApp {
                                     |ui| {
                                         ui.add_sized(
                                             [96.0, self.theme.button_height],
-                                            self.primary_button("Send"),
+                                            self.primary_button(if turn_in_flight {
+                                                "Queue"
+                                            } else {
+                                                "Send"
+                                            }),
                                         )
                                     },
                                 )
                                 .inner
                                 .clicked();
                             send_now |= clicked;
+                            let stop_clicked = ui
+                                .add_enabled_ui(turn_in_flight, |ui| {
+                                    ui.add_sized(
+                                        [96.0, self.theme.button_height],
+                                        self.secondary_button("Stop"),
+                                    )
+                                    .on_hover_text("Stop the response (Esc)")
+                                })
+                                .inner
+                                .clicked();
+                            cancel_now |= stop_clicked;
                         });
                     });
                 });
 
-                if send_now && input_enabled {
+                if cancel_edit {
+                    self.editing_message_index = None;
+                    self.input_buffer.clear();
+                }
+                if cancel_now && turn_in_flight {
+                    self.cancel_turn();
+                } else if send_now && input_enabled {
                     self.submit_prompt(ctx);
                 }
             });
@@ -1744,25 +5046,64 @@ impl eframe::App for BrownieApp {
             egui::CornerRadius::ZERO,
             self.theme.surface_0,
         );
+        self.keep_window_on_screen(ctx);
         self.drain_events(ctx);
+        self.poll_catalog_changes();
         self.render_top_bar(ctx);
         self.render_left_panel(ctx);
         self.render_right_panel(ctx);
         self.render_center_panel(ctx);
+        self.render_permission_dialog(ctx);
+        if ctx.input(|input| input.viewport().close_requested()) {
+            if self.session_persist.dirty {
+                self.flush_current_session();
+            }
+            self.persist_window_geometry(ctx);
+        } else {
+            self.persist_session_if_due(ctx);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        apply_close_transition, apply_focus_transition, apply_toggle_minimize_transition,
-        resolve_block_target_for_template, BlockTargetResolution, CanvasBlock,
+        apply_close_all_transition, apply_close_transition, apply_focus_transition,
+        apply_maximize_toggle, apply_minimize_all_transition, apply_move_transition,
+        apply_toggle_minimize_transition, build_file_tree, cancelled_assistant_message,
+        canvas_lifecycle_level, canvas_warning_after_outcome, collapsed_preview,
+        composer_budget_color, composer_enabled, connection_state_level, discard_queued_prompts,
+        duplicate_canvas_block, estimate_tokens, file_tree_metadata, language_for_path,
+        publish_canvas_snapshot, push_bounded, read_file_preview, remove_pending_permission,
+        reopen_offer_for, reset_canvas_for_new_session, resolve_block_target_by_title,
+        resolve_block_target_for_template, resolve_playground_prompt, resolve_workspace_file,
+        retain_maximized_block, template_from_block, tool_chip_message, tool_chip_style,
+        tool_outcome_level, transcript_search, trim_for_regenerate, truncate_transcript_after,
+        turn_error_message, write_canvas_export, BlockTargetResolution, BrownieApp, CanvasBlock,
+        DiagnosticLevel, PendingPermission, PersistDebounce, PlaygroundResult,
+        CANCELLED_RESPONSE_MARKER, FILE_TREE_MAX_ENTRIES, FILE_TREE_TRUNCATED_LABEL,
+    };
+    use crate::copilot::{CopilotClient, ToolExclusions};
+    use crate::event::AppEvent;
+    use crate::session::store::SessionWriter;
+    use crate::session::{SessionMeta, SessionSummary, ToolStatus, SCHEMA_VERSION};
+    use crate::settings::Settings;
+    use crate::test_support::temp_dir;
+    use crate::ui::catalog::{
+        BuiltinCatalogProvider, CatalogManager, CatalogProvider, UiIntent, UserCatalogProvider,
     };
-    use crate::ui::catalog::UiIntent;
+    use crate::ui::event::{UiEvent, UiEventLog, UiFieldValue};
     use crate::ui::runtime::UiRuntime;
-    use crate::ui::workspace::CanvasBlockState;
+    use crate::ui::workspace::{
+        CanvasBlockActionStatus, CanvasBlockState, CanvasBlockSummary, CanvasWorkspaceState,
+        SharedCanvasSnapshot,
+    };
+    use copilot_sdk::ConnectionState;
     use serde_json::json;
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, VecDeque};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, Instant};
 
     fn block(block_id: &str, template_id: &str, touched: u128) -> CanvasBlock {
         CanvasBlock {
@@ -1793,9 +5134,78 @@ mod tests {
         }
     }
 
-    #[test]
-    fn target_selection_prefers_active_matching_block() {
-        let blocks = vec![
+    /// A `BrownieApp` over an empty temp workspace with no sessions and no SDK
+    /// connection. The runtime is dropped after the app.
+    struct AppFixture {
+        app: BrownieApp,
+        workspace: PathBuf,
+        _runtime: tokio::runtime::Runtime,
+    }
+
+    impl Drop for AppFixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.workspace);
+        }
+    }
+
+    fn app_fixture() -> AppFixture {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("test runtime should start");
+        let _guard = runtime.enter();
+        let workspace = temp_dir("app_fixture");
+        fs::create_dir_all(&workspace).expect("create workspace");
+        let settings = Settings::default();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let copilot =
+            CopilotClient::new(workspace.clone(), tx, None, ToolExclusions::from(&settings))
+                .expect("client should build without connecting");
+        let session_writer = SessionWriter::new(runtime.handle().clone(), |_| {});
+        let app = BrownieApp::with_sessions(
+            rx,
+            copilot,
+            session_writer,
+            workspace.clone(),
+            Vec::new(),
+            settings,
+            Vec::new(),
+        );
+        AppFixture {
+            app,
+            workspace,
+            _runtime: runtime,
+        }
+    }
+
+    #[test]
+    fn reopen_offer_only_names_a_session_of_this_workspace() {
+        let session = |session_id: &str, workspace: &str, messages: usize| {
+            SessionSummary::from(&SessionMeta {
+                session_id: session_id.to_string(),
+                workspace: workspace.to_string(),
+                messages: vec![Default::default(); messages],
+                ..Default::default()
+            })
+        };
+        let sessions = vec![
+            session("elsewhere", "/work/other", 3),
+            session("empty", "/work/here", 0),
+            session("here", "/work/here", 2),
+        ];
+        let here = Path::new("/work/here");
+
+        let offer = reopen_offer_for(&sessions, Some("here"), here);
+        assert_eq!(offer.map(|offer| offer.session_id).as_deref(), Some("here"));
+        assert!(reopen_offer_for(&sessions, Some("elsewhere"), here).is_none());
+        assert!(reopen_offer_for(&sessions, Some("empty"), here).is_none());
+        assert!(reopen_offer_for(&sessions, None, here).is_none());
+    }
+
+    #[test]
+    fn target_selection_prefers_active_matching_block() {
+        let blocks = vec![
             block("block-1", "builtin.file_listing.default", 1),
             block("block-2", "builtin.file_listing.default", 999),
         ];
@@ -1836,6 +5246,101 @@ mod tests {
         );
     }
 
+    fn titled_block(block_id: &str, title: &str) -> CanvasBlock {
+        let mut block = block(block_id, "builtin.file_listing.default", 1);
+        block.state.title = title.to_string();
+        block
+    }
+
+    #[test]
+    fn token_estimate_rounds_up_by_characters() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("héllo"), 2);
+    }
+
+    #[test]
+    fn composer_budget_color_follows_thresholds() {
+        let theme = crate::theme::Theme::default();
+        assert_eq!(
+            composer_budget_color(&theme, 10, 100, 200),
+            theme.text_muted
+        );
+        assert_eq!(composer_budget_color(&theme, 100, 100, 200), theme.warning);
+        assert_eq!(composer_budget_color(&theme, 250, 100, 200), theme.danger);
+    }
+
+    #[test]
+    fn canvas_snapshot_tracks_opened_and_closed_blocks() {
+        let snapshot = SharedCanvasSnapshot::default();
+        let mut blocks = vec![titled_block("block-1", "Files")];
+        assert!(publish_canvas_snapshot(&snapshot, &blocks).is_some());
+        assert!(publish_canvas_snapshot(&snapshot, &blocks).is_none());
+
+        blocks.push(titled_block("block-2", "Plan"));
+        blocks[1].state.minimized = true;
+        publish_canvas_snapshot(&snapshot, &blocks);
+        assert_eq!(
+            *snapshot.read().expect("snapshot lock"),
+            vec![
+                CanvasBlockSummary {
+                    block_id: "block-1".to_string(),
+                    template_id: "builtin.file_listing.default".to_string(),
+                    title: "Files".to_string(),
+                    minimized: false,
+                },
+                CanvasBlockSummary {
+                    block_id: "block-2".to_string(),
+                    template_id: "builtin.file_listing.default".to_string(),
+                    title: "Plan".to_string(),
+                    minimized: true,
+                },
+            ]
+        );
+
+        let mut active = Some("block-2".to_string());
+        assert!(apply_close_transition(&mut blocks, &mut active, "block-1"));
+        publish_canvas_snapshot(&snapshot, &blocks);
+        let published = snapshot.read().expect("snapshot lock");
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].block_id, "block-2");
+    }
+
+    #[test]
+    fn title_target_matches_unique_title_case_insensitively() {
+        let blocks = vec![
+            titled_block("block-1", "Workspace Files"),
+            titled_block("block-2", "Plan Review"),
+        ];
+        assert_eq!(
+            resolve_block_target_by_title(&blocks, " plan review "),
+            BlockTargetResolution::Existing(1)
+        );
+    }
+
+    #[test]
+    fn title_target_reports_ambiguous_titles() {
+        let blocks = vec![
+            titled_block("block-2", "Files"),
+            titled_block("block-1", "FILES"),
+            titled_block("block-3", "Plan"),
+        ];
+        assert_eq!(
+            resolve_block_target_by_title(&blocks, "files"),
+            BlockTargetResolution::Ambiguous(vec!["block-1".to_string(), "block-2".to_string()])
+        );
+    }
+
+    #[test]
+    fn title_target_reports_missing_title() {
+        let blocks = vec![titled_block("block-1", "Files")];
+        assert_eq!(
+            resolve_block_target_by_title(&blocks, "Notes"),
+            BlockTargetResolution::NotFound
+        );
+    }
+
     #[test]
     fn focus_transition_sets_active_without_removing_blocks() {
         let mut blocks = vec![
@@ -1878,4 +5383,1152 @@ mod tests {
         assert!(blocks.iter().all(|block| block.state.block_id != "block-2"));
         assert_eq!(active.as_deref(), Some("block-3"));
     }
+
+    #[test]
+    fn minimize_all_only_touches_expanded_blocks() {
+        let mut blocks = vec![
+            block("block-1", "builtin.file_listing.default", 1),
+            block("block-2", "builtin.plan_review.default", 2),
+            block("block-3", "builtin.status.default", 3),
+        ];
+        blocks[1].state.minimized = true;
+
+        let minimized = apply_minimize_all_transition(&mut blocks, 9);
+        assert_eq!(minimized, vec!["block-1", "block-3"]);
+        assert!(blocks.iter().all(|block| block.state.minimized));
+        assert_eq!(blocks[1].last_touched_at, 2);
+        assert_eq!(blocks[2].last_touched_at, 9);
+
+        assert!(apply_minimize_all_transition(&mut blocks, 10).is_empty());
+    }
+
+    #[test]
+    fn close_all_removes_every_block_and_clears_active() {
+        let mut blocks = vec![
+            block("block-1", "builtin.file_listing.default", 1),
+            block("block-2", "builtin.plan_review.default", 2),
+        ];
+        let mut active = Some("block-2".to_string());
+
+        let closed = apply_close_all_transition(&mut blocks, &mut active);
+        assert_eq!(closed, vec!["block-1", "block-2"]);
+        assert!(blocks.is_empty());
+        assert_eq!(active, None);
+
+        assert!(apply_close_all_transition(&mut blocks, &mut active).is_empty());
+    }
+
+    #[test]
+    fn duplicated_block_has_independent_form_state() {
+        let mut source = block("block-1", "user.review_form", 1);
+        source.state.schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "review_form",
+                "kind": "form",
+                "fields": [{"id": "notes", "label": "Notes", "kind": "text"}]
+            }]
+        });
+        source
+            .ui_runtime
+            .load_schema_value(&source.state.schema)
+            .expect("form schema should load");
+        let draft = UiFieldValue::Text {
+            value: "draft".to_string(),
+        };
+        source
+            .ui_runtime
+            .simulate_form_commit("review_form", "notes", draft.clone());
+
+        let mut copy =
+            duplicate_canvas_block(&source, "block-2".to_string(), 2).expect("duplicate loads");
+        assert_eq!(copy.state.block_id, "block-2");
+        assert_eq!(copy.state.schema, source.state.schema);
+        assert_eq!(copy.state.intent, source.state.intent);
+        assert_eq!(copy.state.form_state.get("review_form:notes"), Some(&draft));
+        assert_eq!(copy.synced_event_count, 0);
+
+        copy.ui_runtime.simulate_form_commit(
+            "review_form",
+            "notes",
+            UiFieldValue::Text {
+                value: "forked".to_string(),
+            },
+        );
+        assert_eq!(
+            source
+                .ui_runtime
+                .form_state_snapshot()
+                .get("review_form:notes"),
+            Some(&draft)
+        );
+        assert_ne!(
+            copy.ui_runtime
+                .form_state_snapshot()
+                .get("review_form:notes"),
+            Some(&draft)
+        );
+    }
+
+    fn form_block(block_id: &str, notes: &str) -> CanvasBlock {
+        let mut block = block(block_id, "user.review_form", 1);
+        block.state.schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "review_form",
+                "kind": "form",
+                "fields": [{"id": "notes", "label": "Notes", "kind": "text"}]
+            }]
+        });
+        block.load_runtime().expect("form schema should load");
+        block.ui_runtime.simulate_form_commit(
+            "review_form",
+            "notes",
+            UiFieldValue::Text {
+                value: notes.to_string(),
+            },
+        );
+        block
+    }
+
+    #[test]
+    fn minimized_block_drops_its_runtime_and_keeps_form_values() {
+        let mut blocks = vec![form_block("block-1", "draft")];
+        let draft = UiFieldValue::Text {
+            value: "draft".to_string(),
+        };
+
+        assert_eq!(
+            apply_toggle_minimize_transition(&mut blocks, "block-1", 2),
+            Some(true)
+        );
+        assert!(!blocks[0].ui_runtime.has_schema());
+        assert_eq!(
+            blocks[0].form_state_snapshot().get("review_form:notes"),
+            Some(&draft)
+        );
+
+        assert_eq!(
+            apply_toggle_minimize_transition(&mut blocks, "block-1", 3),
+            Some(false)
+        );
+        blocks[0].load_runtime().expect("schema should reload");
+        assert_eq!(
+            blocks[0]
+                .ui_runtime
+                .form_state_snapshot()
+                .get("review_form:notes"),
+            Some(&draft)
+        );
+    }
+
+    #[test]
+    fn reloading_an_unsaved_block_replays_its_event_log() {
+        let mut block = form_block("block-1", "draft");
+        assert!(block.state.form_state.is_empty());
+        block.state.schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "review_form",
+                "kind": "form",
+                "fields": [
+                    {"id": "notes", "label": "Notes", "kind": "text"},
+                    {"id": "urgent", "label": "Urgent", "kind": "checkbox"}
+                ]
+            }]
+        });
+
+        block.load_runtime().expect("changed schema should load");
+        let form_state = block.ui_runtime.form_state_snapshot();
+        assert_eq!(
+            form_state.get("review_form:notes"),
+            Some(&UiFieldValue::Text {
+                value: "draft".to_string()
+            })
+        );
+        assert_eq!(
+            form_state.get("review_form:urgent"),
+            Some(&UiFieldValue::Checkbox { value: false })
+        );
+    }
+
+    #[test]
+    fn restoring_another_session_does_not_reuse_runtimes_by_block_id() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+        app.current_session = Some(SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "session-a".to_string(),
+            workspace: app.workspace.to_string_lossy().to_string(),
+            title: None,
+            created_at: "0".to_string(),
+            canvas_workspace: CanvasWorkspaceState::default(),
+            messages: Vec::new(),
+        });
+        app.canvas_blocks.push(form_block("block-1", "from a"));
+        let mut saved = form_block("block-1", "from b");
+        saved.state.form_state = saved.form_state_snapshot();
+        let workspace = CanvasWorkspaceState {
+            blocks: vec![saved.state],
+            active_block_id: None,
+        };
+
+        app.restore_canvas_workspace(Some("session-b"), &workspace);
+
+        assert_eq!(
+            app.canvas_blocks[0]
+                .form_state_snapshot()
+                .get("review_form:notes"),
+            Some(&UiFieldValue::Text {
+                value: "from b".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn maximize_toggles_and_is_cleared_when_block_closes() {
+        let mut blocks = vec![
+            block("block-1", "builtin.file_listing.default", 1),
+            block("block-2", "builtin.plan_review.default", 2),
+        ];
+        let mut active = Some("block-2".to_string());
+        let mut maximized = None;
+
+        apply_maximize_toggle(&mut maximized, "block-1");
+        assert_eq!(maximized.as_deref(), Some("block-1"));
+        apply_maximize_toggle(&mut maximized, "block-2");
+        assert_eq!(maximized.as_deref(), Some("block-2"));
+        apply_maximize_toggle(&mut maximized, "block-2");
+        assert_eq!(maximized, None);
+
+        apply_maximize_toggle(&mut maximized, "block-1");
+        assert!(apply_close_transition(&mut blocks, &mut active, "block-2"));
+        retain_maximized_block(&mut maximized, &blocks);
+        assert_eq!(maximized.as_deref(), Some("block-1"));
+
+        assert!(apply_close_transition(&mut blocks, &mut active, "block-1"));
+        retain_maximized_block(&mut maximized, &blocks);
+        assert_eq!(maximized, None);
+    }
+
+    #[test]
+    fn move_transition_reorders_without_touching_block_state() {
+        let mut blocks = vec![
+            block("block-1", "builtin.file_listing.default", 1),
+            block("block-2", "builtin.plan_review.default", 2),
+            block("block-3", "builtin.status.default", 3),
+        ];
+        blocks[2].state.minimized = true;
+        blocks[2].synced_event_count = 4;
+
+        assert!(apply_move_transition(&mut blocks, 2, 0));
+        let order = blocks
+            .iter()
+            .map(|block| block.state.block_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec!["block-3", "block-1", "block-2"]);
+        assert!(blocks[0].state.minimized);
+        assert_eq!(blocks[0].synced_event_count, 4);
+        assert_eq!(blocks[0].last_touched_at, 3);
+        assert_eq!(blocks[0].state.template_id, "builtin.status.default");
+
+        assert!(apply_move_transition(&mut blocks, 0, 2));
+        let order = blocks
+            .iter()
+            .map(|block| block.state.block_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec!["block-1", "block-2", "block-3"]);
+
+        assert!(!apply_move_transition(&mut blocks, 1, 1));
+        assert!(!apply_move_transition(&mut blocks, 0, 3));
+    }
+
+    #[test]
+    fn template_preview_leaves_canvas_and_session_untouched() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+        app.current_session = Some(SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "session-1".to_string(),
+            workspace: app.workspace.to_string_lossy().to_string(),
+            title: None,
+            created_at: "0".to_string(),
+            canvas_workspace: CanvasWorkspaceState::default(),
+            messages: Vec::new(),
+        });
+        app.canvas_blocks
+            .push(block("block-1", "builtin.file_listing.default", 1));
+        let workspace_before =
+            serde_json::to_value(app.snapshot_canvas_workspace()).expect("workspace serializes");
+        let session_before =
+            serde_json::to_value(&app.current_session).expect("session serializes");
+
+        app.open_template_preview("builtin.dashboard.default");
+        let preview = app
+            .template_preview
+            .as_ref()
+            .expect("builtin template should preview");
+        assert_eq!(preview.title, "Project Status Dashboard");
+        assert!(preview.runtime.has_schema());
+        assert!(preview.runtime.runtime_error().is_none());
+        assert_eq!(
+            serde_json::to_value(app.snapshot_canvas_workspace()).expect("workspace serializes"),
+            workspace_before
+        );
+        assert_eq!(
+            serde_json::to_value(&app.current_session).expect("session serializes"),
+            session_before
+        );
+        assert!(!app.session_persist.dirty);
+
+        app.open_template_preview("builtin.missing");
+        assert!(app.template_preview.is_none());
+        assert!(app
+            .diagnostics_log
+            .back()
+            .is_some_and(|entry| entry.text.contains("builtin.missing is not in the catalog")));
+    }
+
+    #[test]
+    fn playground_resolves_prompt_through_intent_and_catalog() {
+        let providers: Vec<Box<dyn CatalogProvider>> =
+            vec![Box::new(BuiltinCatalogProvider::default())];
+        let manager = CatalogManager::new(providers, false);
+
+        let result = resolve_playground_prompt(&manager, "Show me the files in the workspace");
+        let PlaygroundResult::Resolved(intent_trace, trace) = result else {
+            panic!("file listing prompt should resolve");
+        };
+        assert_eq!(trace.intent.primary, "file_listing");
+        assert!(intent_trace
+            .iter()
+            .any(|line| line.starts_with("matched: ")));
+        assert_eq!(
+            trace.selected_template_id.as_deref(),
+            Some("builtin.file_listing.default")
+        );
+        assert!(trace
+            .ranked_candidates
+            .iter()
+            .any(|candidate| candidate.selected));
+
+        assert_eq!(
+            resolve_playground_prompt(&manager, "hello there"),
+            PlaygroundResult::NoIntent
+        );
+    }
+
+    #[test]
+    fn canvas_export_refuses_overwrites_and_non_html_targets() {
+        let root = temp_dir("canvas_export");
+        let target = root.join("exports").join("canvas.html");
+
+        write_canvas_export(&target, "<p>first</p>").expect("new export should be written");
+        let err = write_canvas_export(&target, "<p>second</p>")
+            .expect_err("existing export must not be overwritten");
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(
+            fs::read_to_string(&target).expect("read export"),
+            "<p>first</p>"
+        );
+
+        let err = write_canvas_export(&root.join("notes.txt"), "<p>x</p>")
+            .expect_err("non-html target must be refused");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!root.join("notes.txt").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn file_tree_nests_directories_and_skips_ignored_dirs() {
+        let root = temp_dir("file_tree");
+        fs::create_dir_all(root.join("src/ui/deep")).expect("create dirs");
+        fs::create_dir_all(root.join("target/debug")).expect("create target");
+        fs::create_dir_all(root.join(".git")).expect("create git dir");
+        fs::write(root.join("Cargo.toml"), "").expect("write manifest");
+        fs::write(root.join("src/main.rs"), "").expect("write main");
+        fs::write(root.join("src/ui/deep/hidden.rs"), "").expect("write deep file");
+
+        let skip_dirs = Settings::default().skip_dirs;
+        let tree = build_file_tree(&root, &root, 2, FILE_TREE_MAX_ENTRIES, &skip_dirs, false)
+            .expect("tree should build");
+        let names = tree
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["src", "Cargo.toml"]);
+
+        let src = &tree[0];
+        assert!(src.is_dir);
+        assert_eq!(src.path, "src");
+        let src_names = src
+            .children
+            .iter()
+            .map(|node| node.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(src_names, vec!["src/ui", "src/main.rs"]);
+
+        // Depth bound: `src/ui` is listed but its contents are not walked.
+        let ui = &src.children[0];
+        assert!(ui.is_dir);
+        assert!(ui.children.is_empty());
+
+        assert!(build_file_tree(
+            &root.join("missing"),
+            &root,
+            2,
+            FILE_TREE_MAX_ENTRIES,
+            &skip_dirs,
+            false
+        )
+        .is_err());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn file_tree_walks_nested_dirs_and_marks_truncation() {
+        let root = temp_dir("file_tree_truncate");
+        fs::create_dir_all(root.join("src/ui/widgets")).expect("create dirs");
+        fs::write(root.join("README.md"), "").expect("write readme");
+        fs::write(root.join("src/main.rs"), "").expect("write main");
+        fs::write(root.join("src/ui/mod.rs"), "").expect("write ui mod");
+        fs::write(root.join("src/ui/widgets/button.rs"), "").expect("write widget");
+
+        let tree =
+            build_file_tree(&root, &root, 4, FILE_TREE_MAX_ENTRIES, &[], false).expect("tree");
+        let widgets = &tree[0].children[0].children[0];
+        assert_eq!(widgets.path, "src/ui/widgets");
+        assert_eq!(widgets.children[0].path, "src/ui/widgets/button.rs");
+        assert!(tree.iter().all(|node| !node.path.is_empty()));
+
+        // Six entries in total; a cap of three keeps the shallow ones and marks the cut.
+        let capped = build_file_tree(&root, &root, 4, 3, &[], false).expect("capped tree");
+        let names = capped
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["src", "README.md", FILE_TREE_TRUNCATED_LABEL]);
+        assert_eq!(capped[0].children.len(), 1);
+        assert_eq!(capped[0].children[0].path, "src/main.rs");
+        assert!(capped[2].path.is_empty());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn file_tree_metadata_reads_sizes_and_tolerates_missing_entries() {
+        let root = temp_dir("file_tree_metadata");
+        fs::create_dir_all(root.join("dir/nested")).expect("create dir");
+        fs::write(root.join("dir/a.txt"), "hello").expect("write file");
+        fs::write(root.join("dir/b.txt"), "").expect("write file");
+        fs::write(root.join("dir/skip.log"), "").expect("write ignored file");
+        fs::write(root.join(".gitignore"), "*.log\n").expect("write ignore");
+
+        let (size, modified) = file_tree_metadata(&root.join("dir/a.txt"), false);
+        assert_eq!(size, Some(5));
+        assert!(modified.is_some());
+        let (size, _) = file_tree_metadata(&root.join("dir"), true);
+        assert_eq!(size, None);
+
+        // An entry that vanished (or cannot be read) yields no metadata instead of an error.
+        assert_eq!(
+            file_tree_metadata(&root.join("gone.txt"), false),
+            (None, None)
+        );
+
+        // Entry counts match the visible children: ignored files are not counted, and a
+        // directory at the depth limit was not walked so its count is unknown.
+        let tree =
+            build_file_tree(&root, &root, 2, FILE_TREE_MAX_ENTRIES, &[], true).expect("tree");
+        let dir = &tree[0];
+        assert_eq!(dir.entry_count, Some(3));
+        assert_eq!(dir.entry_count, Some(dir.children.len()));
+        assert_eq!(dir.children[0].path, "dir/nested");
+        assert_eq!(dir.children[0].entry_count, None);
+        assert_eq!(dir.children[1].size, Some(5));
+
+        // Without metadata nothing is read beyond the walk.
+        let plain =
+            build_file_tree(&root, &root, 2, FILE_TREE_MAX_ENTRIES, &[], false).expect("tree");
+        assert_eq!(plain[0].entry_count, None);
+        assert_eq!(plain[0].children[1].size, None);
+        assert_eq!(plain[0].children[1].modified, None);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn diagnostics_are_classified_by_level() {
+        assert_eq!(
+            connection_state_level(ConnectionState::Connected),
+            DiagnosticLevel::Info
+        );
+        assert_eq!(
+            connection_state_level(ConnectionState::Disconnected),
+            DiagnosticLevel::Warn
+        );
+        assert_eq!(
+            connection_state_level(ConnectionState::Error),
+            DiagnosticLevel::Error
+        );
+        assert_eq!(
+            canvas_lifecycle_level(CanvasBlockActionStatus::Succeeded),
+            DiagnosticLevel::Info
+        );
+        assert_eq!(
+            canvas_lifecycle_level(CanvasBlockActionStatus::Failed),
+            DiagnosticLevel::Warn
+        );
+        assert_eq!(
+            tool_outcome_level(&ToolStatus::RenderedCatalog),
+            DiagnosticLevel::Info
+        );
+        assert_eq!(
+            tool_outcome_level(&ToolStatus::TextOnly),
+            DiagnosticLevel::Warn
+        );
+        assert_eq!(
+            tool_outcome_level(&ToolStatus::Denied),
+            DiagnosticLevel::Warn
+        );
+        assert_eq!(
+            tool_outcome_level(&ToolStatus::parse("failed")),
+            DiagnosticLevel::Error
+        );
+
+        // The filter keeps everything at or above the chosen level.
+        let levels = DiagnosticLevel::ALL
+            .into_iter()
+            .filter(|level| *level >= DiagnosticLevel::Warn)
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec![DiagnosticLevel::Warn, DiagnosticLevel::Error]);
+    }
+
+    #[test]
+    fn push_bounded_drops_oldest_entries_past_cap() {
+        let mut log = VecDeque::new();
+        for index in 0..5 {
+            push_bounded(&mut log, index, 3);
+        }
+        assert_eq!(log, VecDeque::from([2, 3, 4]));
+
+        push_bounded(&mut log, 5, 3);
+        assert_eq!(log.front(), Some(&3));
+        assert_eq!(log.len(), 3);
+    }
+
+    #[test]
+    fn language_for_path_maps_common_extensions() {
+        assert_eq!(language_for_path(Path::new("src/main.rs")), "rust");
+        assert_eq!(language_for_path(Path::new("tools/gen.PY")), "python");
+        assert_eq!(language_for_path(Path::new("Cargo.toml")), "toml");
+        assert_eq!(language_for_path(Path::new("web/app.tsx")), "typescript");
+        assert_eq!(language_for_path(Path::new("include/api.hpp")), "cpp");
+        assert_eq!(language_for_path(Path::new("LICENSE")), "text");
+    }
+
+    #[test]
+    fn read_file_preview_refuses_binary_and_oversized_files() {
+        let root = temp_dir("file_preview");
+        fs::create_dir_all(&root).expect("create root");
+        fs::write(root.join("small.rs"), b"fn main() {}\n\xff").expect("write text");
+        fs::write(root.join("big.txt"), "x".repeat(64)).expect("write big");
+        fs::write(root.join("image.png"), [0x89, b'P', b'N', b'G', 0, 1]).expect("write binary");
+
+        let preview = read_file_preview(&root.join("small.rs"), 32).expect("small file opens");
+        assert!(preview.starts_with("fn main() {}"));
+        assert!(preview.ends_with('\u{fffd}'));
+
+        assert!(read_file_preview(&root.join("big.txt"), 32)
+            .unwrap_err()
+            .contains("too large"));
+        assert!(read_file_preview(&root.join("image.png"), 32)
+            .unwrap_err()
+            .contains("binary"));
+        assert!(read_file_preview(&root, 32).is_err());
+        assert!(read_file_preview(&root.join("missing.rs"), 32).is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn workspace_file_resolution_refuses_paths_outside_the_workspace() {
+        let root = temp_dir("workspace_file");
+        let workspace = root.join("workspace");
+        fs::create_dir_all(workspace.join("src")).expect("create workspace");
+        fs::write(workspace.join("src/main.rs"), "fn main() {}").expect("write inside");
+        fs::write(root.join("secret.txt"), "secret").expect("write outside");
+
+        let inside = resolve_workspace_file(&workspace, "src/main.rs").expect("inside opens");
+        assert!(inside.ends_with("src/main.rs"));
+        assert!(resolve_workspace_file(&workspace, "../secret.txt").is_err());
+        assert!(resolve_workspace_file(&workspace, "src/../../secret.txt").is_err());
+        let absolute = root.join("secret.txt").to_string_lossy().to_string();
+        assert!(resolve_workspace_file(&workspace, &absolute).is_err());
+        assert!(resolve_workspace_file(&workspace, "missing.rs").is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn file_tree_excludes_gitignored_entries() {
+        let root = temp_dir("file_tree_gitignore");
+        fs::create_dir_all(root.join("node_modules/pkg")).expect("create node_modules");
+        fs::create_dir_all(root.join("dist")).expect("create dist");
+        fs::create_dir_all(root.join("src")).expect("create src");
+        fs::write(root.join(".gitignore"), "node_modules/\ndist\n*.log\n").expect("write ignore");
+        fs::write(root.join("debug.log"), "").expect("write log");
+        fs::write(root.join("src/lib.rs"), "").expect("write lib");
+        fs::write(root.join("src/trace.log"), "").expect("write nested log");
+
+        let tree = build_file_tree(&root, &root, 3, FILE_TREE_MAX_ENTRIES, &[], false)
+            .expect("tree should build");
+        let names = tree
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["src", ".gitignore"]);
+        let src_paths = tree[0]
+            .children
+            .iter()
+            .map(|node| node.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(src_paths, vec!["src/lib.rs"]);
+
+        // Listing a subdirectory still honours the workspace-level `.gitignore`.
+        let nested = build_file_tree(
+            &root.join("src"),
+            &root,
+            2,
+            FILE_TREE_MAX_ENTRIES,
+            &[],
+            false,
+        )
+        .expect("subtree should build");
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0].path, "src/lib.rs");
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn block_saved_as_template_is_valid_and_resolvable() {
+        let root = temp_dir("block_template");
+        let source = block("block-1", "builtin.file_listing.default", 1);
+        let template = template_from_block(&source.state, " Team Listing ", "User Team/Listing");
+        assert_eq!(template.meta.id, "user_team_listing");
+        assert_eq!(template.meta.title, "Team Listing");
+        assert_eq!(template.match_rules.primary, "file_listing");
+        assert_eq!(template.schema, source.state.schema);
+
+        let providers: Vec<Box<dyn CatalogProvider>> =
+            vec![Box::new(UserCatalogProvider::new("user-local", &root))];
+        let mut manager = CatalogManager::new(providers, false);
+        manager
+            .upsert_user_template(&template)
+            .expect("block template should save");
+        assert!(manager.load_diagnostics().is_empty());
+        assert!(manager.find_by_id("user_team_listing").is_some());
+
+        let resolution = manager.resolve(&source.state.intent);
+        let selected = resolution
+            .selected
+            .expect("saved block template should resolve for its intent");
+        assert_eq!(selected.template_id(), "user_team_listing");
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn cancelled_stream_becomes_truncated_assistant_message() {
+        let message = cancelled_assistant_message("Here is the first half  ", "42".to_string())
+            .expect("partial output should be kept");
+        assert_eq!(message.role, "assistant");
+        assert_eq!(
+            message.content,
+            "Here is the first half\n\n[response cancelled]"
+        );
+        assert_eq!(message.timestamp, "42");
+
+        assert!(cancelled_assistant_message("   ", "42".to_string()).is_none());
+    }
+
+    #[test]
+    fn regenerate_trims_trailing_reply_or_error_only() {
+        let message = |role: &str, content: &str| crate::session::Message {
+            role: role.to_string(),
+            content: content.to_string(),
+            timestamp: "0".to_string(),
+            tool: None,
+        };
+
+        let mut transcript = vec![
+            message("user", "first"),
+            message("assistant", "first answer"),
+            message("user", "second"),
+            message("assistant", "second answer"),
+        ];
+        assert_eq!(
+            trim_for_regenerate(&mut transcript).as_deref(),
+            Some("second")
+        );
+        assert_eq!(transcript.len(), 3);
+        assert_eq!(transcript[2].content, "second");
+
+        // Last turn is the user's: nothing to regenerate.
+        assert!(trim_for_regenerate(&mut transcript).is_none());
+        assert_eq!(transcript.len(), 3);
+
+        let mut empty = Vec::new();
+        assert!(trim_for_regenerate(&mut empty).is_none());
+
+        let mut orphan = vec![message("assistant", "hello")];
+        assert!(trim_for_regenerate(&mut orphan).is_none());
+        assert_eq!(orphan.len(), 1);
+
+        let mut failed = vec![
+            message("user", "first"),
+            message("error", "The response failed: connection reset"),
+        ];
+        assert_eq!(trim_for_regenerate(&mut failed).as_deref(), Some("first"));
+        assert_eq!(failed.len(), 1);
+    }
+
+    #[test]
+    fn truncate_transcript_after_keeps_edited_turn_and_drops_later_ones() {
+        let transcript = ["first", "first answer", "second", "second answer"]
+            .iter()
+            .enumerate()
+            .map(|(index, content)| crate::session::Message {
+                role: if index % 2 == 0 { "user" } else { "assistant" }.to_string(),
+                content: content.to_string(),
+                timestamp: "0".to_string(),
+                tool: None,
+            })
+            .collect::<Vec<_>>();
+
+        // Editing the first user message discards every later turn.
+        let mut first = transcript.clone();
+        assert!(truncate_transcript_after(&mut first, 0));
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].content, "first");
+
+        // Editing the last user message only drops its reply.
+        let mut last = transcript.clone();
+        assert!(truncate_transcript_after(&mut last, 2));
+        assert_eq!(last.len(), 3);
+        assert_eq!(last[2].content, "second");
+
+        let mut untouched = transcript.clone();
+        assert!(!truncate_transcript_after(&mut untouched, 4));
+        assert_eq!(untouched.len(), 4);
+    }
+
+    #[test]
+    fn collapsed_preview_cuts_at_word_boundaries() {
+        assert_eq!(collapsed_preview("short reply", 24, 2_000), None);
+        assert_eq!(
+            collapsed_preview("alpha beta gamma", 0, 8).as_deref(),
+            Some("alpha…")
+        );
+        // A cut that lands exactly on a space keeps the whole preceding word.
+        assert_eq!(
+            collapsed_preview("alpha beta gamma", 0, 10).as_deref(),
+            Some("alpha beta…")
+        );
+        assert_eq!(
+            collapsed_preview("one\ntwo\nthree\nfour", 2, 0).as_deref(),
+            Some("one\ntwo…")
+        );
+        // Trailing whitespace past the limit hides nothing.
+        assert_eq!(collapsed_preview("one\ntwo\n", 2, 0), None);
+        // A single unbroken word falls back to a hard cut.
+        assert_eq!(
+            collapsed_preview("abcdefghij", 0, 4).as_deref(),
+            Some("abcd…")
+        );
+        assert_eq!(collapsed_preview("alpha beta gamma", 0, 0), None);
+    }
+
+    #[test]
+    fn transcript_search_finds_messages_ignoring_case() {
+        let transcript = vec![
+            crate::session::Message {
+                role: "user".to_string(),
+                content: "Where is the Catalog loaded?".to_string(),
+                timestamp: "0".to_string(),
+                tool: None,
+            },
+            tool_chip_message(
+                "query_ui_catalog",
+                ToolStatus::RenderedCatalog,
+                "0".to_string(),
+            ),
+            crate::session::Message {
+                role: "assistant".to_string(),
+                content: "In src/ui/catalog.rs.".to_string(),
+                timestamp: "0".to_string(),
+                tool: None,
+            },
+        ];
+
+        assert_eq!(transcript_search(&transcript, "CATALOG"), vec![0, 2]);
+        assert_eq!(transcript_search(&transcript, "  loaded "), vec![0]);
+        assert!(transcript_search(&transcript, "session").is_empty());
+        assert!(transcript_search(&transcript, "   ").is_empty());
+    }
+
+    #[test]
+    fn queued_prompts_drain_one_per_completed_turn_in_order() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+        let last_user_prompt = |app: &BrownieApp| {
+            app.transcript
+                .iter()
+                .rev()
+                .find(|message| message.role == "user")
+                .map(|message| message.content.clone())
+        };
+        app.awaiting_assistant_turn = true;
+        app.prompt_queue
+            .extend(["second".to_string(), "third".to_string()]);
+
+        // Streaming: deltas never drain the queue.
+        app.apply_event(AppEvent::StreamDelta("first reply".to_string()), None);
+        assert_eq!(app.prompt_queue.len(), 2);
+
+        // Each completed turn sends exactly one prompt, which starts the next turn.
+        app.apply_event(AppEvent::StreamEnd, None);
+        assert_eq!(last_user_prompt(app).as_deref(), Some("second"));
+        assert!(app.awaiting_assistant_turn);
+        assert_eq!(app.prompt_queue.len(), 1);
+
+        // A cancelled turn discards the rest instead of sending it.
+        app.cancel_turn();
+        app.apply_event(AppEvent::StreamEnd, None);
+        app.apply_event(AppEvent::ResponseCancelled, None);
+        assert_eq!(last_user_prompt(app).as_deref(), Some("second"));
+        assert!(app.prompt_queue.is_empty());
+        assert!(!app.awaiting_assistant_turn);
+    }
+
+    #[test]
+    fn persist_debounce_coalesces_writes_within_interval() {
+        let interval = Duration::from_millis(500);
+        let start = Instant::now();
+        let mut debounce = PersistDebounce::default();
+        assert!(!debounce.is_due(start, interval));
+
+        debounce.mark_dirty();
+        assert!(debounce.is_due(start, interval), "first write is immediate");
+        debounce.mark_written(start);
+        assert_eq!(debounce.wait_for(start, interval), None);
+
+        debounce.mark_dirty();
+        debounce.mark_dirty();
+        let soon = start + Duration::from_millis(200);
+        assert!(!debounce.is_due(soon, interval));
+        assert_eq!(
+            debounce.wait_for(soon, interval),
+            Some(Duration::from_millis(300))
+        );
+        assert!(debounce.is_due(start + interval, interval));
+    }
+
+    #[test]
+    fn new_session_reset_leaves_no_stale_blocks() {
+        let mut blocks = vec![
+            block("block-1", "builtin.file_listing.default", 1),
+            block("block-2", "builtin.code_review.default", 2),
+        ];
+        let mut active_block_id = Some("block-2".to_string());
+        let mut event_log = UiEventLog::default();
+        event_log.push(UiEvent::TreeNodeClicked {
+            component_id: "workspace_tree".to_string(),
+            path: "src/main.rs".to_string(),
+        });
+
+        reset_canvas_for_new_session(&mut blocks, &mut active_block_id, &mut event_log);
+
+        assert!(blocks.is_empty());
+        assert!(active_block_id.is_none());
+        assert!(event_log.entries().is_empty());
+    }
+
+    #[test]
+    fn pending_permission_blocks_composer_until_resolved() {
+        let mut permissions = VecDeque::new();
+        assert!(composer_enabled(true, false, false, &permissions));
+
+        permissions.push_back(PendingPermission {
+            tool_call_id: "call-1".to_string(),
+            tool_name: "shell".to_string(),
+            details: "fullCommandText=cargo test".to_string(),
+        });
+        assert!(!composer_enabled(true, false, false, &permissions));
+        assert!(!composer_enabled(true, true, false, &permissions));
+
+        permissions.pop_front();
+        assert!(composer_enabled(true, false, false, &permissions));
+        assert!(!composer_enabled(false, false, false, &permissions));
+    }
+
+    #[test]
+    fn timed_out_permission_is_dropped_and_unlocks_composer() {
+        let request = |id: &str| PendingPermission {
+            tool_call_id: id.to_string(),
+            tool_name: "shell".to_string(),
+            details: String::new(),
+        };
+        let mut permissions = VecDeque::from([request("call-1"), request("call-2")]);
+
+        assert_eq!(
+            remove_pending_permission(&mut permissions, "call-2"),
+            Some(request("call-2"))
+        );
+        assert_eq!(remove_pending_permission(&mut permissions, "missing"), None);
+        assert!(!composer_enabled(true, false, false, &permissions));
+
+        remove_pending_permission(&mut permissions, "call-1");
+        assert!(composer_enabled(true, false, false, &permissions));
+    }
+
+    #[test]
+    fn tool_chips_map_status_to_label_and_color() {
+        let theme = crate::theme::Theme::default();
+        let style = |tool_name: &str, status: &str| {
+            let message = tool_chip_message(tool_name, ToolStatus::parse(status), "0".into());
+            tool_chip_style(&theme, &message)
+        };
+
+        let (label, color) = style("query_ui_catalog", "rendered_catalog");
+        assert_eq!(label, "🔧 query_ui_catalog → rendered_catalog");
+        assert_eq!(color, theme.success);
+
+        assert_eq!(style("query_ui_catalog", "text_only").1, theme.warning);
+        assert_eq!(style("shell", "suppressed").1, theme.warning);
+        assert_eq!(style("shell", "denied").1, theme.warning);
+        assert_eq!(style("query_ui_catalog", "error").1, theme.danger);
+        let (label, color) = style("write", "something_new");
+        assert_eq!(label, "🔧 write → something_new");
+        assert_eq!(color, theme.text_muted);
+
+        // Chip color and diagnostic level come from the same mapping.
+        for status in ["text_only", "suppressed", "denied", "not_found", "error"] {
+            let status = ToolStatus::parse(status);
+            let message = tool_chip_message("shell", status.clone(), "0".into());
+            assert_eq!(
+                tool_chip_style(&theme, &message).1,
+                tool_outcome_level(&status).color(&theme)
+            );
+        }
+
+        let message = tool_chip_message(
+            "shell",
+            ToolStatus::Suppressed,
+            "2026-01-01T00:00:00Z".into(),
+        );
+        assert_eq!(message.role, "tool");
+        assert_eq!(message.content, "shell → suppressed");
+        let saved = serde_json::to_value(&message).expect("message serializes");
+        assert_eq!(saved["tool"]["status"], "suppressed");
+        let restored: crate::session::Message =
+            serde_json::from_value(saved).expect("message deserializes");
+        assert_eq!(restored.tool, message.tool);
+
+        // Tool messages saved before outcomes were recorded still render their text.
+        let legacy = crate::session::Message {
+            role: "tool".to_string(),
+            content: "shell → suppressed".to_string(),
+            timestamp: "0".to_string(),
+            tool: None,
+        };
+        assert_eq!(
+            tool_chip_style(&theme, &legacy),
+            ("🔧 shell → suppressed".to_string(), theme.text_muted)
+        );
+    }
+
+    #[test]
+    fn failed_cancel_lets_the_turn_end_normally() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+        app.awaiting_assistant_turn = true;
+
+        app.cancel_turn();
+        app.apply_event(AppEvent::CancelFailed("abort rejected".to_string()), None);
+        assert!(!app.cancel_requested);
+
+        app.apply_event(AppEvent::StreamDelta("full reply".to_string()), None);
+        app.apply_event(AppEvent::StreamEnd, None);
+        assert_eq!(app.transcript.len(), 1);
+        assert_eq!(app.transcript[0].content, "full reply");
+        assert!(!app.awaiting_assistant_turn);
+    }
+
+    #[test]
+    fn stream_end_before_response_cancelled_closes_turn_as_cancelled() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+        app.awaiting_assistant_turn = true;
+        app.apply_event(AppEvent::StreamDelta("partial".to_string()), None);
+
+        app.cancel_turn();
+        app.apply_event(AppEvent::StreamDelta(" dropped".to_string()), None);
+        app.apply_event(AppEvent::StreamEnd, None);
+        app.apply_event(AppEvent::ResponseCancelled, None);
+
+        assert_eq!(app.transcript.len(), 1);
+        assert_eq!(
+            app.transcript[0].content,
+            format!("partial\n\n{CANCELLED_RESPONSE_MARKER}")
+        );
+        assert!(!app.cancel_requested);
+        assert!(!app.awaiting_assistant_turn);
+
+        // The late ResponseCancelled must not mute the next turn.
+        app.awaiting_assistant_turn = true;
+        app.apply_event(AppEvent::StreamDelta("next".to_string()), None);
+        assert_eq!(app.in_progress_assistant, "next");
+    }
+
+    #[test]
+    fn stream_end_drains_queue_only_when_no_cancel_is_pending() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+
+        app.awaiting_assistant_turn = true;
+        app.prompt_queue.push_back("follow-up".to_string());
+        app.cancel_turn();
+        app.apply_event(AppEvent::StreamEnd, None);
+        app.apply_event(AppEvent::ResponseCancelled, None);
+        assert!(app.prompt_queue.is_empty());
+        assert!(!app.awaiting_assistant_turn, "cancelled turn must not send");
+        let note = app.transcript.last().expect("discard note");
+        assert_eq!(note.role, "error");
+        assert!(note.content.contains("follow-up"));
+
+        app.awaiting_assistant_turn = true;
+        app.prompt_queue.push_back("next question".to_string());
+        app.apply_event(AppEvent::StreamEnd, None);
+        assert!(app.prompt_queue.is_empty());
+        assert!(
+            app.awaiting_assistant_turn,
+            "completed turn sends the queued prompt"
+        );
+        assert_eq!(
+            app.transcript
+                .last()
+                .map(|message| message.content.as_str()),
+            Some("next question")
+        );
+    }
+
+    #[test]
+    fn reconnect_keeps_transcript_canvas_and_queue() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+        app.current_session = Some(SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "session-1".to_string(),
+            workspace: app.workspace.to_string_lossy().to_string(),
+            title: None,
+            created_at: "0".to_string(),
+            canvas_workspace: CanvasWorkspaceState::default(),
+            messages: Vec::new(),
+        });
+        app.transcript.push(crate::session::Message {
+            role: "user".to_string(),
+            content: "hello".to_string(),
+            timestamp: "0".to_string(),
+            tool: None,
+        });
+        app.canvas_blocks
+            .push(block("block-1", "builtin.file_listing.default", 1));
+        app.prompt_queue.push_back("follow-up".to_string());
+
+        app.apply_event(AppEvent::SessionReconnected("session-2".to_string()), None);
+
+        assert_eq!(
+            app.current_session
+                .as_ref()
+                .map(|session| session.session_id.as_str()),
+            Some("session-1")
+        );
+        assert_eq!(app.transcript.len(), 1);
+        assert_eq!(app.canvas_blocks.len(), 1);
+        assert_eq!(app.prompt_queue.len(), 1);
+    }
+
+    #[test]
+    fn interrupted_turn_discards_queue_with_note() {
+        let mut queue = VecDeque::new();
+        assert!(discard_queued_prompts(&mut queue, "the response failed", "0".into()).is_none());
+
+        queue.extend(["first".to_string(), "second ".to_string()]);
+        let note = discard_queued_prompts(&mut queue, "the response was cancelled", "0".into())
+            .expect("queued prompts should produce a note");
+        assert!(queue.is_empty());
+        assert_eq!(note.role, "error");
+        assert_eq!(
+            note.content,
+            "2 queued prompts were not sent because the response was cancelled:\n- first\n- second"
+        );
+    }
+
+    #[test]
+    fn sdk_error_adds_error_bubble_only_when_turn_was_pending() {
+        assert!(turn_error_message(false, "connection reset", "0".to_string()).is_none());
+
+        let message = turn_error_message(true, "connection reset\n", "0".to_string())
+            .expect("pending turn should get an error bubble");
+        assert_eq!(message.role, "error");
+        assert_eq!(message.content, "The response failed: connection reset");
+    }
+
+    #[test]
+    fn failed_turn_drops_partial_reply_and_can_be_retried() {
+        let mut fixture = app_fixture();
+        let app = &mut fixture.app;
+        app.transcript.push(crate::session::Message {
+            role: "user".to_string(),
+            content: "hello".to_string(),
+            timestamp: "0".to_string(),
+            tool: None,
+        });
+        app.is_streaming = true;
+        app.in_progress_assistant.push_str("partial answ");
+
+        app.apply_event(AppEvent::SdkError("connection reset".to_string()), None);
+
+        assert!(app.in_progress_assistant.is_empty());
+        assert_eq!(app.transcript.len(), 2);
+        assert_eq!(
+            trim_for_regenerate(&mut app.transcript).as_deref(),
+            Some("hello")
+        );
+    }
+
+    #[test]
+    fn canvas_warning_follows_latest_catalog_outcome() {
+        let showing =
+            canvas_warning_after_outcome(false, "query_ui_catalog", &ToolStatus::TextOnly);
+        assert!(showing);
+        let showing = canvas_warning_after_outcome(showing, "shell", &ToolStatus::RenderedCatalog);
+        assert!(showing, "other tools leave the banner alone");
+        let showing =
+            canvas_warning_after_outcome(showing, "query_ui_catalog", &ToolStatus::RenderedCatalog);
+        assert!(!showing);
+        let showing = canvas_warning_after_outcome(showing, "query_ui_catalog", &ToolStatus::Error);
+        assert!(showing);
+        let showing = canvas_warning_after_outcome(
+            showing,
+            "query_ui_catalog",
+            &ToolStatus::RenderedProvisional,
+        );
+        assert!(!showing);
+    }
 }
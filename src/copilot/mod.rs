@@ -1,28 +1,142 @@
 use crate::event::AppEvent;
-use crate::ui::catalog::{CatalogManager, TemplateDocument, TemplateMatch, TemplateMeta, UiIntent};
-use crate::ui::intent::intent_from_text;
+use crate::session::ToolStatus;
+use crate::settings::Settings;
+use crate::ui::catalog::{
+    CatalogManager, SharedCatalog, TemplateDocument, TemplateMatch, TemplateMeta, UiIntent,
+};
+use crate::ui::intent::intent_matches_from_text;
+use crate::ui::workspace::{
+    resolve_title_target, CanvasBlockSummary, SharedCanvasSnapshot, TitleTarget,
+};
 use copilot_sdk::{
-    Client, ConnectionState, Session, SessionConfig, SessionEventData, SystemMessageConfig,
-    SystemMessageMode, Tool, ToolHandler, ToolResultObject,
+    Client, ConnectionState, PermissionRequest, PermissionRequestResult, Session, SessionConfig,
+    SessionEventData, SystemMessageConfig, SystemMessageMode, Tool, ToolHandler, ToolResultObject,
 };
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{mpsc, Arc};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::runtime::Handle;
-use tokio::sync::RwLock;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::sync::{Mutex, Notify, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::{self, Duration};
 
+/// Tools implemented by Brownie itself; always allowed and never prompted for.
+const HOST_TOOLS: [&str; 2] = ["query_ui_catalog", "list_canvas_blocks"];
+/// SDK execution tools Active Mode unlocks, each still gated by a permission prompt.
+const ACTIVE_MODE_TOOLS: [&str; 3] = ["shell", "powershell", "write"];
+/// Permission request kind the SDK uses for tools registered by the host.
+const CUSTOM_TOOL_PERMISSION_KIND: &str = "custom-tool";
+const PERMISSION_TIMEOUT: Duration = Duration::from_secs(300);
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+const RECONNECT_MAX_ATTEMPTS: u32 = 6;
+
+/// Which tools the SDK session may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionMode {
+    /// Only the canvas tools; execution tools are excluded outright.
+    #[default]
+    Passive,
+    /// Shell and write tools are available, each gated by a permission prompt.
+    Active,
+}
+
+impl SessionMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Passive => "Passive Mode",
+            Self::Active => "Active Mode",
+        }
+    }
+}
+
+/// SDK tools to exclude per session mode; names are passed to the SDK as given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolExclusions {
+    pub passive: Vec<String>,
+    pub active: Vec<String>,
+}
+
+impl From<&Settings> for ToolExclusions {
+    fn from(settings: &Settings) -> Self {
+        fn clean(tools: &[String]) -> Vec<String> {
+            tools
+                .iter()
+                .map(|tool| tool.trim())
+                .filter(|tool| !tool.is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        }
+        Self {
+            passive: clean(&settings.passive_excluded_tools),
+            active: clean(&settings.active_excluded_tools),
+        }
+    }
+}
+
+type PermissionReplies = Arc<std::sync::Mutex<HashMap<String, mpsc::Sender<bool>>>>;
+type CustomTools = Arc<std::sync::Mutex<Vec<(Tool, ToolHandler)>>>;
+
+/// Latest canvas summary from the app and whether the live session has seen it yet.
+#[derive(Debug, Default)]
+struct CanvasContext {
+    summary: String,
+    pending: bool,
+}
+
 #[derive(Clone)]
 pub struct CopilotClient {
     workspace: PathBuf,
+    catalog: SharedCatalog,
+    tool_exclusions: ToolExclusions,
     tx: mpsc::Sender<AppEvent>,
+    canvas_snapshot: SharedCanvasSnapshot,
+    custom_tools: CustomTools,
+    canvas_context: Arc<std::sync::Mutex<CanvasContext>>,
     client: Arc<Client>,
     session: Arc<RwLock<Option<Arc<Session>>>>,
+    listener: Arc<Mutex<Option<JoinHandle<()>>>>,
+    mode: Arc<RwLock<SessionMode>>,
+    permission_replies: PermissionReplies,
+    permission_nonce: Arc<AtomicU64>,
     runtime_handle: Handle,
     state_poller_started: Arc<AtomicBool>,
+    reconnecting: ConnectGuard,
+    retry_now: Arc<Notify>,
+}
+
+/// Why a session is opened. Reconnects report `SessionReconnected` so the app keeps
+/// its transcript, canvas and queued prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionOpen {
+    Fresh,
+    Reconnect,
+}
+
+/// Serializes connection attempts. `restart` and the reconnect loop both hold a
+/// permit while they connect, so they never open sessions side by side.
+#[derive(Clone, Default)]
+struct ConnectGuard(Arc<AtomicBool>);
+
+impl ConnectGuard {
+    fn try_acquire(&self) -> Option<ConnectPermit> {
+        self.0
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .ok()
+            .map(|_| ConnectPermit(Arc::clone(&self.0)))
+    }
+}
+
+/// Releases its [`ConnectGuard`] when dropped.
+struct ConnectPermit(Arc<AtomicBool>);
+
+impl Drop for ConnectPermit {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
 }
 
 impl CopilotClient {
@@ -39,13 +153,17 @@ Current Canvas capabilities:
 - code_review template: markdown, form fields, diff, action buttons
 - plan_review template: markdown, form fields, action button
 - file_listing template: generic file explorer block rendered in canvas (set `root_path` when needed)
+- compare template: two versions side by side with a line diff
+- dashboard template: project status overview with key facts, progress, and recent items
 
 Behavior requirements:
 - Do not claim there is no canvas or that the UI is terminal-only.
 - Use the `query_ui_catalog` tool for requests about showing UI in canvas.
 - For requests to show/list/browse workspace files in canvas, call `query_ui_catalog` before answering and pass the user's request text in `query`.
 - For file browsing requests, pass `root_path` when you want a specific directory root.
+- Call `list_canvas_blocks` to see which canvas blocks are open before deciding whether to update, focus, or create one.
 - Prefer updating/focusing existing canvas blocks when the same template is already present, instead of repeatedly creating replacement views.
+- To focus, minimize, or close an existing block, call `query_ui_catalog` with `operation` set accordingly and a `target_block_id` or `target_title`.
 - Never claim that something is rendered unless `query_ui_catalog` in the same turn returns `status=rendered_catalog` or `status=rendered_provisional`.
 - If `query_ui_catalog` returns `status=text_only` or any error, explicitly say canvas was not rendered and provide a text fallback.
 - If `query_ui_catalog` reports `rendered_catalog` or `rendered_provisional`, confirm what was rendered.
@@ -59,6 +177,12 @@ Behavior requirements:
             .schema(json!({
                 "type": "object",
                 "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": ["render", "focus", "minimize", "close"],
+                        "description": "What to do: render a template (default) or focus, minimize, or close an existing block",
+                        "default": "render"
+                    },
                     "query": {
                         "type": "string",
                         "description": "Natural-language request to evaluate against the UI catalog"
@@ -83,6 +207,10 @@ Behavior requirements:
                         "type": "string",
                         "description": "Optional explicit canvas block id to update or focus"
                     },
+                    "target_title": {
+                        "type": "string",
+                        "description": "Optional canvas block title to update; matched case-insensitively and ignored when target_block_id is set"
+                    },
                     "allow_provisional": {
                         "type": "boolean",
                         "description": "When no catalog template matches, create and render a provisional template",
@@ -92,7 +220,30 @@ Behavior requirements:
             }))
     }
 
-    fn query_ui_catalog_handler(workspace: PathBuf, tx: mpsc::Sender<AppEvent>) -> ToolHandler {
+    fn list_canvas_blocks_tool() -> Tool {
+        Tool::new("list_canvas_blocks")
+            .description("List the blocks currently open in the Brownie canvas")
+            .schema(json!({
+                "type": "object",
+                "properties": {}
+            }))
+    }
+
+    fn list_canvas_blocks_handler(canvas_snapshot: SharedCanvasSnapshot) -> ToolHandler {
+        Arc::new(move |_name, _args| {
+            let blocks = canvas_snapshot
+                .read()
+                .map(|blocks| blocks.clone())
+                .unwrap_or_default();
+            ToolResultObject::text(json!({ "blocks": blocks }).to_string())
+        })
+    }
+
+    fn query_ui_catalog_handler(
+        catalog: SharedCatalog,
+        canvas_snapshot: SharedCanvasSnapshot,
+        tx: mpsc::Sender<AppEvent>,
+    ) -> ToolHandler {
         Arc::new(move |_name, args| {
             let query = extract_tool_query(args).unwrap_or_else(fallback_canvas_query);
 
@@ -104,9 +255,40 @@ Behavior requirements:
                 .get("target_block_id")
                 .and_then(|value| value.as_str())
                 .map(ToOwned::to_owned);
+            let target_title = args
+                .get("target_title")
+                .and_then(|value| value.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned);
             let root_path = extract_string_argument(args, &["root_path", "root", "path"]);
+            let operation = args
+                .get("operation")
+                .and_then(|value| value.as_str())
+                .map(|value| value.trim().to_ascii_lowercase())
+                .filter(|value| !value.is_empty())
+                .unwrap_or_else(|| "render".to_string());
+
+            if operation != "render" {
+                let (result, event) = {
+                    let blocks = canvas_snapshot
+                        .read()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    block_operation_result(
+                        &operation,
+                        target_block_id.clone(),
+                        target_title.clone(),
+                        &blocks,
+                    )
+                };
+                if let Some(event) = event {
+                    let _ = tx.send(event);
+                }
+                return ToolResultObject::text(result.to_string());
+            }
 
-            let Some(intent) = intent_from_text(query.as_str()) else {
+            let mut candidates = intent_matches_from_text(query.as_str()).into_iter();
+            let Some(best) = candidates.next() else {
                 return ToolResultObject::text(
                     json!({
                         "status": "text_only",
@@ -117,9 +299,30 @@ Behavior requirements:
                 );
             };
 
-            let user_catalog_dir = workspace.join(".brownie").join("catalog");
-            let catalog_manager = CatalogManager::with_default_providers(user_catalog_dir, false);
-            let resolution = catalog_manager.resolve(&intent);
+            let (intent, confidence, reason) = (best.intent, best.confidence, best.trace.lines());
+            let alternatives = candidates
+                .map(|alternative| {
+                    format!(
+                        "{} ({:.2})",
+                        alternative.intent.primary, alternative.confidence
+                    )
+                })
+                .collect::<Vec<_>>();
+            if !alternatives.is_empty() {
+                let _ = tx.send(AppEvent::IntentAlternatives(format!(
+                    "picked intent {} ({confidence:.2}); alternatives: {}",
+                    intent.primary,
+                    alternatives.join(", ")
+                )));
+            }
+
+            let resolution = {
+                let mut catalog = catalog
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                catalog.reload_if_changed();
+                catalog.resolve(&intent)
+            };
 
             if let Some(template) = resolution.selected {
                 let event = AppEvent::CanvasToolRender {
@@ -129,6 +332,7 @@ Behavior requirements:
                     provider_id: template.source.provider_id.clone(),
                     provider_kind: template.source.kind.as_str().to_string(),
                     target_block_id: target_block_id.clone(),
+                    target_title: target_title.clone(),
                     root_path: root_path.clone(),
                     schema: template.schema_value().clone(),
                     provisional_template: None,
@@ -139,10 +343,13 @@ Behavior requirements:
                     json!({
                         "status": "rendered_catalog",
                         "intent": intent.summary(),
+                        "confidence": confidence,
+                        "intent_reason": reason,
                         "template_id": template.document.meta.id,
                         "title": template.document.meta.title,
                         "provider": template.source.provider_id,
                         "target_block_id": target_block_id,
+                        "target_title": target_title,
                         "root_path": root_path,
                         "needs_save_confirmation": false
                     })
@@ -169,6 +376,7 @@ Behavior requirements:
                 provider_id: "runtime-provisional".to_string(),
                 provider_kind: "provisional".to_string(),
                 target_block_id: target_block_id.clone(),
+                target_title: target_title.clone(),
                 root_path: root_path.clone(),
                 schema: provisional.schema.clone(),
                 provisional_template: Some(provisional.clone()),
@@ -179,9 +387,12 @@ Behavior requirements:
                 json!({
                     "status": "rendered_provisional",
                     "intent": intent.summary(),
+                    "confidence": confidence,
+                    "intent_reason": reason,
                     "template_id": provisional.meta.id,
                     "title": provisional.meta.title,
                     "target_block_id": target_block_id,
+                    "target_title": target_title,
                     "root_path": root_path,
                     "needs_save_confirmation": true
                 })
@@ -190,7 +401,12 @@ Behavior requirements:
         })
     }
 
-    pub fn new(workspace: PathBuf, tx: mpsc::Sender<AppEvent>) -> copilot_sdk::Result<Self> {
+    pub fn new(
+        workspace: PathBuf,
+        tx: mpsc::Sender<AppEvent>,
+        org_catalog_url: Option<String>,
+        tool_exclusions: ToolExclusions,
+    ) -> copilot_sdk::Result<Self> {
         let runtime_handle = Handle::try_current().map_err(|err| {
             copilot_sdk::CopilotError::InvalidConfig(format!("tokio runtime unavailable: {err}"))
         })?;
@@ -201,104 +417,345 @@ Behavior requirements:
             .cwd(workspace.clone())
             .build()?;
 
+        let catalog = CatalogManager::with_default_providers(
+            workspace.join(".brownie").join("catalog"),
+            org_catalog_url.as_deref(),
+        );
         Ok(Self {
             workspace,
+            catalog: Arc::new(std::sync::Mutex::new(catalog)),
+            tool_exclusions,
             tx,
+            canvas_snapshot: SharedCanvasSnapshot::default(),
+            custom_tools: Arc::new(std::sync::Mutex::new(Vec::new())),
+            canvas_context: Arc::new(std::sync::Mutex::new(CanvasContext::default())),
             client: Arc::new(client),
             session: Arc::new(RwLock::new(None)),
+            listener: Arc::new(Mutex::new(None)),
+            mode: Arc::new(RwLock::new(SessionMode::default())),
+            permission_replies: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            permission_nonce: Arc::new(AtomicU64::new(0)),
             runtime_handle,
             state_poller_started: Arc::new(AtomicBool::new(false)),
+            reconnecting: ConnectGuard::default(),
+            retry_now: Arc::new(Notify::new()),
         })
     }
 
+    /// Snapshot the app keeps current so `list_canvas_blocks` can report open blocks.
+    pub fn canvas_snapshot(&self) -> SharedCanvasSnapshot {
+        Arc::clone(&self.canvas_snapshot)
+    }
+
+    /// Template catalog shared by the UI and the `query_ui_catalog` tool.
+    pub fn catalog(&self) -> SharedCatalog {
+        Arc::clone(&self.catalog)
+    }
+
+    /// Loads remote catalog providers on the blocking pool so network fetches never
+    /// run on the UI thread. Each result arrives as `AppEvent::CatalogRemoteLoaded`.
+    /// Returns how many providers are being fetched.
+    pub fn refresh_remote_catalog(&self) -> usize {
+        let refreshes = self
+            .catalog
+            .lock()
+            .map(|catalog| catalog.remote_refreshes())
+            .unwrap_or_default();
+        let count = refreshes.len();
+        for refresh in refreshes {
+            let tx = self.tx.clone();
+            self.runtime_handle.spawn_blocking(move || {
+                let _ = tx.send(AppEvent::CatalogRemoteLoaded(refresh.fetch()));
+            });
+        }
+        count
+    }
+
+    /// Adds an embedder-defined tool next to the built-in canvas tools.
+    ///
+    /// Tools are attached when a session is created, so call this after `new` and
+    /// before `start`. A tool registered later only reaches sessions created
+    /// afterwards (new chat, mode switch, or reconnect). Registered tools are
+    /// available in Passive Mode; in Active Mode each call asks for permission.
+    #[allow(dead_code)] // Embedder API; the Brownie binary registers no extra tools.
+    pub fn register_tool(&self, tool: Tool, handler: ToolHandler) {
+        if let Ok(mut tools) = self.custom_tools.lock() {
+            tools.push((tool, handler));
+        }
+    }
+
+    /// Tools added with `register_tool`, in registration order.
+    fn registered_tools(&self) -> Vec<(Tool, ToolHandler)> {
+        self.custom_tools
+            .lock()
+            .map(|tools| tools.clone())
+            .unwrap_or_default()
+    }
+
+    /// Records the current canvas summary for the assistant. New sessions get it in the
+    /// system message; a live session gets it as a context note on the next prompt.
+    pub fn update_context(&self, summary: String) {
+        if let Ok(mut context) = self.canvas_context.lock() {
+            if context.summary != summary {
+                context.summary = summary;
+                context.pending = true;
+            }
+        }
+    }
+
     pub fn start(&self) {
         let _ = self
             .tx
             .send(AppEvent::StatusChanged(ConnectionState::Connecting));
         self.spawn_state_poller();
 
-        let client = Arc::clone(&self.client);
-        let tx = self.tx.clone();
-        let workspace = self.workspace.clone();
-        let session_slot = Arc::clone(&self.session);
-        let runtime_handle = self.runtime_handle.clone();
-
+        let this = self.clone();
         self.runtime_handle.spawn(async move {
-            if let Err(err) = client.start().await {
+            this.connect(SessionOpen::Fresh).await;
+        });
+    }
+
+    /// Starts the CLI, checks auth, and opens a fresh session. Returns whether a
+    /// session is live afterwards; failures are reported as `StatusChanged(Error)`.
+    async fn connect(&self, open: SessionOpen) -> bool {
+        let tx = &self.tx;
+        if let Err(err) = self.client.start().await {
+            let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Error));
+            let _ = tx.send(AppEvent::SdkError(format!(
+                "failed to start Copilot client: {err}"
+            )));
+            return false;
+        }
+
+        match self.client.get_auth_status().await {
+            Ok(auth) if auth.is_authenticated => {
+                let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Connected));
+            }
+            Ok(auth) => {
+                let message = auth
+                    .status_message
+                    .unwrap_or_else(|| "copilot CLI is not authenticated".to_string());
+                let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Error));
+                let _ = tx.send(AppEvent::SdkError(message));
+                return false;
+            }
+            Err(err) => {
                 let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Error));
                 let _ = tx.send(AppEvent::SdkError(format!(
-                    "failed to start Copilot client: {err}"
+                    "failed to query auth status: {err}"
                 )));
-                return;
+                return false;
             }
+        }
 
-            match client.get_auth_status().await {
-                Ok(auth) if auth.is_authenticated => {
-                    let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Connected));
-                }
-                Ok(auth) => {
-                    let message = auth
-                        .status_message
-                        .unwrap_or_else(|| "copilot CLI is not authenticated".to_string());
-                    let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Error));
-                    let _ = tx.send(AppEvent::SdkError(message));
-                    return;
-                }
-                Err(err) => {
-                    let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Error));
-                    let _ = tx.send(AppEvent::SdkError(format!(
-                        "failed to query auth status: {err}"
-                    )));
-                    return;
+        self.close_session().await;
+        self.create_session(open).await;
+        self.session.read().await.is_some()
+    }
+
+    /// Retries `connect` with exponential backoff until it succeeds or
+    /// `RECONNECT_MAX_ATTEMPTS` is reached. Only one retry loop runs at a time.
+    fn spawn_reconnect(&self) {
+        let Some(permit) = self.reconnecting.try_acquire() else {
+            return;
+        };
+
+        let this = self.clone();
+        self.runtime_handle.spawn(async move {
+            let mut attempt = 0;
+            let mut connected = false;
+            while let Some(delay) = reconnect_delay(attempt) {
+                wait_for_retry(delay, &this.retry_now).await;
+                attempt += 1;
+                let _ = this
+                    .tx
+                    .send(AppEvent::StatusChanged(ConnectionState::Connecting));
+                if this.connect(SessionOpen::Reconnect).await {
+                    connected = true;
+                    break;
                 }
             }
+            if !connected {
+                let _ = this.tx.send(AppEvent::SdkError(format!(
+                    "gave up reconnecting after {attempt} attempts"
+                )));
+            }
+            drop(permit);
+        });
+    }
 
-            let query_ui_catalog_tool = Self::query_ui_catalog_tool();
-            let mut session_config = SessionConfig {
-                tools: vec![query_ui_catalog_tool.clone()],
-                available_tools: Some(vec!["query_ui_catalog".to_string()]),
-                excluded_tools: Some(vec![
-                    "shell".to_string(),
-                    "powershell".to_string(),
-                    "write".to_string(),
-                ]),
-                request_permission: Some(false),
-                system_message: Some(SystemMessageConfig {
-                    mode: Some(SystemMessageMode::Append),
-                    content: Some(Self::brownie_system_message().to_string()),
-                }),
-                ..Default::default()
-            };
-            session_config.working_directory = Some(workspace.to_string_lossy().to_string());
+    /// Replaces the active SDK session with a fresh one; the app resets on `SessionCreated`.
+    /// With `cancel_turn` the running turn is aborted first, and the abort is awaited so
+    /// none of its events land in the new session.
+    pub fn new_session(&self, cancel_turn: bool) {
+        let this = self.clone();
+        self.runtime_handle.spawn(async move {
+            if cancel_turn {
+                this.abort_turn().await;
+            }
+            this.replace_session().await;
+        });
+    }
+
+    /// Switches tool access and recreates the session so the new config takes effect.
+    pub fn set_mode(&self, mode: SessionMode) {
+        let this = self.clone();
+        self.runtime_handle.spawn(async move {
+            *this.mode.write().await = mode;
+            this.replace_session().await;
+        });
+    }
+
+    /// Answers a pending `ToolPermissionRequested`; unknown ids are ignored.
+    pub fn resolve_permission(&self, tool_call_id: &str, approved: bool) {
+        let reply = match self.permission_replies.lock() {
+            Ok(mut replies) => replies.remove(tool_call_id),
+            Err(_) => None,
+        };
+        if let Some(reply) = reply {
+            let _ = reply.send(approved);
+        }
+    }
 
-            match client.create_session(session_config).await {
-                Ok(session) => {
-                    let handler = Self::query_ui_catalog_handler(workspace.clone(), tx.clone());
+    /// Tears down the session and client, then re-runs the start sequence. While the
+    /// reconnect loop holds the connect guard it is told to skip its backoff and retry
+    /// now instead, so the two never open sessions side by side.
+    pub fn restart(&self) {
+        let _ = self
+            .tx
+            .send(AppEvent::StatusChanged(ConnectionState::Connecting));
+        let Some(permit) = self.reconnecting.try_acquire() else {
+            self.retry_now.notify_one();
+            return;
+        };
+        let this = self.clone();
+        self.runtime_handle.spawn(async move {
+            this.close_session().await;
+            // The client may already be dead; a failed stop must not block the restart.
+            let _ = this.client.stop().await;
+            this.connect(SessionOpen::Reconnect).await;
+            drop(permit);
+        });
+    }
+
+    async fn replace_session(&self) {
+        self.close_session().await;
+        self.create_session(SessionOpen::Fresh).await;
+    }
+
+    /// Stops the event listener and destroys the current session, leaving the slot empty.
+    async fn close_session(&self) {
+        if let Some(listener) = self.listener.lock().await.take() {
+            listener.abort();
+        }
+        let previous = take_slot(&self.session).await;
+        if let Some(previous) = previous {
+            if let Err(err) = previous.destroy().await {
+                let _ = self.tx.send(AppEvent::SdkError(format!(
+                    "failed to close previous session: {err}"
+                )));
+            }
+        }
+    }
+
+    fn permission_handler(
+        &self,
+    ) -> impl Fn(&PermissionRequest) -> PermissionRequestResult + Send + Sync + 'static {
+        permission_handler(
+            self.tx.clone(),
+            Arc::clone(&self.permission_replies),
+            Arc::clone(&self.permission_nonce),
+            PERMISSION_TIMEOUT,
+        )
+    }
+
+    async fn create_session(&self, open: SessionOpen) {
+        let tx = self.tx.clone();
+        let workspace = self.workspace.clone();
+        let mode = *self.mode.read().await;
+        let query_ui_catalog_tool = Self::query_ui_catalog_tool();
+        let list_canvas_blocks_tool = Self::list_canvas_blocks_tool();
+        let custom_tools = self.registered_tools();
+        let extra_tools = custom_tools
+            .iter()
+            .map(|(tool, _)| tool.clone())
+            .collect::<Vec<_>>();
+        let mut session_config =
+            session_config_for_mode(mode, &workspace, &extra_tools, &self.tool_exclusions);
+        if let Ok(mut context) = self.canvas_context.lock() {
+            context.pending = false;
+            if !context.summary.is_empty() {
+                if let Some(content) = session_config
+                    .system_message
+                    .as_mut()
+                    .and_then(|message| message.content.as_mut())
+                {
+                    content.push_str("\n\n");
+                    content.push_str(&context.summary);
+                }
+            }
+        }
+        let host_tools = session_tool_names(&extra_tools);
+
+        match self.client.create_session(session_config).await {
+            Ok(session) => {
+                let handler = Self::query_ui_catalog_handler(
+                    self.catalog(),
+                    self.canvas_snapshot(),
+                    tx.clone(),
+                );
+                session
+                    .register_tool_with_handler(query_ui_catalog_tool, Some(handler))
+                    .await;
+                session
+                    .register_tool_with_handler(
+                        list_canvas_blocks_tool,
+                        Some(Self::list_canvas_blocks_handler(self.canvas_snapshot())),
+                    )
+                    .await;
+                for (tool, handler) in custom_tools {
                     session
-                        .register_tool_with_handler(query_ui_catalog_tool, Some(handler))
+                        .register_tool_with_handler(tool, Some(handler))
                         .await;
-
-                    let session_id = session.session_id().to_string();
-                    {
-                        let mut slot = session_slot.write().await;
-                        *slot = Some(Arc::clone(&session));
-                    }
-                    let _ = tx.send(AppEvent::SessionCreated(session_id));
-                    Self::spawn_event_listener(runtime_handle, session, tx);
                 }
-                Err(err) => {
-                    let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Error));
-                    let _ = tx.send(AppEvent::SdkError(format!(
-                        "failed to create session: {err}"
-                    )));
+                session
+                    .register_permission_handler(self.permission_handler())
+                    .await;
+
+                let session_id = session.session_id().to_string();
+                {
+                    let mut slot = self.session.write().await;
+                    *slot = Some(Arc::clone(&session));
                 }
+                let _ = tx.send(match open {
+                    SessionOpen::Fresh => AppEvent::SessionCreated(session_id),
+                    SessionOpen::Reconnect => AppEvent::SessionReconnected(session_id),
+                });
+                let listener = Self::spawn_event_listener(
+                    self.runtime_handle.clone(),
+                    session,
+                    tx,
+                    mode,
+                    host_tools,
+                );
+                *self.listener.lock().await = Some(listener);
             }
-        });
+            Err(err) => {
+                let _ = tx.send(AppEvent::StatusChanged(ConnectionState::Error));
+                let _ = tx.send(AppEvent::SdkError(format!(
+                    "failed to create session: {err}"
+                )));
+            }
+        }
     }
 
     pub fn send(&self, prompt: String) {
         let tx = self.tx.clone();
         let session_slot = Arc::clone(&self.session);
+        let context_note = self.canvas_context.lock().ok().and_then(|mut context| {
+            std::mem::take(&mut context.pending).then(|| context.summary.clone())
+        });
+        let prompt = with_context_note(prompt, context_note.as_deref());
 
         self.runtime_handle.spawn(async move {
             let session = {
@@ -317,6 +774,40 @@ Behavior requirements:
         });
     }
 
+    /// Aborts the in-flight assistant turn, if any.
+    pub fn cancel(&self) {
+        let this = self.clone();
+        self.runtime_handle.spawn(async move {
+            this.abort_turn().await;
+        });
+    }
+
+    /// Aborts the running turn and reports the result as `ResponseCancelled` or `CancelFailed`.
+    async fn abort_turn(&self) {
+        let session = {
+            let guard = self.session.read().await;
+            guard.clone()
+        };
+
+        let Some(session) = session else {
+            let _ = self
+                .tx
+                .send(AppEvent::CancelFailed("No active session".to_string()));
+            return;
+        };
+
+        match session.abort().await {
+            Ok(()) => {
+                let _ = self.tx.send(AppEvent::ResponseCancelled);
+            }
+            Err(err) => {
+                let _ = self.tx.send(AppEvent::CancelFailed(err.to_string()));
+            }
+        }
+    }
+
+    /// Watches the client state for the app's lifetime. It is started once; later
+    /// reconnects reuse it, and it triggers `spawn_reconnect` on Error/Disconnected.
     fn spawn_state_poller(&self) {
         if self
             .state_poller_started
@@ -326,18 +817,23 @@ Behavior requirements:
             return;
         }
 
-        let tx = self.tx.clone();
-        let client = Arc::clone(&self.client);
+        let this = self.clone();
         self.runtime_handle.spawn(async move {
             let mut ticker = time::interval(Duration::from_millis(500));
-            let mut last_state = client.state().await;
+            let mut last_state = this.client.state().await;
 
             loop {
                 ticker.tick().await;
-                let current_state = client.state().await;
+                let current_state = this.client.state().await;
                 if current_state != last_state {
                     last_state = current_state;
-                    let _ = tx.send(AppEvent::StatusChanged(current_state));
+                    let _ = this.tx.send(AppEvent::StatusChanged(current_state));
+                    if matches!(
+                        current_state,
+                        ConnectionState::Error | ConnectionState::Disconnected
+                    ) {
+                        this.spawn_reconnect();
+                    }
                 }
             }
         });
@@ -347,15 +843,21 @@ Behavior requirements:
         runtime_handle: Handle,
         session: Arc<Session>,
         tx: mpsc::Sender<AppEvent>,
-    ) {
+        mode: SessionMode,
+        host_tools: Vec<String>,
+    ) -> JoinHandle<()> {
         runtime_handle.spawn(async move {
             let mut events = session.subscribe();
             let mut active_tool_calls: HashMap<String, String> = HashMap::new();
             loop {
                 match events.recv().await {
                     Ok(event) => match event.data {
-                        SessionEventData::AssistantMessageDelta(delta) => {
-                            let _ = tx.send(AppEvent::StreamDelta(delta.delta_content));
+                        data @ (SessionEventData::AssistantMessageDelta(_)
+                        | SessionEventData::AssistantReasoningDelta(_)
+                        | SessionEventData::AssistantReasoning(_)) => {
+                            if let Some(app_event) = assistant_text_event(&data) {
+                                let _ = tx.send(app_event);
+                            }
                         }
                         SessionEventData::AssistantMessage(message) => {
                             let _ = tx.send(AppEvent::StreamDelta(message.content));
@@ -370,14 +872,14 @@ Behavior requirements:
                         SessionEventData::ToolUserRequested(data) => {
                             let tool_name = data.tool_name;
                             active_tool_calls.insert(data.tool_call_id, tool_name.clone());
-                            if tool_name != "query_ui_catalog" {
+                            if mode == SessionMode::Passive && !host_tools.contains(&tool_name) {
                                 let _ = tx.send(AppEvent::ToolCallSuppressed(tool_name));
                             }
                         }
                         SessionEventData::ToolExecutionStart(data) => {
                             let tool_name = data.tool_name;
                             active_tool_calls.insert(data.tool_call_id, tool_name.clone());
-                            if tool_name != "query_ui_catalog" {
+                            if mode == SessionMode::Passive && !host_tools.contains(&tool_name) {
                                 let _ = tx.send(AppEvent::ToolCallSuppressed(tool_name));
                             }
                         }
@@ -407,10 +909,222 @@ Behavior requirements:
                     }
                 }
             }
+        })
+    }
+}
+
+/// `prompt` prefixed with a canvas context note, when there is one to deliver.
+/// The transcript keeps the user's own text; only the model sees the note.
+fn with_context_note(prompt: String, note: Option<&str>) -> String {
+    match note {
+        Some(note) => format!("[Canvas context, not from the user]\n{note}\n\n{prompt}"),
+        None => prompt,
+    }
+}
+
+/// Chat text carried by a session event: message deltas feed the reply bubble, reasoning
+/// goes to the separate Thinking area so it never mixes into the visible answer.
+fn assistant_text_event(data: &SessionEventData) -> Option<AppEvent> {
+    match data {
+        SessionEventData::AssistantMessageDelta(delta) => {
+            Some(AppEvent::StreamDelta(delta.delta_content.clone()))
+        }
+        SessionEventData::AssistantReasoningDelta(delta) => {
+            Some(AppEvent::ReasoningDelta(delta.delta_content.clone()))
+        }
+        SessionEventData::AssistantReasoning(reasoning) => {
+            Some(AppEvent::ReasoningDelta(reasoning.content.clone()))
+        }
+        _ => None,
+    }
+}
+
+async fn take_slot<T>(slot: &RwLock<Option<T>>) -> Option<T> {
+    slot.write().await.take()
+}
+
+/// Sleeps out a reconnect backoff, returning early once `retry_now` is notified.
+async fn wait_for_retry(delay: Duration, retry_now: &Notify) {
+    tokio::select! {
+        _ = time::sleep(delay) => {}
+        _ = retry_now.notified() => {}
+    }
+}
+
+/// Delay before reconnect attempt `attempt` (0-based): doubles from
+/// `RECONNECT_BASE_DELAY`, capped at `RECONNECT_MAX_DELAY`; `None` once attempts run out.
+fn reconnect_delay(attempt: u32) -> Option<Duration> {
+    if attempt >= RECONNECT_MAX_ATTEMPTS {
+        return None;
+    }
+    let delay = RECONNECT_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt));
+    Some(delay.min(RECONNECT_MAX_DELAY))
+}
+
+/// Names of the tools Brownie hosts in a session: the built-ins plus `extra_tools`.
+fn session_tool_names(extra_tools: &[Tool]) -> Vec<String> {
+    HOST_TOOLS
+        .iter()
+        .map(|tool| tool.to_string())
+        .chain(extra_tools.iter().map(|tool| tool.name.clone()))
+        .collect()
+}
+
+/// Session config for `mode`: passive sessions expose only host tools (the canvas tools
+/// plus `extra_tools`) and never prompt, active sessions add the execution tools in
+/// `ACTIVE_MODE_TOOLS` except `exclusions.active` and ask before each call.
+fn session_config_for_mode(
+    mode: SessionMode,
+    workspace: &Path,
+    extra_tools: &[Tool],
+    exclusions: &ToolExclusions,
+) -> SessionConfig {
+    let mut system_message = CopilotClient::brownie_system_message().to_string();
+    let (available_tools, excluded_tools, request_permission) = match mode {
+        SessionMode::Passive => (
+            Some(session_tool_names(extra_tools)),
+            Some(exclusions.passive.clone()),
+            false,
+        ),
+        SessionMode::Active => {
+            let mut available_tools = session_tool_names(extra_tools);
+            available_tools.extend(
+                ACTIVE_MODE_TOOLS
+                    .iter()
+                    .filter(|tool| !exclusions.active.iter().any(|excluded| excluded == *tool))
+                    .map(|tool| tool.to_string()),
+            );
+            system_message.push_str(
+                "\n\nActive Mode is enabled: shell and write tools are available, and the user approves each call before it runs.",
+            );
+            if exclusions.active.is_empty() {
+                (Some(available_tools), None, true)
+            } else {
+                system_message.push_str(&format!(
+                    " These tools remain unavailable: {}.",
+                    exclusions.active.join(", ")
+                ));
+                (Some(available_tools), Some(exclusions.active.clone()), true)
+            }
+        }
+    };
+
+    let mut tools = vec![
+        CopilotClient::query_ui_catalog_tool(),
+        CopilotClient::list_canvas_blocks_tool(),
+    ];
+    tools.extend(extra_tools.iter().cloned());
+
+    SessionConfig {
+        tools,
+        available_tools,
+        excluded_tools,
+        request_permission: Some(request_permission),
+        system_message: Some(SystemMessageConfig {
+            mode: Some(SystemMessageMode::Append),
+            content: Some(system_message),
+        }),
+        working_directory: Some(workspace.to_string_lossy().to_string()),
+        ..Default::default()
+    }
+}
+
+/// Forwards permission requests to the UI and blocks until it answers. Unanswered
+/// requests are denied after `timeout`, and the UI is told so it can drop the dialog.
+fn permission_handler(
+    tx: mpsc::Sender<AppEvent>,
+    replies: PermissionReplies,
+    nonce: Arc<AtomicU64>,
+    timeout: Duration,
+) -> impl Fn(&PermissionRequest) -> PermissionRequestResult + Send + Sync + 'static {
+    move |request| {
+        if is_host_tool_request(request) {
+            return PermissionRequestResult::approved();
+        }
+        let tool_name = permission_tool_name(request);
+        let tool_call_id = request
+            .tool_call_id
+            .clone()
+            .unwrap_or_else(|| format!("permission-{}", nonce.fetch_add(1, Ordering::SeqCst) + 1));
+        let (reply_tx, reply_rx) = mpsc::channel();
+        if let Ok(mut pending) = replies.lock() {
+            pending.insert(tool_call_id.clone(), reply_tx);
+        }
+
+        let sent = tx.send(AppEvent::ToolPermissionRequested {
+            tool_call_id: tool_call_id.clone(),
+            tool_name,
+            details: permission_details(request),
         });
+        let approved = sent.is_ok()
+            && match wait_for_permission_reply(&reply_rx, timeout) {
+                Ok(approved) => approved,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    let _ = tx.send(AppEvent::ToolPermissionTimedOut {
+                        tool_call_id: tool_call_id.clone(),
+                    });
+                    false
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => false,
+            };
+        if let Ok(mut pending) = replies.lock() {
+            pending.remove(&tool_call_id);
+        }
+
+        if approved {
+            PermissionRequestResult::approved()
+        } else {
+            PermissionRequestResult::denied()
+        }
+    }
+}
+
+/// Waits for the UI's answer. On a multi-threaded runtime the wait is moved off the
+/// worker with `block_in_place`, so a pending prompt does not stall other tasks.
+fn wait_for_permission_reply(
+    reply_rx: &mpsc::Receiver<bool>,
+    timeout: Duration,
+) -> Result<bool, mpsc::RecvTimeoutError> {
+    let on_multi_thread_runtime = Handle::try_current()
+        .is_ok_and(|handle| handle.runtime_flavor() == RuntimeFlavor::MultiThread);
+    if on_multi_thread_runtime {
+        tokio::task::block_in_place(|| reply_rx.recv_timeout(timeout))
+    } else {
+        reply_rx.recv_timeout(timeout)
     }
 }
 
+/// Tool named by a permission request; custom tools carry it in the extension data,
+/// built-in ones (shell, write, ...) are identified by the request kind.
+fn permission_tool_name(request: &PermissionRequest) -> String {
+    ["toolName", "tool_name"]
+        .iter()
+        .find_map(|key| request.extension_data.get(*key).and_then(Value::as_str))
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| request.kind.clone())
+}
+
+/// Whether a permission request is for one of Brownie's own tools. The name is only
+/// trusted on custom-tool requests, so a shell or write request cannot borrow it.
+fn is_host_tool_request(request: &PermissionRequest) -> bool {
+    request.kind == CUSTOM_TOOL_PERMISSION_KIND
+        && HOST_TOOLS.contains(&permission_tool_name(request).as_str())
+}
+
+/// One-line summary of a permission request's extension data, sorted by key.
+fn permission_details(request: &PermissionRequest) -> String {
+    let mut entries = request
+        .extension_data
+        .iter()
+        .map(|(key, value)| match value {
+            Value::String(text) => format!("{key}={text}"),
+            other => format!("{key}={other}"),
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries.join(", ")
+}
+
 fn extract_string_argument(args: &Value, keys: &[&str]) -> Option<String> {
     for key in keys {
         if let Some(query) = args.get(key).and_then(Value::as_str) {
@@ -525,11 +1239,118 @@ fn query_from_template_hint(hint: &str) -> Option<&'static str> {
     None
 }
 
+/// Why a focus/minimize/close target cannot be applied, as a tool status
+/// (`not_found` or `ambiguous`) and message, or `None` when it names one open block.
+/// An id is checked exactly; a title alone resolves the way the app resolves it.
+fn block_target_problem(
+    blocks: &[CanvasBlockSummary],
+    target_block_id: Option<&str>,
+    target_title: Option<&str>,
+) -> Option<(&'static str, String)> {
+    if let Some(block_id) = target_block_id {
+        return (!blocks.iter().any(|block| block.block_id == block_id)).then(|| {
+            (
+                "not_found",
+                format!("no open canvas block with id '{block_id}'"),
+            )
+        });
+    }
+    let title = target_title?;
+    let titles = blocks
+        .iter()
+        .map(|block| (block.block_id.as_str(), block.title.as_str()));
+    match resolve_title_target(titles, title) {
+        TitleTarget::Unique(_) => None,
+        TitleTarget::NotFound => Some((
+            "not_found",
+            format!("no open canvas block titled '{title}'"),
+        )),
+        TitleTarget::Ambiguous(block_ids) => Some((
+            "ambiguous",
+            format!(
+                "several open canvas blocks are titled '{title}'; pass target_block_id (candidates: {})",
+                block_ids.join(", ")
+            ),
+        )),
+    }
+}
+
+/// Tool result for a focus/minimize/close call, plus the event to apply when the
+/// target names an open block.
+fn block_operation_result(
+    operation: &str,
+    target_block_id: Option<String>,
+    target_title: Option<String>,
+    blocks: &[CanvasBlockSummary],
+) -> (Value, Option<AppEvent>) {
+    let event =
+        match block_operation_event(operation, target_block_id.clone(), target_title.clone()) {
+            Ok(event) => event,
+            Err(message) => {
+                let result = json!({
+                    "status": "error",
+                    "operation": operation,
+                    "message": message
+                });
+                return (result, None);
+            }
+        };
+    if let Some((status, message)) =
+        block_target_problem(blocks, target_block_id.as_deref(), target_title.as_deref())
+    {
+        let result = json!({
+            "status": status,
+            "operation": operation,
+            "target_block_id": target_block_id,
+            "target_title": target_title,
+            "message": message
+        });
+        return (result, None);
+    }
+    let result = json!({
+        "status": "applied",
+        "operation": operation,
+        "target_block_id": target_block_id,
+        "target_title": target_title
+    });
+    (result, Some(event))
+}
+
+/// Event for a non-render `query_ui_catalog` operation on an existing block.
+fn block_operation_event(
+    operation: &str,
+    target_block_id: Option<String>,
+    target_title: Option<String>,
+) -> Result<AppEvent, String> {
+    if target_block_id.is_none() && target_title.is_none() {
+        return Err(format!(
+            "operation '{operation}' requires target_block_id or target_title"
+        ));
+    }
+    match operation {
+        "focus" => Ok(AppEvent::CanvasToolFocus {
+            target_block_id,
+            target_title,
+        }),
+        "minimize" => Ok(AppEvent::CanvasToolMinimize {
+            target_block_id,
+            target_title,
+        }),
+        "close" => Ok(AppEvent::CanvasToolClose {
+            target_block_id,
+            target_title,
+        }),
+        other => Err(format!(
+            "unknown operation '{other}'; expected render, focus, minimize, or close"
+        )),
+    }
+}
+
 fn summarize_tool_execution(
     success: bool,
     result_content: Option<&str>,
     error_message: Option<&str>,
-) -> (String, Option<String>) {
+) -> (ToolStatus, Option<String>) {
     if !success {
         let fallback = error_message
             .map(|message| message.to_string())
@@ -539,7 +1360,7 @@ fn summarize_tool_execution(
                     .filter(|message| !message.is_empty())
                     .map(ToOwned::to_owned)
             });
-        return ("error".to_string(), fallback);
+        return (ToolStatus::Error, fallback);
     }
 
     if let Some(content) = result_content {
@@ -549,12 +1370,12 @@ fn summarize_tool_execution(
                     .get("message")
                     .and_then(Value::as_str)
                     .map(|message| message.to_string());
-                return (status.to_string(), message);
+                return (ToolStatus::parse(status), message);
             }
         }
     }
 
-    ("success".to_string(), None)
+    (ToolStatus::Success, None)
 }
 
 fn provisional_template_id(intent: &UiIntent) -> String {
@@ -571,8 +1392,463 @@ fn provisional_template_id(intent: &UiIntent) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{extract_tool_query, fallback_canvas_query, summarize_tool_execution};
+    use super::{
+        assistant_text_event, block_operation_result, build_provisional_template,
+        extract_tool_query, fallback_canvas_query, is_host_tool_request, permission_details,
+        permission_handler, permission_tool_name, reconnect_delay, session_config_for_mode,
+        summarize_tool_execution, wait_for_retry, with_context_note, ConnectGuard, CopilotClient,
+        SessionMode, ToolExclusions,
+    };
+    use crate::event::AppEvent;
+    use crate::session::ToolStatus;
+    use crate::settings::Settings;
+    use crate::ui::catalog::{
+        BuiltinCatalogProvider, CatalogManager, CatalogProvider, SharedCatalog,
+    };
+    use crate::ui::intent::intent_from_text;
+    use crate::ui::workspace::{CanvasBlockSummary, SharedCanvasSnapshot};
+    use copilot_sdk::{
+        AssistantMessageDeltaData, AssistantReasoningDeltaData, ConnectionState, PermissionRequest,
+        PermissionRequestResult, SessionEventData, Tool, ToolResultObject,
+    };
     use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use std::sync::{mpsc, Arc, Barrier, Mutex, RwLock};
+    use std::time::Duration;
+
+    fn builtin_catalog() -> SharedCatalog {
+        let providers: Vec<Box<dyn CatalogProvider>> =
+            vec![Box::new(BuiltinCatalogProvider::default())];
+        Arc::new(Mutex::new(CatalogManager::new(providers, false)))
+    }
+
+    fn canvas_with_block(block_id: &str, title: &str) -> SharedCanvasSnapshot {
+        Arc::new(RwLock::new(vec![CanvasBlockSummary {
+            block_id: block_id.to_string(),
+            template_id: "builtin.files.tree.default".to_string(),
+            title: title.to_string(),
+            minimized: false,
+        }]))
+    }
+
+    #[test]
+    fn catalog_tool_block_operations_emit_matching_events() {
+        let (tx, rx) = mpsc::channel();
+        let handler = CopilotClient::query_ui_catalog_handler(
+            builtin_catalog(),
+            canvas_with_block("block-1", "Workspace Files"),
+            tx,
+        );
+
+        for operation in ["focus", "minimize", "close"] {
+            let _ = handler(
+                "query_ui_catalog",
+                &json!({ "operation": operation, "target_block_id": "block-1" }),
+            );
+            let event = rx.try_recv().expect("block operation should emit an event");
+            let (target_block_id, target_title) = match (operation, event) {
+                (
+                    "focus",
+                    AppEvent::CanvasToolFocus {
+                        target_block_id,
+                        target_title,
+                    },
+                )
+                | (
+                    "minimize",
+                    AppEvent::CanvasToolMinimize {
+                        target_block_id,
+                        target_title,
+                    },
+                )
+                | (
+                    "close",
+                    AppEvent::CanvasToolClose {
+                        target_block_id,
+                        target_title,
+                    },
+                ) => (target_block_id, target_title),
+                (operation, event) => panic!("unexpected event for {operation}: {event:?}"),
+            };
+            assert_eq!(target_block_id.as_deref(), Some("block-1"));
+            assert_eq!(target_title, None);
+        }
+
+        let _ = handler(
+            "query_ui_catalog",
+            &json!({ "operation": "close", "target_title": "Workspace Files" }),
+        );
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::CanvasToolClose { target_title: Some(title), .. }) if title == "Workspace Files"
+        ));
+
+        let _ = handler("query_ui_catalog", &json!({ "operation": "close" }));
+        assert!(rx.try_recv().is_err(), "untargeted operation must not emit");
+
+        let _ = handler(
+            "query_ui_catalog",
+            &json!({ "operation": "focus", "target_block_id": "block-9" }),
+        );
+        assert!(rx.try_recv().is_err(), "unknown block must not emit");
+    }
+
+    #[test]
+    fn block_operations_on_unknown_targets_report_not_found() {
+        let blocks = vec![CanvasBlockSummary {
+            block_id: "block-1".to_string(),
+            template_id: "builtin.files.tree.default".to_string(),
+            title: "Workspace Files".to_string(),
+            minimized: false,
+        }];
+
+        let (result, event) =
+            block_operation_result("focus", Some("block-9".to_string()), None, &blocks);
+        assert_eq!(result["status"], "not_found");
+        assert_eq!(result["message"], "no open canvas block with id 'block-9'");
+        assert!(event.is_none());
+
+        let (result, event) =
+            block_operation_result("close", None, Some("Release Plan".to_string()), &blocks);
+        assert_eq!(result["status"], "not_found");
+        assert_eq!(
+            result["message"],
+            "no open canvas block titled 'Release Plan'"
+        );
+        assert!(event.is_none());
+
+        let (result, event) = block_operation_result(
+            "minimize",
+            None,
+            Some("workspace files".to_string()),
+            &blocks,
+        );
+        assert_eq!(result["status"], "applied");
+        assert!(matches!(event, Some(AppEvent::CanvasToolMinimize { .. })));
+    }
+
+    #[test]
+    fn block_operation_on_shared_title_reports_ambiguous() {
+        let blocks = ["block-1", "block-2"].map(|block_id| CanvasBlockSummary {
+            block_id: block_id.to_string(),
+            template_id: "builtin.plan_review.default".to_string(),
+            title: "Plan Review".to_string(),
+            minimized: false,
+        });
+
+        let (result, event) =
+            block_operation_result("focus", None, Some("plan review".to_string()), &blocks);
+        assert_eq!(result["status"], "ambiguous");
+        assert!(result["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("block-1, block-2")));
+        assert!(event.is_none());
+
+        let (result, _) =
+            block_operation_result("focus", Some("block-2".to_string()), None, &blocks);
+        assert_eq!(result["status"], "applied");
+    }
+
+    #[test]
+    fn restart_and_reconnect_racing_connect_only_once() {
+        let guard = ConnectGuard::default();
+        let connects = Arc::new(AtomicUsize::new(0));
+        let start = Arc::new(Barrier::new(2));
+        let contenders = ["restart", "reconnect"].map(|_| {
+            let guard = guard.clone();
+            let connects = Arc::clone(&connects);
+            let start = Arc::clone(&start);
+            std::thread::spawn(move || {
+                start.wait();
+                if let Some(_permit) = guard.try_acquire() {
+                    connects.fetch_add(1, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+            })
+        });
+        for contender in contenders {
+            contender.join().expect("contender should finish");
+        }
+
+        assert_eq!(connects.load(Ordering::SeqCst), 1);
+        assert!(
+            guard.try_acquire().is_some(),
+            "permit must be released once the connect finishes"
+        );
+    }
+
+    #[test]
+    fn context_note_prefixes_prompt_only_when_present() {
+        assert_eq!(with_context_note("hi".to_string(), None), "hi");
+        assert_eq!(
+            with_context_note("hi".to_string(), Some("Open canvas blocks: none")),
+            "[Canvas context, not from the user]\nOpen canvas blocks: none\n\nhi"
+        );
+    }
+
+    #[test]
+    fn reasoning_deltas_route_to_their_own_event() {
+        let reasoning = SessionEventData::AssistantReasoningDelta(AssistantReasoningDeltaData {
+            reasoning_id: "r-1".to_string(),
+            delta_content: "considering files".to_string(),
+        });
+        assert!(matches!(
+            assistant_text_event(&reasoning),
+            Some(AppEvent::ReasoningDelta(text)) if text == "considering files"
+        ));
+
+        let content = SessionEventData::AssistantMessageDelta(AssistantMessageDeltaData {
+            message_id: "m-1".to_string(),
+            delta_content: "Here are the files".to_string(),
+        });
+        assert!(matches!(
+            assistant_text_event(&content),
+            Some(AppEvent::StreamDelta(text)) if text == "Here are the files"
+        ));
+    }
+
+    #[test]
+    fn session_config_follows_configured_exclusions() {
+        let workspace = std::path::Path::new("/tmp/demo");
+        let mut settings = Settings::default();
+        settings.passive_excluded_tools = vec!["shell".to_string(), " ".to_string()];
+        settings.active_excluded_tools = vec![" shell ".to_string(), "custom_tool".to_string()];
+        let exclusions = ToolExclusions::from(&settings);
+
+        let passive = session_config_for_mode(SessionMode::Passive, workspace, &[], &exclusions);
+        assert_eq!(passive.excluded_tools, Some(vec!["shell".to_string()]));
+        assert_eq!(passive.request_permission, Some(false));
+
+        let active = session_config_for_mode(SessionMode::Active, workspace, &[], &exclusions);
+        assert_eq!(
+            active.excluded_tools,
+            Some(vec!["shell".to_string(), "custom_tool".to_string()])
+        );
+        assert_eq!(active.request_permission, Some(true));
+        assert_eq!(
+            active.available_tools,
+            Some(vec![
+                "query_ui_catalog".to_string(),
+                "list_canvas_blocks".to_string(),
+                "powershell".to_string(),
+                "write".to_string()
+            ])
+        );
+        assert!(active
+            .system_message
+            .and_then(|message| message.content)
+            .is_some_and(|content| content.contains("remain unavailable: shell, custom_tool")));
+    }
+
+    #[test]
+    fn session_config_includes_registered_tools() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("test runtime should build");
+        let _guard = runtime.enter();
+        let (tx, _rx) = mpsc::channel();
+        let exclusions = ToolExclusions::from(&Settings::default());
+        let client = CopilotClient::new(std::env::temp_dir(), tx, None, exclusions.clone())
+            .expect("client should build without connecting");
+        client.register_tool(
+            Tool::new("dummy_tool").description("Test-only tool"),
+            Arc::new(|_name, _args| ToolResultObject::text("ok".to_string())),
+        );
+        let extra_tools = client
+            .registered_tools()
+            .into_iter()
+            .map(|(tool, _)| tool)
+            .collect::<Vec<_>>();
+
+        let workspace = std::path::Path::new("/tmp/demo");
+        let passive =
+            session_config_for_mode(SessionMode::Passive, workspace, &extra_tools, &exclusions);
+        assert_eq!(passive.tools.len(), 3);
+        assert!(passive.tools.iter().any(|tool| tool.name == "dummy_tool"));
+        assert!(passive
+            .available_tools
+            .as_ref()
+            .is_some_and(|tools| tools.contains(&"dummy_tool".to_string())));
+
+        let active =
+            session_config_for_mode(SessionMode::Active, workspace, &extra_tools, &exclusions);
+        assert_eq!(
+            active
+                .tools
+                .iter()
+                .map(|tool| tool.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["query_ui_catalog", "list_canvas_blocks", "dummy_tool"]
+        );
+    }
+
+    #[test]
+    fn session_config_excludes_execution_tools_only_in_passive_mode() {
+        let workspace = std::path::Path::new("/tmp/demo");
+        let exclusions = ToolExclusions::from(&Settings::default());
+
+        let passive = session_config_for_mode(SessionMode::Passive, workspace, &[], &exclusions);
+        assert_eq!(passive.request_permission, Some(false));
+        assert_eq!(
+            passive.excluded_tools,
+            Some(vec![
+                "shell".to_string(),
+                "powershell".to_string(),
+                "write".to_string()
+            ])
+        );
+        assert_eq!(
+            passive.available_tools,
+            Some(vec![
+                "query_ui_catalog".to_string(),
+                "list_canvas_blocks".to_string()
+            ])
+        );
+
+        let active = session_config_for_mode(SessionMode::Active, workspace, &[], &exclusions);
+        assert_eq!(active.request_permission, Some(true));
+        assert_eq!(active.excluded_tools, None);
+        assert_eq!(
+            active.available_tools,
+            Some(vec![
+                "query_ui_catalog".to_string(),
+                "list_canvas_blocks".to_string(),
+                "shell".to_string(),
+                "powershell".to_string(),
+                "write".to_string()
+            ])
+        );
+        assert_eq!(active.working_directory.as_deref(), Some("/tmp/demo"));
+        assert_eq!(active.tools.len(), 2);
+    }
+
+    #[test]
+    fn permission_requests_name_custom_tools_from_extension_data() {
+        let custom = PermissionRequest {
+            kind: "custom-tool".to_string(),
+            tool_call_id: Some("call-1".to_string()),
+            extension_data: HashMap::from([("toolName".to_string(), json!("query_ui_catalog"))]),
+        };
+        assert_eq!(permission_tool_name(&custom), "query_ui_catalog");
+
+        let shell = PermissionRequest {
+            kind: "shell".to_string(),
+            tool_call_id: Some("call-2".to_string()),
+            extension_data: HashMap::from([
+                ("fullCommandText".to_string(), json!("cargo test")),
+                ("timeout".to_string(), json!(30)),
+            ]),
+        };
+        assert_eq!(permission_tool_name(&shell), "shell");
+        assert_eq!(
+            permission_details(&shell),
+            "fullCommandText=cargo test, timeout=30"
+        );
+    }
+
+    #[test]
+    fn only_custom_tool_requests_can_claim_a_host_tool_name() {
+        let custom = PermissionRequest {
+            kind: "custom-tool".to_string(),
+            tool_call_id: Some("call-1".to_string()),
+            extension_data: HashMap::from([("toolName".to_string(), json!("list_canvas_blocks"))]),
+        };
+        assert!(is_host_tool_request(&custom));
+
+        let disguised_shell = PermissionRequest {
+            kind: "shell".to_string(),
+            tool_call_id: Some("call-2".to_string()),
+            extension_data: HashMap::from([("toolName".to_string(), json!("query_ui_catalog"))]),
+        };
+        assert!(!is_host_tool_request(&disguised_shell));
+    }
+
+    #[test]
+    fn unanswered_permission_is_denied_and_reported_as_timed_out() {
+        let (tx, rx) = mpsc::channel();
+        let replies = Arc::new(Mutex::new(HashMap::new()));
+        let handler = permission_handler(
+            tx,
+            Arc::clone(&replies),
+            Arc::new(AtomicU64::new(0)),
+            Duration::from_millis(20),
+        );
+        let request = PermissionRequest {
+            kind: "shell".to_string(),
+            tool_call_id: Some("call-1".to_string()),
+            extension_data: HashMap::new(),
+        };
+
+        let result = handler(&request);
+
+        assert_eq!(result.kind, PermissionRequestResult::denied().kind);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::ToolPermissionRequested { tool_call_id, .. }) if tool_call_id == "call-1"
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::ToolPermissionTimedOut { tool_call_id }) if tool_call_id == "call-1"
+        ));
+        assert!(replies.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn restart_during_reconnect_backoff_retries_immediately() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("test runtime should build");
+        let _guard = runtime.enter();
+        let (tx, rx) = mpsc::channel();
+        let client = CopilotClient::new(
+            std::env::temp_dir(),
+            tx,
+            None,
+            ToolExclusions::from(&Settings::default()),
+        )
+        .expect("client should build without connecting");
+        let reconnect_loop = client
+            .reconnecting
+            .try_acquire()
+            .expect("guard starts free");
+
+        client.restart();
+
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::StatusChanged(ConnectionState::Connecting))
+        ));
+        let skipped = runtime.block_on(tokio::time::timeout(
+            Duration::from_secs(5),
+            wait_for_retry(Duration::from_secs(30), &client.retry_now),
+        ));
+        assert!(skipped.is_ok(), "restart must cut the backoff short");
+        assert!(runtime.block_on(client.session.read()).is_none());
+        drop(reconnect_loop);
+    }
+
+    #[test]
+    fn reconnect_delay_doubles_up_to_cap_then_stops() {
+        let schedule = (0..7).map(reconnect_delay).collect::<Vec<_>>();
+        assert_eq!(
+            schedule,
+            vec![
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(8)),
+                Some(Duration::from_secs(16)),
+                Some(Duration::from_secs(30)),
+                None,
+            ]
+        );
+        assert_eq!(reconnect_delay(u32::MAX), None);
+    }
 
     #[test]
     fn summarize_tool_execution_reads_status_from_json_payload() {
@@ -581,14 +1857,14 @@ mod tests {
             Some("{\"status\":\"text_only\",\"message\":\"No UI intent detected\"}"),
             None,
         );
-        assert_eq!(status, "text_only");
+        assert_eq!(status, ToolStatus::TextOnly);
         assert_eq!(message.as_deref(), Some("No UI intent detected"));
     }
 
     #[test]
     fn summarize_tool_execution_reports_error_when_execution_fails() {
         let (status, message) = summarize_tool_execution(false, None, Some("tool call failed"));
-        assert_eq!(status, "error");
+        assert_eq!(status, ToolStatus::Error);
         assert_eq!(message.as_deref(), Some("tool call failed"));
     }
 
@@ -596,7 +1872,7 @@ mod tests {
     fn summarize_tool_execution_uses_result_content_when_error_message_missing() {
         let (status, message) =
             summarize_tool_execution(false, Some("{\"error\":\"bad args\"}"), None);
-        assert_eq!(status, "error");
+        assert_eq!(status, ToolStatus::Error);
         assert_eq!(message.as_deref(), Some("{\"error\":\"bad args\"}"));
     }
 
@@ -665,6 +1941,16 @@ mod tests {
             "Show me the files in the workspace in the canvas"
         );
     }
+
+    #[test]
+    fn provisional_template_heading_is_a_separate_markdown_line() {
+        let intent = intent_from_text("review this diff").expect("intent");
+        let template = build_provisional_template("  review this diff ", &intent);
+        let text = template.schema["components"][0]["text"]
+            .as_str()
+            .expect("markdown text");
+        assert_eq!(text, "### Provisional Canvas\nreview this diff");
+    }
 }
 
 fn sanitize_identifier(raw: &str) -> String {
@@ -690,15 +1976,14 @@ fn build_provisional_template(query: &str, intent: &UiIntent) -> TemplateDocumen
     let mut components = vec![json!({
         "id": "provisional_intro",
         "kind": "markdown",
-        "text": format!("### Provisional Canvas\\n{}", query.trim())
+        "text": format!("### Provisional Canvas\n{}", query.trim())
     })];
 
     if intent.primary == "file_listing" {
         components.push(json!({
             "id": "workspace_tree",
-            "kind": "code",
-            "language": "text",
-            "code": "__WORKSPACE_TREE__"
+            "kind": "tree",
+            "nodes": []
         }));
     }
 
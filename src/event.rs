@@ -1,19 +1,36 @@
 use copilot_sdk::ConnectionState;
 use serde_json::Value;
 
-use crate::ui::catalog::{TemplateDocument, UiIntent};
+use crate::session::ToolStatus;
+use crate::ui::catalog::{RemoteCatalogLoad, TemplateDocument, UiIntent};
 
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     StreamDelta(String),
+    ReasoningDelta(String),
     StreamEnd,
+    ResponseCancelled,
+    /// The abort request for the running turn failed, so the turn goes on.
+    CancelFailed(String),
     StatusChanged(ConnectionState),
     SdkError(String),
     SessionCreated(String),
+    SessionReconnected(String),
+    SessionWriteFailed(String),
+    CatalogRemoteLoaded(RemoteCatalogLoad),
     ToolCallSuppressed(String),
+    IntentAlternatives(String),
+    ToolPermissionRequested {
+        tool_call_id: String,
+        tool_name: String,
+        details: String,
+    },
+    ToolPermissionTimedOut {
+        tool_call_id: String,
+    },
     ToolExecutionOutcome {
         tool_name: String,
-        status: String,
+        status: ToolStatus,
         message: Option<String>,
     },
     CanvasToolRender {
@@ -23,8 +40,21 @@ pub enum AppEvent {
         provider_id: String,
         provider_kind: String,
         target_block_id: Option<String>,
+        target_title: Option<String>,
         root_path: Option<String>,
         schema: Value,
         provisional_template: Option<TemplateDocument>,
     },
+    CanvasToolFocus {
+        target_block_id: Option<String>,
+        target_title: Option<String>,
+    },
+    CanvasToolMinimize {
+        target_block_id: Option<String>,
+        target_title: Option<String>,
+    },
+    CanvasToolClose {
+        target_block_id: Option<String>,
+        target_title: Option<String>,
+    },
 }
@@ -2,22 +2,44 @@ mod app;
 mod copilot;
 mod event;
 mod session;
+mod settings;
+#[cfg(test)]
+mod test_support;
 mod theme;
 mod ui;
+mod window;
 
 use app::BrownieApp;
-use copilot::CopilotClient;
+use copilot::{CopilotClient, ToolExclusions};
 use eframe::egui;
+use event::AppEvent;
+use session::store::SessionWriter;
 use std::collections::BTreeSet;
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 
-fn should_skip_dir(path: &Path) -> bool {
-    matches!(
-        path.file_name().and_then(|name| name.to_str()),
-        Some(".git") | Some("target")
-    )
+/// `.git` is always skipped; `skip_dirs` adds directory names from settings.
+fn should_skip_dir(path: &Path, skip_dirs: &[String]) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    name == ".git" || skip_dirs.iter().any(|skip| skip == name)
+}
+
+/// Walker over `root` that honours the workspace's own `.gitignore` files (even outside
+/// a git checkout, but not those above `root`) and skips directories named in `skip_dirs`.
+fn workspace_walker(root: &Path, skip_dirs: &[String]) -> ignore::WalkBuilder {
+    let skip_dirs = skip_dirs.to_vec();
+    let mut builder = ignore::WalkBuilder::new(root);
+    builder
+        .hidden(false)
+        .parents(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            !(entry.file_type().is_some_and(|kind| kind.is_dir())
+                && should_skip_dir(entry.path(), &skip_dirs))
+        });
+    builder
 }
 
 fn to_workspace_relative(path: &Path, workspace: &Path) -> String {
@@ -27,7 +49,7 @@ fn to_workspace_relative(path: &Path, workspace: &Path) -> String {
         .to_string()
 }
 
-fn detect_instruction_files(workspace: &Path) -> Vec<String> {
+fn detect_instruction_files(workspace: &Path, skip_dirs: &[String]) -> Vec<String> {
     let mut discovered = BTreeSet::new();
     let known_files = [
         workspace.join(".github/copilot-instructions.md"),
@@ -40,38 +62,60 @@ fn detect_instruction_files(workspace: &Path) -> Vec<String> {
         }
     }
 
-    let mut stack = vec![workspace.to_path_buf()];
-    while let Some(dir) = stack.pop() {
-        let entries = match fs::read_dir(&dir) {
-            Ok(entries) => entries,
-            Err(_) => continue,
+    for entry in workspace_walker(workspace, skip_dirs).build().flatten() {
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
         };
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                if should_skip_dir(&path) {
-                    continue;
-                }
-                stack.push(path);
-                continue;
-            }
-
-            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
-                continue;
-            };
-            if file_name.ends_with(".instructions.md") {
-                discovered.insert(to_workspace_relative(&path, workspace));
-            }
+        if file_name.ends_with(".instructions.md") {
+            discovered.insert(to_workspace_relative(path, workspace));
         }
     }
 
     discovered.into_iter().collect()
 }
 
+const USAGE: &str = "usage: brownie [--workspace <path> | <path>]";
+
+/// Workspace from `args` (program name already skipped), or `cwd` when none is given.
+fn workspace_from_args(
+    args: impl IntoIterator<Item = String>,
+    cwd: PathBuf,
+) -> Result<PathBuf, String> {
+    let mut args = args.into_iter();
+    let path = match args.next() {
+        None => return Ok(cwd),
+        Some(flag) if flag == "--workspace" || flag == "-w" => args
+            .next()
+            .ok_or_else(|| format!("{flag} requires a path\n{USAGE}"))?,
+        Some(flag) if flag.starts_with('-') => {
+            return Err(format!("unknown option `{flag}`\n{USAGE}"));
+        }
+        Some(path) => path,
+    };
+    if let Some(extra) = args.next() {
+        return Err(format!("unexpected argument `{extra}`\n{USAGE}"));
+    }
+
+    let path = cwd.join(path);
+    if !path.exists() {
+        return Err(format!("workspace `{}` does not exist", path.display()));
+    }
+    if !path.is_dir() {
+        return Err(format!("workspace `{}` is not a directory", path.display()));
+    }
+    path.canonicalize()
+        .map_err(|err| format!("workspace `{}` is not accessible: {err}", path.display()))
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let workspace = std::env::current_dir()?;
-    let instruction_files = detect_instruction_files(&workspace);
+    let workspace = workspace_from_args(std::env::args().skip(1), std::env::current_dir()?)?;
+    let (settings, settings_warning) = settings::load(&settings::config_path());
+    let geometry = settings.window.clamped(window::MIN_INNER_SIZE);
+    let instruction_files = detect_instruction_files(&workspace, &settings.skip_dirs);
     let (tx, rx) = mpsc::channel();
 
     let runtime = tokio::runtime::Builder::new_multi_thread()
@@ -79,16 +123,39 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .thread_name("brownie-runtime")
         .build()?;
 
-    let copilot = runtime.block_on(async { CopilotClient::new(workspace.clone(), tx.clone()) })?;
+    let copilot = runtime.block_on(async {
+        CopilotClient::new(
+            workspace.clone(),
+            tx.clone(),
+            settings.org_catalog_url(),
+            ToolExclusions::from(&settings),
+        )
+    })?;
     copilot.start();
 
-    let app = BrownieApp::new(rx, copilot, workspace, instruction_files);
+    let write_failures = tx.clone();
+    let session_writer = SessionWriter::new(runtime.handle().clone(), move |err| {
+        let _ = write_failures.send(AppEvent::SessionWriteFailed(err));
+    });
+    let app = BrownieApp::new(
+        rx,
+        copilot,
+        session_writer,
+        workspace,
+        instruction_files,
+        settings,
+        settings_warning,
+    );
     let _runtime = runtime;
 
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(geometry.inner_size)
+        .with_min_inner_size(window::MIN_INNER_SIZE);
+    if let Some(position) = geometry.position {
+        viewport = viewport.with_position(position);
+    }
     let native_options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1280.0, 800.0])
-            .with_min_inner_size([1024.0, 640.0]),
+        viewport,
         ..Default::default()
     };
 
@@ -100,3 +167,116 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_instruction_files, workspace_from_args};
+    use std::fs;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn workspace_argument_overrides_cwd_and_is_validated() {
+        let root =
+            std::env::temp_dir().join(format!("brownie_workspace_args_{}", std::process::id()));
+        let project = root.join("project");
+        fs::create_dir_all(&project).expect("project dir should be created");
+        fs::write(root.join("file.txt"), "x").expect("file fixture should write");
+        let project = project
+            .canonicalize()
+            .expect("project dir should canonicalize");
+
+        assert_eq!(
+            workspace_from_args(args(&[]), root.clone()),
+            Ok(root.clone())
+        );
+        assert_eq!(
+            workspace_from_args(args(&["project"]), root.clone()),
+            Ok(project.clone())
+        );
+        assert_eq!(
+            workspace_from_args(
+                args(&["--workspace", project.to_str().unwrap()]),
+                root.clone()
+            ),
+            Ok(project.clone())
+        );
+
+        assert!(workspace_from_args(args(&["missing"]), root.clone())
+            .unwrap_err()
+            .contains("does not exist"));
+        assert!(workspace_from_args(args(&["file.txt"]), root.clone())
+            .unwrap_err()
+            .contains("not a directory"));
+        assert!(workspace_from_args(args(&["--workspace"]), root.clone()).is_err());
+        assert!(workspace_from_args(args(&["--verbose"]), root.clone()).is_err());
+        assert!(workspace_from_args(args(&["project", "extra"]), root.clone()).is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn instruction_scan_skips_gitignored_directories() {
+        let root =
+            std::env::temp_dir().join(format!("brownie_instruction_scan_{}", std::process::id()));
+        fs::create_dir_all(root.join("docs")).expect("docs dir should be created");
+        fs::create_dir_all(root.join("node_modules/pkg")).expect("ignored dir should be created");
+        fs::write(root.join(".gitignore"), "node_modules/\n").expect("gitignore should write");
+        fs::write(root.join("AGENTS.md"), "").expect("agents file should write");
+        fs::write(root.join("docs/style.instructions.md"), "").expect("instructions should write");
+        fs::write(root.join("node_modules/pkg/vendor.instructions.md"), "")
+            .expect("ignored instructions should write");
+
+        assert_eq!(
+            detect_instruction_files(&root, &[]),
+            vec![
+                "AGENTS.md".to_string(),
+                "docs/style.instructions.md".to_string()
+            ]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn instruction_scan_ignores_gitignore_above_the_workspace() {
+        let parent =
+            std::env::temp_dir().join(format!("brownie_parent_ignore_{}", std::process::id()));
+        let root = parent.join("workspace");
+        fs::create_dir_all(&root).expect("workspace dir should be created");
+        fs::write(parent.join(".gitignore"), "*.md\n").expect("parent gitignore should write");
+        fs::write(root.join("AGENTS.md"), "").expect("agents file should write");
+
+        assert_eq!(
+            detect_instruction_files(&root, &[]),
+            vec!["AGENTS.md".to_string()]
+        );
+
+        let _ = fs::remove_dir_all(parent);
+    }
+
+    #[test]
+    fn custom_skip_dir_excludes_directory_from_discovery() {
+        let root = std::env::temp_dir().join(format!("brownie_skip_dirs_{}", std::process::id()));
+        fs::create_dir_all(root.join(".venv/lib")).expect("venv dir should be created");
+        fs::create_dir_all(root.join("docs")).expect("docs dir should be created");
+        fs::write(root.join(".venv/lib/tool.instructions.md"), "").expect("venv file should write");
+        fs::write(root.join("docs/team.instructions.md"), "").expect("docs file should write");
+
+        assert_eq!(
+            detect_instruction_files(&root, &[]),
+            vec![
+                ".venv/lib/tool.instructions.md".to_string(),
+                "docs/team.instructions.md".to_string()
+            ]
+        );
+        assert_eq!(
+            detect_instruction_files(&root, &[".venv".to_string()]),
+            vec!["docs/team.instructions.md".to_string()]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
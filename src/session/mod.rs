@@ -1,4 +1,7 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Local, SecondsFormat, TimeZone, Utc};
+use serde::de::{IgnoredAny, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
 
 use crate::ui::workspace::CanvasWorkspaceState;
 
@@ -18,9 +21,328 @@ pub struct SessionMeta {
     pub messages: Vec<Message>,
 }
 
+/// The fields of a session file the session list needs. Messages are counted
+/// without being materialized and the canvas workspace is skipped; open a
+/// session with `store::load_one` for the full `SessionMeta`.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct SessionSummary {
+    pub schema_version: u32,
+    pub session_id: String,
+    pub workspace: String,
+    pub title: Option<String>,
+    pub created_at: String,
+    #[serde(default, rename = "messages", deserialize_with = "count_elements")]
+    pub message_count: usize,
+}
+
+fn count_elements<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    struct CountVisitor;
+
+    impl<'de> Visitor<'de> for CountVisitor {
+        type Value = usize;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<usize, A::Error> {
+            let mut count = 0;
+            while seq.next_element::<IgnoredAny>()?.is_some() {
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+
+    deserializer.deserialize_seq(CountVisitor)
+}
+
+impl From<&SessionMeta> for SessionSummary {
+    fn from(session: &SessionMeta) -> Self {
+        Self {
+            schema_version: session.schema_version,
+            session_id: session.session_id.clone(),
+            workspace: session.workspace.clone(),
+            title: session.title.clone(),
+            created_at: session.created_at.clone(),
+            message_count: session.messages.len(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Message {
     pub role: String,
     pub content: String,
     pub timestamp: String,
+    /// Set on `tool` role messages; `content` keeps a readable copy of it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<ToolCallRecord>,
+}
+
+/// A tool call outcome shown as a chip in the transcript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCallRecord {
+    pub tool_name: String,
+    pub status: ToolStatus,
+}
+
+/// Outcome of a tool call, from the `status` field of its result or from how the
+/// host handled the call. Statuses this build does not know are kept as `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ToolStatus {
+    RenderedCatalog,
+    RenderedProvisional,
+    Applied,
+    Success,
+    TextOnly,
+    NoMatch,
+    NotFound,
+    Ambiguous,
+    Suppressed,
+    Denied,
+    Error,
+    Other(String),
+}
+
+impl ToolStatus {
+    pub fn parse(status: &str) -> Self {
+        match status {
+            "rendered_catalog" => Self::RenderedCatalog,
+            "rendered_provisional" => Self::RenderedProvisional,
+            "applied" => Self::Applied,
+            "success" => Self::Success,
+            "text_only" => Self::TextOnly,
+            "no_match" => Self::NoMatch,
+            "not_found" => Self::NotFound,
+            "ambiguous" => Self::Ambiguous,
+            "suppressed" => Self::Suppressed,
+            "denied" => Self::Denied,
+            "error" | "failed" => Self::Error,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::RenderedCatalog => "rendered_catalog",
+            Self::RenderedProvisional => "rendered_provisional",
+            Self::Applied => "applied",
+            Self::Success => "success",
+            Self::TextOnly => "text_only",
+            Self::NoMatch => "no_match",
+            Self::NotFound => "not_found",
+            Self::Ambiguous => "ambiguous",
+            Self::Suppressed => "suppressed",
+            Self::Denied => "denied",
+            Self::Error => "error",
+            Self::Other(status) => status,
+        }
+    }
+}
+
+impl From<String> for ToolStatus {
+    fn from(status: String) -> Self {
+        Self::parse(&status)
+    }
+}
+
+impl From<ToolStatus> for String {
+    fn from(status: ToolStatus) -> Self {
+        status.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for ToolStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+const DISPLAY_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const SESSION_TITLE_MAX_CHARS: usize = 48;
+
+/// Placeholder title given to a session before it has any user message.
+pub fn default_session_title(session_id: &str) -> String {
+    format!("Session {}", session_id.chars().take(8).collect::<String>())
+}
+
+impl SessionMeta {
+    /// Whether the title is missing or still the generated placeholder.
+    pub fn has_default_title(&self) -> bool {
+        match &self.title {
+            Some(title) => *title == default_session_title(&self.session_id),
+            None => true,
+        }
+    }
+}
+
+/// Lowercased title and message text of a session, built once so repeated
+/// searches do not re-lowercase the whole history.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionSearchText {
+    text: String,
+}
+
+impl SessionSearchText {
+    pub fn new(session: &SessionMeta) -> Self {
+        let mut text = session.title.as_deref().unwrap_or_default().to_lowercase();
+        for message in &session.messages {
+            text.push('\n');
+            text.push_str(&message.content.to_lowercase());
+        }
+        Self { text }
+    }
+
+    /// Substring match of an already trimmed and lowercased query against the title
+    /// and every message body.
+    pub fn matches(&self, query: &str) -> bool {
+        self.text.contains(query)
+    }
+}
+
+/// Single-line session title derived from a prompt, truncated at a word boundary.
+pub fn title_from_prompt(prompt: &str) -> Option<String> {
+    let collapsed = prompt.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    if collapsed.chars().count() <= SESSION_TITLE_MAX_CHARS {
+        return Some(collapsed);
+    }
+
+    let cut = collapsed
+        .char_indices()
+        .nth(SESSION_TITLE_MAX_CHARS)
+        .map(|(index, _)| index)
+        .unwrap_or(collapsed.len());
+    let head = &collapsed[..cut];
+    let ends_on_word = collapsed[cut..].starts_with(' ');
+    let head = match head.rfind(' ') {
+        Some(space) if space > 0 && !ends_on_word => &head[..space],
+        _ => head,
+    };
+    Some(format!("{}…", head.trim_end()))
+}
+
+/// Current time as an RFC 3339 UTC string, the format written to session files.
+pub fn now_timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Parses a stored timestamp. Older sessions stored bare unix seconds, so an
+/// integer string is accepted alongside RFC 3339.
+pub fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(seconds) = raw.parse::<i64>() {
+        return DateTime::from_timestamp(seconds, 0);
+    }
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|value| value.with_timezone(&Utc))
+}
+
+/// Renders a stored timestamp in local time; unparseable values are shown as-is.
+pub fn format_timestamp(raw: &str) -> String {
+    format_timestamp_in(raw, &Local)
+}
+
+fn format_timestamp_in<Tz>(raw: &str, zone: &Tz) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    match parse_timestamp(raw) {
+        Some(value) => value
+            .with_timezone(zone)
+            .format(DISPLAY_TIMESTAMP_FORMAT)
+            .to_string(),
+        None => raw.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        default_session_title, format_timestamp_in, now_timestamp, parse_timestamp,
+        title_from_prompt, Message, SessionMeta, SessionSearchText,
+    };
+    use chrono::Utc;
+
+    #[test]
+    fn formats_legacy_unix_seconds_and_rfc3339_timestamps() {
+        assert_eq!(
+            format_timestamp_in("1700000000", &Utc),
+            "2023-11-14 22:13:20"
+        );
+        assert_eq!(
+            format_timestamp_in("2024-05-01T12:30:00+02:00", &Utc),
+            "2024-05-01 10:30:00"
+        );
+        assert_eq!(format_timestamp_in("not a time", &Utc), "not a time");
+    }
+
+    #[test]
+    fn new_timestamps_round_trip_through_parser() {
+        let stored = now_timestamp();
+        assert!(stored.ends_with('Z'));
+        assert!(parse_timestamp(&stored).is_some());
+        assert!(parse_timestamp("1") < parse_timestamp(&stored));
+    }
+
+    #[test]
+    fn title_from_prompt_strips_newlines_and_truncates_at_word_boundary() {
+        assert_eq!(
+            title_from_prompt("  Fix the\nbuild   please \n").as_deref(),
+            Some("Fix the build please")
+        );
+        assert_eq!(
+            title_from_prompt(
+                "Explain how the catalog resolver ranks templates across provider tiers"
+            )
+            .as_deref(),
+            Some("Explain how the catalog resolver ranks templates…")
+        );
+        assert_eq!(
+            title_from_prompt("Summarize the differences between the two configuration approaches")
+                .as_deref(),
+            Some("Summarize the differences between the two…")
+        );
+        assert!(title_from_prompt(" \n\t ").is_none());
+    }
+
+    #[test]
+    fn default_title_is_detected_until_renamed() {
+        let mut meta = SessionMeta {
+            session_id: "0123456789abcdef".to_string(),
+            title: Some(default_session_title("0123456789abcdef")),
+            ..SessionMeta::default()
+        };
+        assert_eq!(meta.title.as_deref(), Some("Session 01234567"));
+        assert!(meta.has_default_title());
+
+        meta.title = Some("Release planning".to_string());
+        assert!(!meta.has_default_title());
+    }
+
+    #[test]
+    fn search_text_matches_title_or_message_content_case_insensitively() {
+        let meta = SessionMeta {
+            session_id: "search-session".to_string(),
+            title: Some("Release Planning".to_string()),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: "Why does the Borrow checker reject this?".to_string(),
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                tool: None,
+            }],
+            ..SessionMeta::default()
+        };
+
+        let search = SessionSearchText::new(&meta);
+        assert!(search.matches("release plan"));
+        assert!(search.matches("borrow checker"));
+        assert!(!search.matches("lifetime"));
+    }
 }
@@ -1,29 +1,86 @@
-use crate::session::{SessionMeta, SCHEMA_VERSION};
+use crate::session::{
+    parse_timestamp, SessionMeta, SessionSearchText, SessionSummary, SCHEMA_VERSION,
+};
 use crate::ui::workspace::CanvasWorkspaceState;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs;
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use tokio::runtime::Handle;
 
-fn home_dir() -> PathBuf {
-    std::env::var_os("HOME")
-        .map(PathBuf::from)
-        .or_else(|| std::env::var_os("USERPROFILE").map(PathBuf::from))
-        .unwrap_or_else(|| PathBuf::from("."))
+fn legacy_sessions_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".brownie").join("sessions"))
 }
 
 fn sessions_dir() -> PathBuf {
-    home_dir().join(".brownie").join("sessions")
+    dirs::data_dir()
+        .or_else(dirs::config_dir)
+        .map(|base| base.join("brownie").join("sessions"))
+        .or_else(legacy_sessions_dir)
+        .unwrap_or_else(|| PathBuf::from(".brownie").join("sessions"))
 }
 
-fn session_path(session_id: &str) -> PathBuf {
-    sessions_dir().join(format!("{session_id}.json"))
+/// Moves session files from the pre-platform-dir location into `target`.
+/// Files already present in `target` are left in place at the legacy path. Returns
+/// how many files moved, plus one warning per file that could not be moved.
+fn migrate_legacy_sessions(legacy: &Path, target: &Path) -> (usize, Vec<String>) {
+    if !legacy.is_dir() || legacy == target {
+        return (0, Vec::new());
+    }
+
+    let entries = match fs::read_dir(legacy) {
+        Ok(entries) => entries,
+        Err(err) => {
+            return (
+                0,
+                vec![format!("failed to read {}: {err}", legacy.display())],
+            )
+        }
+    };
+    let mut moved = 0usize;
+    let mut warnings = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension() != Some(OsStr::new("json")) {
+            continue;
+        }
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        let destination = target.join(file_name);
+        if destination.exists() {
+            continue;
+        }
+        let result = fs::rename(&path, &destination).or_else(|_| {
+            fs::copy(&path, &destination)?;
+            fs::remove_file(&path)
+        });
+        match result {
+            Ok(()) => moved += 1,
+            Err(err) => warnings.push(format!(
+                "failed to migrate {} to {}: {err}",
+                path.display(),
+                destination.display()
+            )),
+        }
+    }
+    (moved, warnings)
 }
 
 fn read_session_file(path: &Path) -> Result<SessionMeta, String> {
     let data = fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))?;
-    let mut session: SessionMeta = serde_json::from_slice(&data)
-        .map_err(|err| format!("failed to parse {}: {err}", path.display()))?;
+    let session: SessionMeta = match serde_json::from_slice(&data) {
+        Ok(session) => session,
+        Err(err) => return Err(quarantine_corrupt_file(path, &err.to_string())),
+    };
+    check_schema_version(path, session)
+}
+
+/// Accepts the current schema and legacy v1, which predates the canvas workspace.
+fn check_schema_version(path: &Path, mut session: SessionMeta) -> Result<SessionMeta, String> {
     if session.schema_version == 1 {
         session.canvas_workspace = CanvasWorkspaceState::default();
         return Ok(session);
@@ -39,26 +96,148 @@ fn read_session_file(path: &Path) -> Result<SessionMeta, String> {
     Ok(session)
 }
 
+/// Reads only the list fields of a session file, quarantining it when even those do
+/// not parse. Legacy v1 files are listed as-is; they are upgraded when opened
+/// through `load_one`.
+fn read_summary_file(path: &Path) -> Result<SessionSummary, String> {
+    let data = fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    let summary: SessionSummary = match serde_json::from_slice(&data) {
+        Ok(summary) => summary,
+        Err(err) => return Err(quarantine_corrupt_file(path, &err.to_string())),
+    };
+    if summary.schema_version != 1 && summary.schema_version != SCHEMA_VERSION {
+        return Err(format!(
+            "unknown schema_version in {}: {}",
+            path.display(),
+            summary.schema_version
+        ));
+    }
+    Ok(summary)
+}
+
+/// First free quarantine name for `path`: `<id>.json.corrupt`, then
+/// `<id>.json.corrupt.1`, `.2`, ... so earlier quarantined copies are kept.
+fn quarantine_path(path: &Path) -> PathBuf {
+    let first = path.with_extension("json.corrupt");
+    if !first.exists() {
+        return first;
+    }
+    (1u32..)
+        .map(|suffix| path.with_extension(format!("json.corrupt.{suffix}")))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(first)
+}
+
+/// Renames an unparseable session to a quarantine name so it stops failing every load.
+fn quarantine_corrupt_file(path: &Path, parse_error: &str) -> String {
+    let quarantined = quarantine_path(path);
+    match fs::rename(path, &quarantined) {
+        Ok(()) => format!(
+            "failed to parse {}: {parse_error}; quarantined as {}",
+            path.display(),
+            quarantined.display()
+        ),
+        Err(err) => format!(
+            "failed to parse {}: {parse_error}; quarantine failed: {err}",
+            path.display()
+        ),
+    }
+}
+
+/// Reads a session and rewrites legacy v1 files in place as the current schema.
+/// Read-only files are upgraded in memory only; a failed rewrite is returned as a warning.
+fn load_session_file(path: &Path) -> Result<(SessionMeta, Option<String>), String> {
+    let mut session = read_session_file(path)?;
+    if session.schema_version == SCHEMA_VERSION {
+        return Ok((session, None));
+    }
+
+    session.schema_version = SCHEMA_VERSION;
+    let read_only = fs::metadata(path)
+        .map(|metadata| metadata.permissions().readonly())
+        .unwrap_or(true);
+    if read_only {
+        return Ok((session, None));
+    }
+    let warning = write_session_file(path, &session)
+        .err()
+        .map(|err| format!("failed to upgrade {}: {err}", path.display()));
+    Ok((session, warning))
+}
+
 pub fn ensure_sessions_dir() -> io::Result<PathBuf> {
     let dir = sessions_dir();
     fs::create_dir_all(&dir)?;
     Ok(dir)
 }
 
-pub fn save(meta: &SessionMeta) -> io::Result<()> {
-    let dir = ensure_sessions_dir()?;
-    let final_path = session_path(&meta.session_id);
-    let tmp_path = dir.join(format!("{}.json.tmp", meta.session_id));
-    let bytes = serde_json::to_vec_pretty(meta)
-        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+/// Moves sessions out of the legacy `~/.brownie/sessions` directory. Run once at
+/// startup, before the first load; returns one warning per file left behind.
+pub fn migrate_legacy() -> Vec<String> {
+    let Some(legacy) = legacy_sessions_dir() else {
+        return Vec::new();
+    };
+    match ensure_sessions_dir() {
+        Ok(dir) => migrate_legacy_sessions(&legacy, &dir).1,
+        Err(err) => vec![format!("failed to create sessions directory: {err}")],
+    }
+}
+
+fn save_in(dir: &Path, meta: &SessionMeta) -> io::Result<()> {
+    write_session_file(&session_path(dir, &meta.session_id)?, meta)
+}
+
+/// Whether `session_id` can name a file directly inside the sessions directory: a
+/// single normal path component that is not hidden.
+fn is_valid_session_id(session_id: &str) -> bool {
+    !session_id.trim().is_empty()
+        && !session_id.starts_with('.')
+        && !session_id.contains(['/', '\\'])
+        && matches!(
+            Path::new(session_id)
+                .components()
+                .collect::<Vec<_>>()
+                .as_slice(),
+            [Component::Normal(_)]
+        )
+}
+
+/// `<dir>/<session_id>.json`, refusing ids that would resolve outside `dir`.
+fn session_path(dir: &Path, session_id: &str) -> io::Result<PathBuf> {
+    if !is_valid_session_id(session_id) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid session id {session_id:?}"),
+        ));
+    }
+    Ok(dir.join(format!("{session_id}.json")))
+}
+
+fn write_session_file(final_path: &Path, meta: &SessionMeta) -> io::Result<()> {
+    write_atomic(final_path, &serialize(meta)?)
+}
+
+/// Serializes a session exactly as `save` would write it.
+pub fn serialize(meta: &SessionMeta) -> io::Result<Vec<u8>> {
+    serde_json::to_vec_pretty(meta)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+}
 
+/// Writes through a sibling `.tmp` file and a rename, so readers never see a partial file.
+pub(crate) fn write_atomic(final_path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp_path = final_path.with_extension("json.tmp");
     fs::write(&tmp_path, bytes)?;
-    match fs::rename(&tmp_path, &final_path) {
+    replace_with(&tmp_path, final_path)
+}
+
+/// Renames a fully written `tmp_path` over `final_path`.
+fn replace_with(tmp_path: &Path, final_path: &Path) -> io::Result<()> {
+    match fs::rename(tmp_path, final_path) {
         Ok(()) => Ok(()),
         Err(rename_err) => {
             if final_path.exists() {
-                fs::remove_file(&final_path)?;
-                fs::rename(&tmp_path, &final_path)?;
+                fs::remove_file(final_path)?;
+                fs::rename(tmp_path, final_path)?;
                 Ok(())
             } else {
                 Err(rename_err)
@@ -67,19 +246,242 @@ pub fn save(meta: &SessionMeta) -> io::Result<()> {
     }
 }
 
-pub fn load_all() -> (Vec<SessionMeta>, Vec<String>) {
-    let mut sessions = Vec::new();
-    let mut warnings = Vec::new();
+type WriteFailureSink = Arc<dyn Fn(String) + Send + Sync>;
+/// Newest save or delete sequence claimed per session id.
+type SequenceLedger = Mutex<HashMap<String, u64>>;
 
-    let dir = match ensure_sessions_dir() {
-        Ok(dir) => dir,
-        Err(err) => {
-            warnings.push(format!("failed to initialize sessions directory: {err}"));
-            return (sessions, warnings);
+/// Persists sessions from the UI without blocking it.
+///
+/// Both paths serialize on the caller's thread. `save_in_background` hands the
+/// bytes to a blocking task on the tokio runtime; `save_now` writes inline for
+/// call sites that read the directory straight afterwards. Every save and delete
+/// takes a sequence number, and a write is dropped once a newer save or a delete
+/// of the same session has been claimed, so a slow background write never
+/// clobbers a later save or brings a deleted session back.
+#[derive(Clone)]
+pub struct SessionWriter {
+    runtime: Handle,
+    on_failure: WriteFailureSink,
+    next_sequence: Arc<AtomicU64>,
+    written: Arc<SequenceLedger>,
+}
+
+impl SessionWriter {
+    pub fn new(runtime: Handle, on_failure: impl Fn(String) + Send + Sync + 'static) -> Self {
+        Self {
+            runtime,
+            on_failure: Arc::new(on_failure),
+            next_sequence: Arc::new(AtomicU64::new(0)),
+            written: Arc::new(Mutex::new(HashMap::new())),
         }
-    };
+    }
 
-    let entries = match fs::read_dir(&dir) {
+    pub fn save_now(&self, meta: &SessionMeta) -> io::Result<()> {
+        let bytes = serialize(meta)?;
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst);
+        let dir = ensure_sessions_dir()?;
+        write_if_newer(&self.written, &dir, &meta.session_id, sequence, &bytes).map(|_| ())
+    }
+
+    pub fn save_in_background(&self, meta: &SessionMeta) {
+        let bytes = match serialize(meta) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                (self.on_failure)(format!("{}: {err}", meta.session_id));
+                return;
+            }
+        };
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst);
+        let session_id = meta.session_id.clone();
+        let written = Arc::clone(&self.written);
+        let on_failure = Arc::clone(&self.on_failure);
+        self.runtime.spawn_blocking(move || {
+            let result = ensure_sessions_dir()
+                .and_then(|dir| write_if_newer(&written, &dir, &session_id, sequence, &bytes));
+            if let Err(err) = result {
+                on_failure(format!("{session_id}: {err}"));
+            }
+        });
+    }
+
+    /// Builds the session search index on the blocking pool, so the UI thread never
+    /// parses every session itself. The result arrives once on the returned receiver.
+    pub fn load_search_index_in_background(
+        &self,
+    ) -> mpsc::Receiver<(SessionSearchIndex, Vec<String>)> {
+        let (tx, rx) = mpsc::channel();
+        self.runtime.spawn_blocking(move || {
+            let _ = tx.send(load_search_index());
+        });
+        rx
+    }
+
+    /// Deletes a session file and tombstones the id, so saves issued before the
+    /// delete are dropped when they land.
+    pub fn delete(&self, session_id: &str) -> io::Result<()> {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst);
+        delete_with_tombstone(&self.written, &sessions_dir(), session_id, sequence)
+    }
+}
+
+fn lock_ledger(ledger: &SequenceLedger) -> std::sync::MutexGuard<'_, HashMap<String, u64>> {
+    ledger
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Writes `bytes` unless a newer save or delete for the session was claimed.
+/// The ledger lock covers the sequence checks and the final rename only; the data
+/// goes to a per-sequence `.tmp` file outside it. Returns whether the file was written.
+fn write_if_newer(
+    written: &SequenceLedger,
+    dir: &Path,
+    session_id: &str,
+    sequence: u64,
+    bytes: &[u8],
+) -> io::Result<bool> {
+    let final_path = session_path(dir, session_id)?;
+    {
+        let mut written = lock_ledger(written);
+        if written
+            .get(session_id)
+            .is_some_and(|latest| *latest > sequence)
+        {
+            return Ok(false);
+        }
+        written.insert(session_id.to_string(), sequence);
+    }
+
+    let tmp_path = final_path.with_extension(format!("json.{sequence}.tmp"));
+    fs::write(&tmp_path, bytes)?;
+    let written = lock_ledger(written);
+    if written.get(session_id) != Some(&sequence) {
+        drop(written);
+        let _ = fs::remove_file(&tmp_path);
+        return Ok(false);
+    }
+    replace_with(&tmp_path, &final_path)?;
+    Ok(true)
+}
+
+/// Removes a session file while holding the ledger, recording `sequence` as the
+/// session's newest claim so any earlier in-flight write is dropped.
+fn delete_with_tombstone(
+    written: &SequenceLedger,
+    dir: &Path,
+    session_id: &str,
+    sequence: u64,
+) -> io::Result<()> {
+    let mut written = lock_ledger(written);
+    let latest = written.entry(session_id.to_string()).or_insert(sequence);
+    *latest = (*latest).max(sequence);
+    delete_in(dir, session_id)
+}
+
+/// Copies an exported session file into the sessions directory, upgrading a v1
+/// file to the current schema. The file passes the same checks as a stored session
+/// but is never quarantined. An id already in use is remapped to `<id>-imported-N`.
+pub fn import(path: &Path) -> Result<SessionMeta, String> {
+    let dir = ensure_sessions_dir()
+        .map_err(|err| format!("failed to initialize sessions directory: {err}"))?;
+    import_in(&dir, path)
+}
+
+fn import_in(dir: &Path, path: &Path) -> Result<SessionMeta, String> {
+    let data = fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    let session: SessionMeta = serde_json::from_slice(&data)
+        .map_err(|err| format!("failed to parse {}: {err}", path.display()))?;
+    let mut session = check_schema_version(path, session)?;
+    if !is_valid_session_id(&session.session_id) {
+        return Err(format!(
+            "{} has an invalid session_id {:?}; it must be a plain file name",
+            path.display(),
+            session.session_id
+        ));
+    }
+    session.schema_version = SCHEMA_VERSION;
+
+    let original_id = session.session_id.clone();
+    let mut suffix = 0;
+    while dir.join(format!("{}.json", session.session_id)).exists() {
+        suffix += 1;
+        session.session_id = format!("{original_id}-imported-{suffix}");
+    }
+    save_in(dir, &session).map_err(|err| {
+        format!(
+            "failed to save imported session {}: {err}",
+            session.session_id
+        )
+    })?;
+    Ok(session)
+}
+
+fn delete_in(dir: &Path, session_id: &str) -> io::Result<()> {
+    match fs::remove_file(session_path(dir, session_id)?) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Search text of every stored session, keyed by session id.
+pub type SessionSearchIndex = HashMap<String, SessionSearchText>;
+
+/// Fully parses every session into search text. Slow on a long history; the UI
+/// calls it through `SessionWriter::load_search_index_in_background`.
+fn load_search_index() -> (SessionSearchIndex, Vec<String>) {
+    let (sessions, warnings) = load_all();
+    let index = sessions
+        .iter()
+        .map(|session| (session.session_id.clone(), SessionSearchText::new(session)))
+        .collect();
+    (index, warnings)
+}
+
+/// Fully parses every session, messages and canvas included. The session list
+/// uses `load_summaries`; this is for searches over message bodies.
+fn load_all() -> (Vec<SessionMeta>, Vec<String>) {
+    match ensure_sessions_dir() {
+        Ok(dir) => load_all_in(&dir),
+        Err(err) => (
+            Vec::new(),
+            vec![format!("failed to initialize sessions directory: {err}")],
+        ),
+    }
+}
+
+fn load_all_in(dir: &Path) -> (Vec<SessionMeta>, Vec<String>) {
+    load_dir_in(dir, load_session_file, |session| &session.created_at)
+}
+
+/// Lists every session newest first without materializing messages or canvases.
+pub fn load_summaries() -> (Vec<SessionSummary>, Vec<String>) {
+    match ensure_sessions_dir() {
+        Ok(dir) => load_summaries_in(&dir),
+        Err(err) => (
+            Vec::new(),
+            vec![format!("failed to initialize sessions directory: {err}")],
+        ),
+    }
+}
+
+fn load_summaries_in(dir: &Path) -> (Vec<SessionSummary>, Vec<String>) {
+    load_dir_in(
+        dir,
+        |path| read_summary_file(path).map(|summary| (summary, None)),
+        |summary| &summary.created_at,
+    )
+}
+
+fn load_dir_in<T>(
+    dir: &Path,
+    load: impl Fn(&Path) -> Result<(T, Option<String>), String>,
+    created_at: impl Fn(&T) -> &str,
+) -> (Vec<T>, Vec<String>) {
+    let mut sessions = Vec::new();
+    let mut warnings = Vec::new();
+
+    let entries = match fs::read_dir(dir) {
         Ok(entries) => entries,
         Err(err) => {
             warnings.push(format!("failed to read sessions directory: {err}"));
@@ -93,13 +495,21 @@ pub fn load_all() -> (Vec<SessionMeta>, Vec<String>) {
             continue;
         }
 
-        match read_session_file(&path) {
-            Ok(session) => sessions.push(session),
+        match load(&path) {
+            Ok((session, warning)) => {
+                sessions.push(session);
+                warnings.extend(warning);
+            }
             Err(err) => warnings.push(err),
         }
     }
 
-    sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    sessions.sort_by(|a, b| {
+        let (a, b) = (created_at(a), created_at(b));
+        parse_timestamp(b)
+            .cmp(&parse_timestamp(a))
+            .then_with(|| b.cmp(a))
+    });
     (sessions, warnings)
 }
 
@@ -113,8 +523,14 @@ pub fn load_one(session_id: &str) -> (Option<SessionMeta>, Option<String>) {
             );
         }
     };
+    load_one_in(&dir, session_id)
+}
 
-    let path = dir.join(format!("{session_id}.json"));
+fn load_one_in(dir: &Path, session_id: &str) -> (Option<SessionMeta>, Option<String>) {
+    let path = match session_path(dir, session_id) {
+        Ok(path) => path,
+        Err(err) => return (None, Some(err.to_string())),
+    };
     if !path.exists() {
         return (
             None,
@@ -125,29 +541,347 @@ pub fn load_one(session_id: &str) -> (Option<SessionMeta>, Option<String>) {
         );
     }
 
-    match read_session_file(&path) {
-        Ok(session) => (Some(session), None),
+    match load_session_file(&path) {
+        Ok((session, warning)) => (Some(session), warning),
         Err(err) => (None, Some(err)),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::read_session_file;
+    use super::{
+        delete_in, delete_with_tombstone, import_in, load_all_in, load_one_in, load_summaries_in,
+        migrate_legacy_sessions, read_session_file, save_in, serialize, write_if_newer,
+    };
+    use crate::session::{Message, SessionMeta, SCHEMA_VERSION};
+    use crate::test_support;
+    use std::collections::HashMap;
     use std::fs;
     use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::sync::Mutex;
 
     fn temp_file(prefix: &str) -> PathBuf {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("time should be monotonic")
-            .as_nanos();
-        std::env::temp_dir().join(format!(
-            "brownie_session_store_{prefix}_{}_{}.json",
-            std::process::id(),
-            nanos
-        ))
+        test_support::temp_dir(&format!("session_store_{prefix}")).with_extension("json")
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let dir = temp_file(prefix).with_extension("d");
+        fs::create_dir_all(&dir).expect("temp sessions dir should create");
+        dir
+    }
+
+    #[test]
+    fn renamed_session_round_trips_through_save_and_load() {
+        let dir = temp_dir("rename");
+        let mut session = SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "rename-session".to_string(),
+            workspace: "/tmp/demo".to_string(),
+            title: Some("Session rename-s".to_string()),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            canvas_workspace: Default::default(),
+            messages: Vec::new(),
+        };
+        save_in(&dir, &session).expect("initial save should succeed");
+
+        session.title = Some("Release checklist".to_string());
+        save_in(&dir, &session).expect("renamed save should succeed");
+
+        let (loaded, warning) = load_one_in(&dir, "rename-session");
+        assert!(warning.is_none());
+        let loaded = loaded.expect("renamed session should load");
+        assert_eq!(loaded.title.as_deref(), Some("Release checklist"));
+        assert!(!dir.join("rename-session.json.tmp").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn serialized_background_write_matches_save_on_disk() {
+        let save_dir = temp_dir("save_direct");
+        let split_dir = temp_dir("save_split");
+        let session = SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "split-session".to_string(),
+            workspace: "/tmp/demo".to_string(),
+            title: Some("Split write".to_string()),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            canvas_workspace: Default::default(),
+            messages: Vec::new(),
+        };
+        save_in(&save_dir, &session).expect("direct save should succeed");
+
+        let bytes = serialize(&session).expect("session should serialize");
+        let written = Mutex::new(HashMap::new());
+        let wrote = std::thread::scope(|scope| {
+            scope
+                .spawn(|| write_if_newer(&written, &split_dir, "split-session", 0, &bytes))
+                .join()
+                .expect("writer thread should finish")
+        })
+        .expect("split write should succeed");
+        assert!(wrote);
+
+        let direct = fs::read(save_dir.join("split-session.json")).expect("direct file");
+        let split = fs::read(split_dir.join("split-session.json")).expect("split file");
+        assert_eq!(direct, split);
+        assert!(!split_dir.join("split-session.json.0.tmp").exists());
+
+        let _ = fs::remove_dir_all(save_dir);
+        let _ = fs::remove_dir_all(split_dir);
+    }
+
+    #[test]
+    fn stale_background_write_does_not_overwrite_newer_save() {
+        let dir = temp_dir("stale_write");
+        let written = Mutex::new(HashMap::new());
+        assert!(write_if_newer(&written, &dir, "stale", 2, b"newer").expect("newer write"));
+        assert!(!write_if_newer(&written, &dir, "stale", 1, b"older").expect("stale write"));
+
+        let on_disk = fs::read(dir.join("stale.json")).expect("session file");
+        assert_eq!(on_disk, b"newer");
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn write_issued_before_delete_does_not_resurrect_the_session() {
+        let dir = temp_dir("tombstone");
+        let written = Mutex::new(HashMap::new());
+        assert!(write_if_newer(&written, &dir, "gone", 1, b"saved").expect("first write"));
+
+        delete_with_tombstone(&written, &dir, "gone", 3).expect("delete should succeed");
+        assert!(!write_if_newer(&written, &dir, "gone", 2, b"late").expect("late write"));
+        assert!(!dir.join("gone.json").exists());
+        assert!(!dir.join("gone.json.2.tmp").exists());
+
+        assert!(write_if_newer(&written, &dir, "gone", 4, b"recreated").expect("new write"));
+        assert_eq!(
+            fs::read(dir.join("gone.json")).expect("session file"),
+            b"recreated"
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn summary_loader_lists_titles_without_materializing_messages() {
+        let dir = temp_dir("summaries");
+        // Message entries that would not deserialize as `Message` prove the summary
+        // loader only counts them.
+        fs::write(
+            dir.join("older.json"),
+            r#"{"schema_version":2,"session_id":"older","workspace":"/tmp/demo","title":"Older","created_at":"2026-01-01T00:00:00Z","canvas_workspace":{"blocks":[{"not":"a block"}]},"messages":[1,{"bogus":true},"x"]}"#,
+        )
+        .expect("older session should write");
+        fs::write(
+            dir.join("newer.json"),
+            r#"{"schema_version":1,"session_id":"newer","workspace":"/tmp/demo","title":null,"created_at":"2026-02-01T00:00:00Z","messages":[]}"#,
+        )
+        .expect("newer session should write");
+        fs::write(
+            dir.join("future.json"),
+            r#"{"schema_version":99,"session_id":"future","workspace":"/tmp/demo","title":null,"created_at":"2026-03-01T00:00:00Z","messages":[]}"#,
+        )
+        .expect("future session should write");
+
+        let (summaries, warnings) = load_summaries_in(&dir);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("unknown schema_version"));
+        assert!(dir.join("future.json").exists());
+        let listed: Vec<_> = summaries
+            .iter()
+            .map(|summary| (summary.session_id.as_str(), summary.title.as_deref()))
+            .collect();
+        assert_eq!(listed, vec![("newer", None), ("older", Some("Older"))]);
+        assert_eq!(summaries[1].message_count, 3);
+        assert!(dir.join("older.json").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn imported_session_appears_in_load_all_with_colliding_id_remapped() {
+        let dir = temp_dir("import_sessions");
+        let source = temp_file("import_source");
+        let session = SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "shared-id".to_string(),
+            workspace: "/tmp/demo".to_string(),
+            title: Some("Exported".to_string()),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            canvas_workspace: Default::default(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: "hello".to_string(),
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                tool: None,
+            }],
+        };
+        fs::write(
+            &source,
+            serde_json::to_vec_pretty(&session).expect("session should serialize"),
+        )
+        .expect("export file should write");
+
+        let first = import_in(&dir, &source).expect("first import should succeed");
+        assert_eq!(first.session_id, "shared-id");
+        let second = import_in(&dir, &source).expect("second import should succeed");
+        assert_eq!(second.session_id, "shared-id-imported-1");
+
+        let (loaded, warnings) = load_all_in(&dir);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let mut ids: Vec<_> = loaded
+            .iter()
+            .map(|session| session.session_id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["shared-id", "shared-id-imported-1"]);
+        assert!(loaded
+            .iter()
+            .all(|session| session.messages.len() == 1
+                && session.title.as_deref() == Some("Exported")));
+        assert!(source.exists());
+
+        let _ = fs::remove_file(source);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn session_ids_that_escape_the_sessions_dir_are_rejected() {
+        let root = temp_dir("traversal");
+        let dir = root.join("sessions");
+        fs::create_dir_all(&dir).expect("sessions dir should create");
+        let victim = root.join("victim.json");
+        fs::write(&victim, "keep me").expect("victim file should write");
+
+        for session_id in ["../victim", "../../.bashrc", "a/b", "a\\b", ".hidden", ".."] {
+            let source = temp_file("traversal_source");
+            fs::write(
+                &source,
+                format!(
+                    r#"{{"schema_version":2,"session_id":{},"workspace":"/tmp/demo","title":null,"created_at":"2026-01-01T00:00:00Z","messages":[]}}"#,
+                    serde_json::to_string(session_id).expect("id should encode")
+                ),
+            )
+            .expect("export file should write");
+            let err = import_in(&dir, &source).expect_err("traversal id should be rejected");
+            assert!(err.contains("invalid session_id"), "{err}");
+            let _ = fs::remove_file(source);
+
+            assert!(delete_in(&dir, session_id).is_err());
+            let (loaded, warning) = load_one_in(&dir, session_id);
+            assert!(loaded.is_none());
+            assert!(warning.is_some_and(|warning| warning.contains("invalid session id")));
+        }
+        assert_eq!(
+            fs::read_to_string(&victim).expect("victim should remain"),
+            "keep me"
+        );
+        assert_eq!(fs::read_dir(&dir).expect("sessions dir").count(), 0);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn import_rejects_unknown_schema_without_quarantining_the_file() {
+        let dir = temp_dir("import_reject");
+        let source = temp_file("import_reject_source");
+        fs::write(
+            &source,
+            r#"{"schema_version":99,"session_id":"future","workspace":"/tmp/demo","title":null,"created_at":"2026-01-01T00:00:00Z","messages":[]}"#,
+        )
+        .expect("export file should write");
+
+        let err = import_in(&dir, &source).expect_err("unknown schema should be rejected");
+        assert!(err.contains("unknown schema_version"), "{err}");
+        assert!(source.exists());
+        assert!(!dir.join("future.json").exists());
+
+        let _ = fs::remove_file(source);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn deleted_session_no_longer_loads() {
+        let dir = temp_dir("delete");
+        let session = SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "delete-session".to_string(),
+            workspace: "/tmp/demo".to_string(),
+            title: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            canvas_workspace: Default::default(),
+            messages: Vec::new(),
+        };
+        save_in(&dir, &session).expect("save should succeed");
+
+        delete_in(&dir, "delete-session").expect("delete should succeed");
+        delete_in(&dir, "delete-session").expect("deleting a missing session is a no-op");
+
+        let (loaded, warning) = load_one_in(&dir, "delete-session");
+        assert!(loaded.is_none());
+        assert!(warning
+            .expect("missing session should warn")
+            .contains("session file missing for id delete-session"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn migration_moves_legacy_session_files_to_new_location() {
+        let legacy = temp_dir("migrate_legacy");
+        let target = temp_dir("migrate_target");
+        let session = SessionMeta {
+            schema_version: SCHEMA_VERSION,
+            session_id: "migrated-session".to_string(),
+            workspace: "/tmp/demo".to_string(),
+            title: Some("Migrated".to_string()),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            canvas_workspace: Default::default(),
+            messages: Vec::new(),
+        };
+        save_in(&legacy, &session).expect("legacy save should succeed");
+
+        let (moved, warnings) = migrate_legacy_sessions(&legacy, &target);
+        assert_eq!(moved, 1);
+        assert!(warnings.is_empty());
+        assert!(!legacy.join("migrated-session.json").exists());
+
+        let (loaded, warning) = load_one_in(&target, "migrated-session");
+        assert!(warning.is_none());
+        assert_eq!(
+            loaded
+                .expect("migrated session should load")
+                .title
+                .as_deref(),
+            Some("Migrated")
+        );
+        assert_eq!(migrate_legacy_sessions(&legacy, &target), (0, Vec::new()));
+
+        let _ = fs::remove_dir_all(legacy);
+        let _ = fs::remove_dir_all(target);
+    }
+
+    #[test]
+    fn migration_reports_files_it_cannot_move_as_warnings() {
+        let legacy = temp_dir("migrate_partial");
+        fs::create_dir_all(&legacy).expect("create legacy dir");
+        fs::write(legacy.join("first.json"), "{}").expect("write first");
+        fs::write(legacy.join("second.json"), "{}").expect("write second");
+        let missing_target = legacy.join("missing").join("sessions");
+
+        let (moved, warnings) = migrate_legacy_sessions(&legacy, &missing_target);
+
+        assert_eq!(moved, 0);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings
+            .iter()
+            .all(|warning| warning.starts_with("failed to migrate")));
+        assert!(legacy.join("first.json").exists());
+        assert!(legacy.join("second.json").exists());
+
+        let _ = fs::remove_dir_all(legacy);
     }
 
     #[test]
@@ -171,6 +905,64 @@ mod tests {
         let _ = fs::remove_file(path);
     }
 
+    #[test]
+    fn loading_legacy_schema_rewrites_file_as_current_schema() {
+        let dir = temp_dir("upgrade");
+        let data = r#"{
+  "schema_version": 1,
+  "session_id": "legacy-upgrade",
+  "workspace": "/tmp/demo",
+  "title": "Legacy",
+  "created_at": "1",
+  "messages": []
+}"#;
+        let path = dir.join("legacy-upgrade.json");
+        fs::write(&path, data).expect("legacy session fixture should write");
+
+        let (loaded, warning) = load_one_in(&dir, "legacy-upgrade");
+        assert!(warning.is_none());
+        assert_eq!(
+            loaded.expect("legacy session should load").schema_version,
+            SCHEMA_VERSION
+        );
+
+        let reloaded = read_session_file(&path).expect("upgraded file should load");
+        assert_eq!(reloaded.schema_version, SCHEMA_VERSION);
+        assert_eq!(reloaded.title.as_deref(), Some("Legacy"));
+        assert!(!dir.join("legacy-upgrade.json.tmp").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn corrupt_session_file_is_quarantined_and_stops_warning() {
+        let dir = temp_dir("corrupt");
+        let path = dir.join("broken-session.json");
+        fs::write(&path, "{ not json").expect("corrupt fixture should write");
+
+        let (sessions, warnings) = load_all_in(&dir);
+        assert!(sessions.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("broken-session.json.corrupt"));
+        assert!(!path.exists());
+        assert!(dir.join("broken-session.json.corrupt").exists());
+
+        let (sessions, warnings) = load_all_in(&dir);
+        assert!(sessions.is_empty());
+        assert!(warnings.is_empty());
+
+        fs::write(&path, "{ still not json").expect("second corrupt fixture should write");
+        let (_, warnings) = load_summaries_in(&dir);
+        assert!(warnings[0].contains("broken-session.json.corrupt.1"));
+        assert_eq!(
+            fs::read_to_string(dir.join("broken-session.json.corrupt")).expect("first copy"),
+            "{ not json"
+        );
+        assert!(dir.join("broken-session.json.corrupt.1").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
     #[test]
     fn read_session_file_loads_workspace_aware_schema() {
         let path = temp_file("workspace_aware");
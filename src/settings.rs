@@ -0,0 +1,213 @@
+use crate::session::store::write_atomic;
+use crate::ui::catalog::org_catalog_url_from_env;
+use crate::ui::event::DEFAULT_EVENT_LOG_MAX_ENTRIES;
+use crate::window::WindowGeometry;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// User preferences persisted to `~/.brownie/config.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub font_scale: f32,
+    pub org_enabled: bool,
+    pub org_catalog_url: Option<String>,
+    pub last_session_id: Option<String>,
+    /// Directory names left out of the file explorer and instruction scan.
+    pub skip_dirs: Vec<String>,
+    /// How many directory levels the file explorer expands.
+    pub explorer_max_depth: usize,
+    /// SDK tools excluded from Passive Mode sessions.
+    pub passive_excluded_tools: Vec<String>,
+    /// SDK tools that stay excluded after switching to Active Mode.
+    pub active_excluded_tools: Vec<String>,
+    /// Estimated prompt tokens at which the composer counter turns amber.
+    pub composer_warning_tokens: usize,
+    /// Estimated prompt tokens at which the composer counter turns red.
+    pub composer_danger_tokens: usize,
+    /// Lines after which a chat message collapses behind "Show more"; 0 disables.
+    pub collapse_message_lines: usize,
+    /// Characters after which a chat message collapses behind "Show more"; 0 disables.
+    pub collapse_message_chars: usize,
+    /// Canvas events kept in memory before the oldest are dropped.
+    pub canvas_event_log_max_entries: usize,
+    /// Window size and position from the last exit.
+    pub window: WindowGeometry,
+    /// Keys written by newer builds, carried through untouched on save.
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            font_scale: 1.0,
+            org_enabled: true,
+            org_catalog_url: None,
+            last_session_id: None,
+            skip_dirs: vec![".git".to_string(), "target".to_string()],
+            explorer_max_depth: 4,
+            passive_excluded_tools: vec![
+                "shell".to_string(),
+                "powershell".to_string(),
+                "write".to_string(),
+            ],
+            active_excluded_tools: Vec::new(),
+            composer_warning_tokens: 4_000,
+            composer_danger_tokens: 16_000,
+            collapse_message_lines: 24,
+            collapse_message_chars: 2_000,
+            canvas_event_log_max_entries: DEFAULT_EVENT_LOG_MAX_ENTRIES,
+            window: WindowGeometry::default(),
+            extra: Map::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// Org catalog base URL to use, or `None` when the org tier is disabled.
+    /// `BROWNIE_ORG_CATALOG_URL` takes precedence over the configured value.
+    pub fn org_catalog_url(&self) -> Option<String> {
+        if !self.org_enabled {
+            return None;
+        }
+        org_catalog_url_from_env().or_else(|| {
+            self.org_catalog_url
+                .as_deref()
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(ToOwned::to_owned)
+        })
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".brownie")
+        .join("config.json")
+}
+
+/// Settings stored at `path`. A missing file yields the defaults silently; an
+/// unreadable or invalid one yields the defaults plus a warning, and an invalid file
+/// is moved aside to `config.json.corrupt` so the next save cannot destroy it.
+pub fn load(path: &Path) -> (Settings, Option<String>) {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return (Settings::default(), None),
+        Err(err) => {
+            let warning = format!("failed to read {}: {err}; using defaults", path.display());
+            return (Settings::default(), Some(warning));
+        }
+    };
+    match serde_json::from_slice::<Settings>(&data) {
+        Ok(settings) => (settings, None),
+        Err(err) => {
+            let backup = path.with_extension("json.corrupt");
+            let warning = match fs::rename(path, &backup) {
+                Ok(()) => format!(
+                    "failed to parse {}: {err}; using defaults, bad file kept as {}",
+                    path.display(),
+                    backup.display()
+                ),
+                Err(rename_err) => format!(
+                    "failed to parse {}: {err}; using defaults, backup failed: {rename_err}",
+                    path.display()
+                ),
+            };
+            (Settings::default(), Some(warning))
+        }
+    }
+}
+
+/// Writes `settings` atomically, so a crash mid-save never leaves a truncated file.
+pub fn save(path: &Path, settings: &Settings) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let bytes = serde_json::to_vec_pretty(settings)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+    write_atomic(path, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load, save, Settings};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_config(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "brownie_settings_{name}_{}.json",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn missing_file_loads_defaults() {
+        let path = temp_config("missing");
+        let _ = fs::remove_file(&path);
+
+        let (settings, warning) = load(&path);
+        assert!(warning.is_none());
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.font_scale, 1.0);
+        assert!(settings.org_enabled);
+        assert_eq!(settings.last_session_id, None);
+        assert_eq!(settings.skip_dirs, vec![".git", "target"]);
+    }
+
+    #[test]
+    fn settings_round_trip_and_keep_unknown_keys() {
+        let path = temp_config("round_trip");
+        fs::write(
+            &path,
+            r#"{"font_scale":1.2,"future_option":{"nested":true}}"#,
+        )
+        .expect("config fixture should write");
+
+        let (mut settings, _) = load(&path);
+        assert!((settings.font_scale - 1.2).abs() < 1e-6);
+        assert!(settings.org_enabled);
+
+        settings.org_enabled = false;
+        settings.canvas_event_log_max_entries = 250;
+        settings.last_session_id = Some("session-a".to_string());
+        settings.window.position = Some([40.0, 60.0]);
+        save(&path, &settings).expect("settings should save");
+
+        let (reloaded, _) = load(&path);
+        assert_eq!(reloaded, settings);
+        assert_eq!(reloaded.org_catalog_url(), None);
+        let raw = fs::read_to_string(&path).expect("config should read");
+        assert!(raw.contains("future_option"));
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_to_defaults_and_is_kept_aside() {
+        let path = temp_config("corrupt");
+        let backup = path.with_extension("json.corrupt");
+        let _ = fs::remove_file(&backup);
+        fs::write(&path, "{ not json").expect("corrupt fixture should write");
+
+        let (settings, warning) = load(&path);
+        assert_eq!(settings, Settings::default());
+        let warning = warning.expect("corrupt config should warn");
+        assert!(warning.contains("failed to parse"), "warning: {warning}");
+
+        save(&path, &settings).expect("settings should save");
+        assert_eq!(
+            fs::read_to_string(&backup).expect("bad file should be kept"),
+            "{ not json"
+        );
+        assert!(!path.with_extension("json.tmp").exists());
+
+        let _ = fs::remove_file(path);
+        let _ = fs::remove_file(backup);
+    }
+}
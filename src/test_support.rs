@@ -0,0 +1,11 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Unique path under the system temp dir for one test; nothing is created there.
+pub fn temp_dir(prefix: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time should be monotonic")
+        .as_nanos();
+    std::env::temp_dir().join(format!("brownie_{prefix}_{}_{}", std::process::id(), nanos))
+}
@@ -29,6 +29,7 @@ pub struct Theme {
     pub radius_10: u8,
     pub radius_12: u8,
     pub button_height: f32,
+    pub font_scale: f32,
 }
 
 impl Default for Theme {
@@ -61,6 +62,7 @@ impl Default for Theme {
             radius_10: 10,
             radius_12: Self::R12,
             button_height: 35.0,
+            font_scale: 1.0,
         }
     }
 }
@@ -72,6 +74,26 @@ impl Theme {
     pub const P12: f32 = 12.0;
     pub const P16: f32 = 16.0;
     pub const P24: f32 = 24.0;
+    pub const FONT_SCALE_MIN: f32 = 0.8;
+    pub const FONT_SCALE_MAX: f32 = 2.0;
+    pub const FONT_SCALE_STEP: f32 = 0.1;
+
+    pub fn with_font_scale(mut self, font_scale: f32) -> Self {
+        self.set_font_scale(font_scale);
+        self
+    }
+
+    /// Clamps to `FONT_SCALE_MIN..=FONT_SCALE_MAX`, rounded to one decimal so
+    /// repeated +/- steps do not drift.
+    pub fn set_font_scale(&mut self, font_scale: f32) {
+        let clamped = font_scale.clamp(Self::FONT_SCALE_MIN, Self::FONT_SCALE_MAX);
+        self.font_scale = (clamped * 10.0).round() / 10.0;
+    }
+
+    /// Scaled size for explicit `RichText::size` calls.
+    pub fn text_size(&self, base: f32) -> f32 {
+        base * self.font_scale
+    }
 
     pub fn apply_visuals(&self, ctx: &egui::Context) {
         let mut visuals = egui::Visuals::dark();
@@ -103,27 +125,35 @@ impl Theme {
             spread: 0,
             color: Color32::from_rgba_premultiplied(0, 0, 0, 64),
         };
-        let mut style = (*ctx.style()).clone();
+        let mut style = self.style(&ctx.style());
         style.visuals = visuals;
+        ctx.set_style(style);
+    }
+
+    /// `base` with Brownie spacing and text styles, sizes multiplied by `font_scale`.
+    pub fn style(&self, base: &egui::Style) -> egui::Style {
+        let mut style = base.clone();
         style.spacing.item_spacing = egui::vec2(10.0, 10.0);
         style.spacing.button_padding = egui::vec2(12.0, 8.0);
-        style
-            .text_styles
-            .insert(TextStyle::Heading, FontId::proportional(17.0));
+        style.text_styles.insert(
+            TextStyle::Heading,
+            FontId::proportional(self.text_size(17.0)),
+        );
         style.text_styles.insert(
             TextStyle::Name("section".into()),
-            FontId::proportional(14.0),
+            FontId::proportional(self.text_size(14.0)),
         );
         style
             .text_styles
-            .insert(TextStyle::Body, FontId::proportional(14.0));
+            .insert(TextStyle::Body, FontId::proportional(self.text_size(14.0)));
+        style.text_styles.insert(
+            TextStyle::Monospace,
+            FontId::monospace(self.text_size(13.0)),
+        );
         style
             .text_styles
-            .insert(TextStyle::Monospace, FontId::monospace(13.0));
+            .insert(TextStyle::Small, FontId::proportional(self.text_size(12.0)));
         style
-            .text_styles
-            .insert(TextStyle::Small, FontId::proportional(12.0));
-        ctx.set_style(style);
     }
 
     pub fn panel_frame(&self, fill: Color32, inner_padding: i8) -> Frame {
@@ -160,3 +190,30 @@ impl Theme {
         Stroke::new(1.0, self.border_subtle)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Theme;
+    use eframe::egui::{self, TextStyle};
+
+    #[test]
+    fn font_scale_grows_generated_text_styles_proportionally() {
+        let base = egui::Style::default();
+        let normal = Theme::default().style(&base);
+        let large = Theme::default().with_font_scale(1.5).style(&base);
+
+        for text_style in [TextStyle::Body, TextStyle::Monospace, TextStyle::Heading] {
+            let normal_size = normal.text_styles[&text_style].size;
+            let large_size = large.text_styles[&text_style].size;
+            assert!((large_size - normal_size * 1.5).abs() < f32::EPSILON);
+        }
+        assert_eq!(
+            Theme::default().with_font_scale(9.0).font_scale,
+            Theme::FONT_SCALE_MAX
+        );
+        assert_eq!(
+            Theme::default().with_font_scale(0.1).font_scale,
+            Theme::FONT_SCALE_MIN
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::ui::registry::ComponentRegistry;
-use crate::ui::schema::{validate_schema, UiSchema};
+use crate::ui::schema::{validate_schema, UiSchema, ValidatedSchema};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::cmp::Ordering;
@@ -7,10 +7,20 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
 const BUILTIN_CODE_REVIEW_TEMPLATE: &str = include_str!("catalog_builtin/code_review.json");
 const BUILTIN_PLAN_REVIEW_TEMPLATE: &str = include_str!("catalog_builtin/plan_review.json");
 const BUILTIN_FILE_LISTING_TEMPLATE: &str = include_str!("catalog_builtin/file_listing.json");
+const BUILTIN_UI_DESIGN_REVIEW_TEMPLATE: &str =
+    include_str!("catalog_builtin/ui_design_review.json");
+const BUILTIN_COMPARE_TEMPLATE: &str = include_str!("catalog_builtin/compare.json");
+const BUILTIN_DASHBOARD_TEMPLATE: &str = include_str!("catalog_builtin/dashboard.json");
+const ORG_CATALOG_URL_ENV: &str = "BROWNIE_ORG_CATALOG_URL";
+const ORG_CATALOG_MANIFEST: &str = "manifest.json";
+const ORG_CATALOG_CACHE_DIR: &str = "org_catalog_cache";
+const ORG_CATALOG_HTTP_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UiIntent {
@@ -123,7 +133,7 @@ impl CatalogTemplate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CatalogLoadOutput {
     pub templates: Vec<CatalogTemplate>,
     pub diagnostics: Vec<CatalogLoadDiagnostic>,
@@ -145,6 +155,17 @@ impl CatalogLoadDiagnostic {
     }
 }
 
+/// How one provider's last load went, for the catalog health panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderHealth {
+    pub provider_id: String,
+    pub kind: CatalogSourceKind,
+    pub loaded: usize,
+    pub rejected: usize,
+    /// `template_ref: reason` for each rejection, in load order.
+    pub rejections: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum CatalogError {
@@ -186,6 +207,24 @@ pub trait CatalogProvider: Send + Sync {
 
     fn load_templates(&self) -> Result<CatalogLoadOutput, CatalogError>;
 
+    /// Latest modification time of the provider's backing storage. Providers whose
+    /// contents cannot change at runtime return `None`.
+    fn change_stamp(&self) -> Option<SystemTime> {
+        None
+    }
+
+    /// Whether `load_templates` may block on the network. The manager never loads
+    /// such providers inline; see [`CatalogManager::remote_refreshes`].
+    fn is_remote(&self) -> bool {
+        false
+    }
+
+    /// Templates a remote provider can serve without blocking, such as its last
+    /// download on disk. Shown until the background load replaces them.
+    fn load_cached(&self) -> CatalogLoadOutput {
+        CatalogLoadOutput::default()
+    }
+
     #[allow(dead_code)]
     fn upsert_template(&self, _template: &TemplateDocument) -> Result<(), CatalogError> {
         Err(CatalogError::ReadOnlyProvider {
@@ -193,7 +232,6 @@ pub trait CatalogProvider: Send + Sync {
         })
     }
 
-    #[allow(dead_code)]
     fn delete_template(&self, _template_id: &str) -> Result<(), CatalogError> {
         Err(CatalogError::ReadOnlyProvider {
             provider_id: self.source().provider_id,
@@ -218,6 +256,9 @@ impl BuiltinCatalogProvider {
                 BUILTIN_CODE_REVIEW_TEMPLATE,
                 BUILTIN_PLAN_REVIEW_TEMPLATE,
                 BUILTIN_FILE_LISTING_TEMPLATE,
+                BUILTIN_UI_DESIGN_REVIEW_TEMPLATE,
+                BUILTIN_COMPARE_TEMPLATE,
+                BUILTIN_DASHBOARD_TEMPLATE,
             ],
         }
     }
@@ -285,6 +326,19 @@ impl CatalogProvider for UserCatalogProvider {
         self.source.clone()
     }
 
+    fn change_stamp(&self) -> Option<SystemTime> {
+        let mut latest = fs::metadata(&self.root_dir).ok()?.modified().ok();
+        for entry in fs::read_dir(&self.root_dir).ok()?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let modified = entry.metadata().ok().and_then(|meta| meta.modified().ok());
+            latest = latest.max(modified);
+        }
+        latest
+    }
+
     fn load_templates(&self) -> Result<CatalogLoadOutput, CatalogError> {
         if !self.root_dir.exists() {
             return Ok(CatalogLoadOutput {
@@ -377,10 +431,343 @@ impl CatalogProvider for UserCatalogProvider {
     }
 }
 
+/// Outcome of a conditional GET issued by a [`CatalogTransport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogFetch {
+    Fresh { body: String, etag: Option<String> },
+    NotModified,
+}
+
+/// Minimal blocking HTTP GET used by [`OrgHttpCatalogProvider`], abstracted so tests
+/// can serve manifests and templates from memory.
+pub trait CatalogTransport: Send + Sync {
+    fn get(&self, url: &str, if_none_match: Option<&str>) -> Result<CatalogFetch, String>;
+}
+
+pub struct HttpCatalogTransport {
+    agent: ureq::Agent,
+}
+
+impl Default for HttpCatalogTransport {
+    fn default() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(ORG_CATALOG_HTTP_TIMEOUT)
+                .build(),
+        }
+    }
+}
+
+impl CatalogTransport for HttpCatalogTransport {
+    fn get(&self, url: &str, if_none_match: Option<&str>) -> Result<CatalogFetch, String> {
+        let mut request = self.agent.get(url);
+        if let Some(etag) = if_none_match {
+            request = request.set("If-None-Match", etag);
+        }
+        let response = request.call().map_err(|err| err.to_string())?;
+        if response.status() == 304 {
+            return Ok(CatalogFetch::NotModified);
+        }
+        let etag = response.header("ETag").map(str::to_string);
+        let body = response.into_string().map_err(|err| err.to_string())?;
+        Ok(CatalogFetch::Fresh { body, etag })
+    }
+}
+
+/// Org catalog base URL from `BROWNIE_ORG_CATALOG_URL`, if set and non-empty.
+pub fn org_catalog_url_from_env() -> Option<String> {
+    std::env::var(ORG_CATALOG_URL_ENV)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Lowercased `scheme://host[:port]` of an http(s) URL. URLs carrying credentials
+/// have no origin, since `user@host` could disguise the real host.
+fn http_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if authority.is_empty() || authority.contains('@') {
+        return None;
+    }
+    Some(format!("{scheme}://{}", authority.to_ascii_lowercase()))
+}
+
+#[derive(Debug, Deserialize)]
+struct OrgCatalogManifest {
+    templates: Vec<String>,
+}
+
+/// Last successful org catalog download, keyed by the manifest ETag.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OrgCatalogCache {
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    templates: Vec<OrgCachedTemplate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OrgCachedTemplate {
+    entry: String,
+    raw: String,
+}
+
+/// Read-only provider backed by a remote manifest (`<base_url>/manifest.json`) that
+/// lists template documents relative to the base URL. With a cache directory, the
+/// last successful download is reused on `304 Not Modified` and when offline.
+pub struct OrgHttpCatalogProvider {
+    source: CatalogSource,
+    base_url: String,
+    transport: Box<dyn CatalogTransport>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl OrgHttpCatalogProvider {
+    pub fn new(provider_id: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self::with_transport(
+            provider_id,
+            base_url,
+            Box::new(HttpCatalogTransport::default()),
+        )
+    }
+
+    pub fn with_transport(
+        provider_id: impl Into<String>,
+        base_url: impl Into<String>,
+        transport: Box<dyn CatalogTransport>,
+    ) -> Self {
+        Self {
+            source: CatalogSource {
+                provider_id: provider_id.into(),
+                kind: CatalogSourceKind::Org,
+                read_only: true,
+            },
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            transport,
+            cache_dir: None,
+        }
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    fn url_for(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    /// URL of a manifest entry. Absolute entries are only fetched from the catalog's
+    /// own origin, so a manifest cannot point the app at other hosts.
+    fn template_url(&self, entry: &str) -> Result<String, String> {
+        let lowered = entry.to_ascii_lowercase();
+        if !lowered.starts_with("http://") && !lowered.starts_with("https://") {
+            return Ok(self.url_for(entry));
+        }
+        match (http_origin(entry), http_origin(&self.base_url)) {
+            (Some(origin), Some(catalog_origin)) if origin == catalog_origin => {
+                Ok(entry.to_string())
+            }
+            _ => Err(format!(
+                "skipped: {entry} is outside the catalog origin {}",
+                self.base_url
+            )),
+        }
+    }
+
+    fn diagnostic(&self, template_ref: impl Into<String>, reason: String) -> CatalogLoadDiagnostic {
+        CatalogLoadDiagnostic {
+            provider_id: self.source.provider_id.clone(),
+            template_ref: template_ref.into(),
+            reason,
+        }
+    }
+
+    fn cache_path(&self) -> Option<PathBuf> {
+        self.cache_dir.as_ref().map(|dir| {
+            dir.join(format!(
+                "{}.json",
+                sanitize_filename(&self.source.provider_id)
+            ))
+        })
+    }
+
+    fn read_cache(&self) -> Option<OrgCatalogCache> {
+        let raw = fs::read_to_string(self.cache_path()?).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn write_cache(&self, cache: &OrgCatalogCache) -> Result<(), String> {
+        let Some(path) = self.cache_path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let raw = serde_json::to_string_pretty(cache).map_err(|err| err.to_string())?;
+        fs::write(&path, raw).map_err(|err| err.to_string())
+    }
+
+    fn parse_into(&self, templates: &[OrgCachedTemplate], output: &mut CatalogLoadOutput) {
+        for cached in templates {
+            match parse_and_validate_template(&cached.raw, &self.source, &cached.entry) {
+                Ok(template) => output.templates.push(template),
+                Err(reason) => output
+                    .diagnostics
+                    .push(self.diagnostic(cached.entry.clone(), reason)),
+            }
+        }
+    }
+
+    fn serve_from_cache(
+        &self,
+        cache: Option<OrgCatalogCache>,
+        manifest_url: String,
+        reason: String,
+        output: &mut CatalogLoadOutput,
+    ) {
+        match cache {
+            Some(cache) => {
+                output
+                    .diagnostics
+                    .push(self.diagnostic(manifest_url, format!("served from cache: {reason}")));
+                self.parse_into(&cache.templates, output);
+            }
+            None => output
+                .diagnostics
+                .push(self.diagnostic(manifest_url, format!("manifest unavailable: {reason}"))),
+        }
+    }
+}
+
+impl CatalogProvider for OrgHttpCatalogProvider {
+    fn source(&self) -> CatalogSource {
+        self.source.clone()
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+
+    fn load_cached(&self) -> CatalogLoadOutput {
+        let mut output = CatalogLoadOutput::default();
+        if let Some(cache) = self.read_cache() {
+            self.parse_into(&cache.templates, &mut output);
+        }
+        output
+    }
+
+    fn load_templates(&self) -> Result<CatalogLoadOutput, CatalogError> {
+        let mut output = CatalogLoadOutput {
+            templates: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let cache = self.read_cache();
+        let cached_etag = cache.as_ref().and_then(|cache| cache.etag.clone());
+        let manifest_url = self.url_for(ORG_CATALOG_MANIFEST);
+        let (manifest, etag) = match self.transport.get(&manifest_url, cached_etag.as_deref()) {
+            Ok(CatalogFetch::NotModified) => {
+                self.serve_from_cache(
+                    cache,
+                    manifest_url,
+                    "manifest not modified".to_string(),
+                    &mut output,
+                );
+                return Ok(output);
+            }
+            Ok(CatalogFetch::Fresh { body, etag }) => {
+                match serde_json::from_str::<OrgCatalogManifest>(&body) {
+                    Ok(manifest) => (manifest, etag),
+                    Err(err) => {
+                        self.serve_from_cache(
+                            cache,
+                            manifest_url,
+                            format!("manifest parse failed: {err}"),
+                            &mut output,
+                        );
+                        return Ok(output);
+                    }
+                }
+            }
+            Err(reason) => {
+                self.serve_from_cache(cache, manifest_url, reason, &mut output);
+                return Ok(output);
+            }
+        };
+
+        let mut fetched = Vec::new();
+        let mut complete = true;
+        for entry in manifest.templates {
+            let template_url = match self.template_url(&entry) {
+                Ok(url) => url,
+                Err(reason) => {
+                    output.diagnostics.push(self.diagnostic(entry, reason));
+                    continue;
+                }
+            };
+            let reason = match self.transport.get(&template_url, None) {
+                Ok(CatalogFetch::Fresh { body, .. }) => {
+                    let cached = OrgCachedTemplate { entry, raw: body };
+                    self.parse_into(std::slice::from_ref(&cached), &mut output);
+                    fetched.push(cached);
+                    continue;
+                }
+                Ok(CatalogFetch::NotModified) => {
+                    "unexpected 304 for unconditional request".to_string()
+                }
+                Err(reason) => reason,
+            };
+            complete = false;
+            // Keep the last good copy of a template that failed to download.
+            let previous = cache
+                .as_ref()
+                .and_then(|cache| cache.templates.iter().find(|cached| cached.entry == entry));
+            match previous {
+                Some(previous) => {
+                    output.diagnostics.push(
+                        self.diagnostic(
+                            entry,
+                            format!("served from cache: fetch failed: {reason}"),
+                        ),
+                    );
+                    self.parse_into(std::slice::from_ref(previous), &mut output);
+                }
+                None => output
+                    .diagnostics
+                    .push(self.diagnostic(entry, format!("fetch failed: {reason}"))),
+            }
+        }
+
+        // A partial download must not be stored under the new ETag, or later 304s
+        // would keep serving the incomplete set.
+        if !complete {
+            return Ok(output);
+        }
+        let cache = OrgCatalogCache {
+            etag,
+            templates: fetched,
+        };
+        if let Err(reason) = self.write_cache(&cache) {
+            output
+                .diagnostics
+                .push(self.diagnostic(manifest_url, format!("cache write failed: {reason}")));
+        }
+
+        Ok(output)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolutionCandidate {
     pub template_id: String,
     pub provider_id: String,
+    pub version: String,
     pub provider_kind: CatalogSourceKind,
     pub score: i32,
     pub operation_overlap: usize,
@@ -435,16 +822,49 @@ pub struct ResolutionResult {
     pub trace: ResolutionTrace,
 }
 
+/// Catalog shared between the UI and the `query_ui_catalog` tool handler.
+pub type SharedCatalog = Arc<Mutex<CatalogManager>>;
+
+/// A blocking load of one remote provider, to run off the UI thread. Hand the
+/// result to [`CatalogManager::apply_remote_load`].
+pub struct RemoteCatalogRefresh {
+    provider: Arc<dyn CatalogProvider>,
+}
+
+impl RemoteCatalogRefresh {
+    pub fn fetch(self) -> RemoteCatalogLoad {
+        RemoteCatalogLoad {
+            provider_id: self.provider.source().provider_id,
+            output: load_provider(self.provider.as_ref()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteCatalogLoad {
+    provider_id: String,
+    output: CatalogLoadOutput,
+}
+
 pub struct CatalogManager {
-    providers: Vec<Box<dyn CatalogProvider>>,
+    providers: Vec<Arc<dyn CatalogProvider>>,
+    loads: Vec<CatalogLoadOutput>,
     templates: Vec<CatalogTemplate>,
     load_diagnostics: Vec<CatalogLoadDiagnostic>,
+    change_stamps: Vec<Option<SystemTime>>,
     org_enabled: bool,
 }
 
 impl CatalogManager {
     pub fn new(providers: Vec<Box<dyn CatalogProvider>>, org_enabled: bool) -> Self {
+        let providers: Vec<Arc<dyn CatalogProvider>> =
+            providers.into_iter().map(Arc::from).collect();
         let mut manager = Self {
+            loads: providers
+                .iter()
+                .map(|provider| provider.load_cached())
+                .collect(),
+            change_stamps: vec![None; providers.len()],
             providers,
             templates: Vec::new(),
             load_diagnostics: Vec::new(),
@@ -454,38 +874,104 @@ impl CatalogManager {
         manager
     }
 
-    pub fn with_default_providers(user_catalog_dir: impl Into<PathBuf>, org_enabled: bool) -> Self {
-        let providers: Vec<Box<dyn CatalogProvider>> = vec![
-            Box::new(UserCatalogProvider::new(
-                "user-local",
-                user_catalog_dir.into(),
-            )),
-            Box::new(BuiltinCatalogProvider::default()),
-        ];
+    /// Builds the standard provider stack. The org tier is only enabled when an org
+    /// catalog base URL is configured.
+    pub fn with_default_providers(
+        user_catalog_dir: impl Into<PathBuf>,
+        org_catalog_url: Option<&str>,
+    ) -> Self {
+        let mut providers: Vec<Box<dyn CatalogProvider>> = Vec::new();
+        let user_catalog_dir = user_catalog_dir.into();
+        if let Some(base_url) = org_catalog_url {
+            let cache_dir = user_catalog_dir
+                .parent()
+                .map(|brownie_dir| brownie_dir.join(ORG_CATALOG_CACHE_DIR))
+                .unwrap_or_else(|| user_catalog_dir.join(ORG_CATALOG_CACHE_DIR));
+            providers.push(Box::new(
+                OrgHttpCatalogProvider::new("org-http", base_url).with_cache_dir(cache_dir),
+            ));
+        }
+        providers.push(Box::new(UserCatalogProvider::new(
+            "user-local",
+            user_catalog_dir,
+        )));
+        providers.push(Box::new(BuiltinCatalogProvider::default()));
+        let org_enabled = org_catalog_url.is_some();
         Self::new(providers, org_enabled)
     }
 
+    /// Reloads every local provider. Remote providers keep their last load; refresh
+    /// them in the background through `remote_refreshes`.
     pub fn reload(&mut self) {
-        self.templates.clear();
-        self.load_diagnostics.clear();
-
-        for provider in &self.providers {
-            match provider.load_templates() {
-                Ok(output) => {
-                    self.templates.extend(output.templates);
-                    self.load_diagnostics.extend(output.diagnostics);
-                }
-                Err(err) => {
-                    let source = provider.source();
-                    self.load_diagnostics.push(CatalogLoadDiagnostic {
-                        provider_id: source.provider_id,
-                        template_ref: "provider".to_string(),
-                        reason: err.to_string(),
-                    });
-                }
+        for index in 0..self.providers.len() {
+            if !self.providers[index].is_remote() {
+                self.reload_provider(index);
             }
         }
+        self.rebuild();
+    }
 
+    /// Reloads only the local providers whose backing storage changed since their
+    /// last load. Returns whether anything was reloaded.
+    pub fn reload_if_changed(&mut self) -> bool {
+        let changed = (0..self.providers.len())
+            .filter(|&index| {
+                !self.providers[index].is_remote()
+                    && self.providers[index].change_stamp() != self.change_stamps[index]
+            })
+            .collect::<Vec<_>>();
+        if changed.is_empty() {
+            return false;
+        }
+        for index in changed {
+            self.reload_provider(index);
+        }
+        self.rebuild();
+        true
+    }
+
+    /// One blocking load per remote provider, for the caller to run off the UI thread.
+    pub fn remote_refreshes(&self) -> Vec<RemoteCatalogRefresh> {
+        self.providers
+            .iter()
+            .filter(|provider| provider.is_remote())
+            .map(|provider| RemoteCatalogRefresh {
+                provider: Arc::clone(provider),
+            })
+            .collect()
+    }
+
+    /// Installs a finished remote load. Returns `false` when no provider has its id.
+    pub fn apply_remote_load(&mut self, load: RemoteCatalogLoad) -> bool {
+        let Some(index) = self
+            .providers
+            .iter()
+            .position(|provider| provider.source().provider_id == load.provider_id)
+        else {
+            return false;
+        };
+        self.loads[index] = load.output;
+        self.rebuild();
+        true
+    }
+
+    fn reload_provider(&mut self, index: usize) {
+        let provider = &self.providers[index];
+        self.change_stamps[index] = provider.change_stamp();
+        self.loads[index] = load_provider(provider.as_ref());
+    }
+
+    fn rebuild(&mut self) {
+        self.templates = self
+            .loads
+            .iter()
+            .flat_map(|load| load.templates.iter().cloned())
+            .collect();
+        self.load_diagnostics = self
+            .loads
+            .iter()
+            .flat_map(|load| load.diagnostics.iter().cloned())
+            .collect();
         self.templates.sort_by(|left, right| {
             left.source
                 .provider_id
@@ -498,15 +984,67 @@ impl CatalogManager {
         &self.load_diagnostics
     }
 
+    /// Loaded and rejected template counts per provider, in provider order. Providers
+    /// that loaded nothing are still listed.
+    pub fn provider_health(&self) -> Vec<ProviderHealth> {
+        self.providers
+            .iter()
+            .map(|provider| {
+                let source = provider.source();
+                let rejections = self
+                    .load_diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.provider_id == source.provider_id)
+                    .map(|diagnostic| format!("{}: {}", diagnostic.template_ref, diagnostic.reason))
+                    .collect::<Vec<_>>();
+                ProviderHealth {
+                    loaded: self
+                        .templates
+                        .iter()
+                        .filter(|template| template.source.provider_id == source.provider_id)
+                        .count(),
+                    rejected: rejections.len(),
+                    rejections,
+                    provider_id: source.provider_id,
+                    kind: source.kind,
+                }
+            })
+            .collect()
+    }
+
+    /// Loaded templates across all providers, ordered by provider id then template id.
+    pub fn templates(&self) -> &[CatalogTemplate] {
+        &self.templates
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<&CatalogTemplate> {
+        self.templates
+            .iter()
+            .find(|template| template.template_id() == id)
+    }
+
+    /// Validated schema of a loaded template, for inspecting it outside the canvas.
+    /// Fails when the id is unknown or the schema no longer validates.
+    pub fn preview(&self, template_id: &str) -> Result<ValidatedSchema, String> {
+        let template = self
+            .find_by_id(template_id)
+            .ok_or_else(|| format!("template {template_id} is not in the catalog"))?;
+        let ui_schema: UiSchema = serde_json::from_value(template.schema_value().clone())
+            .map_err(|err| format!("schema deserialize error: {err}"))?;
+        validate_schema(&ui_schema, &ComponentRegistry::new())
+            .map_err(|err| format!("schema validation error: {err}"))
+    }
+
     pub fn upsert_user_template(
         &mut self,
         template: &TemplateDocument,
     ) -> Result<(), CatalogError> {
-        for provider in &self.providers {
-            let source = provider.source();
+        for index in 0..self.providers.len() {
+            let source = self.providers[index].source();
             if source.kind == CatalogSourceKind::User && !source.read_only {
-                provider.upsert_template(template)?;
-                self.reload();
+                self.providers[index].upsert_template(template)?;
+                self.reload_provider(index);
+                self.rebuild();
                 return Ok(());
             }
         }
@@ -516,6 +1054,31 @@ impl CatalogManager {
         })
     }
 
+    /// Deletes a template from the writable user provider that loaded it. Builtin and
+    /// org templates are read-only and are rejected.
+    pub fn delete_user_template(&mut self, template_id: &str) -> Result<(), CatalogError> {
+        let Some(template) = self.find_by_id(template_id) else {
+            return Ok(());
+        };
+        if template.source.read_only {
+            return Err(CatalogError::ReadOnlyProvider {
+                provider_id: template.source.provider_id.clone(),
+            });
+        }
+
+        let provider_id = template.source.provider_id.clone();
+        if let Some(index) = self
+            .providers
+            .iter()
+            .position(|provider| provider.source().provider_id == provider_id)
+        {
+            self.providers[index].delete_template(template_id)?;
+            self.reload_provider(index);
+            self.rebuild();
+        }
+        Ok(())
+    }
+
     pub fn resolve(&self, intent: &UiIntent) -> ResolutionResult {
         let precedence = self.precedence();
         let mut ranked_candidates = Vec::new();
@@ -536,6 +1099,7 @@ impl CatalogManager {
                 ranked_candidates.push(ResolutionCandidate {
                     template_id: template.template_id().to_string(),
                     provider_id: template.source.provider_id.clone(),
+                    version: template.document.meta.version.clone(),
                     provider_kind: template.source.kind,
                     score: 0,
                     operation_overlap: 0,
@@ -553,6 +1117,7 @@ impl CatalogManager {
             let candidate = ResolutionCandidate {
                 template_id: template.template_id().to_string(),
                 provider_id: template.source.provider_id.clone(),
+                version: template.document.meta.version.clone(),
                 provider_kind: template.source.kind,
                 score: score.total,
                 operation_overlap: score.operation_overlap,
@@ -569,7 +1134,7 @@ impl CatalogManager {
 
         let mut selected: Option<CatalogTemplate> = None;
         let mut selected_tier_index: Option<usize> = None;
-        let mut selected_candidate_key: Option<(String, String)> = None;
+        let mut selected_candidate_key: Option<(String, String, String)> = None;
         for (tier_index, _) in precedence.iter().enumerate() {
             let Some(tier_candidates) = matches_by_tier.get(&tier_index) else {
                 continue;
@@ -578,23 +1143,31 @@ impl CatalogManager {
             sorted.sort_by(rank_candidates);
             if let Some(best) = sorted.first() {
                 selected_tier_index = Some(tier_index);
-                selected_candidate_key = Some((best.template_id.clone(), best.provider_id.clone()));
+                selected_candidate_key = Some((
+                    best.template_id.clone(),
+                    best.provider_id.clone(),
+                    best.version.clone(),
+                ));
                 selected = self
                     .templates
                     .iter()
                     .find(|template| {
                         template.template_id() == best.template_id
                             && template.source.provider_id == best.provider_id
+                            && template.document.meta.version == best.version
                     })
                     .cloned();
                 break;
             }
         }
 
-        if let Some((selected_template_id, selected_provider_id)) = &selected_candidate_key {
+        if let Some((selected_template_id, selected_provider_id, selected_version)) =
+            &selected_candidate_key
+        {
             for candidate in &mut ranked_candidates {
                 if &candidate.template_id == selected_template_id
                     && &candidate.provider_id == selected_provider_id
+                    && &candidate.version == selected_version
                 {
                     candidate.selected = true;
                     continue;
@@ -686,6 +1259,19 @@ impl CatalogManager {
     }
 }
 
+fn load_provider(provider: &dyn CatalogProvider) -> CatalogLoadOutput {
+    provider
+        .load_templates()
+        .unwrap_or_else(|err| CatalogLoadOutput {
+            templates: Vec::new(),
+            diagnostics: vec![CatalogLoadDiagnostic {
+                provider_id: provider.source().provider_id,
+                template_ref: "provider".to_string(),
+                reason: err.to_string(),
+            }],
+        })
+}
+
 fn parse_and_validate_template(
     raw_template: &str,
     source: &CatalogSource,
@@ -802,8 +1388,37 @@ fn rank_candidates(left: &ResolutionCandidate, right: &ResolutionCandidate) -> O
     right
         .score
         .cmp(&left.score)
+        .then_with(|| compare_versions_descending(&left.version, &right.version))
         .then_with(|| left.template_id.cmp(&right.template_id))
         .then_with(|| left.provider_id.cmp(&right.provider_id))
+        .then_with(|| left.version.cmp(&right.version))
+}
+
+/// Orders higher semantic versions first, then unparseable versions lexically, so
+/// the ordering stays total.
+fn compare_versions_descending(left: &str, right: &str) -> Ordering {
+    match (parse_semver(left), parse_semver(right)) {
+        (Some(parsed_left), Some(parsed_right)) => parsed_right.cmp(&parsed_left),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => left.cmp(right),
+    }
+}
+
+/// Parses `MAJOR.MINOR.PATCH`, ignoring a leading `v` and any `+build` metadata.
+/// Pre-release versions are treated as unparseable.
+fn parse_semver(raw: &str) -> Option<(u64, u64, u64)> {
+    let trimmed = raw.trim();
+    let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+    let core = trimmed.split('+').next().unwrap_or_default();
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
 }
 
 fn precedence_index(kind: CatalogSourceKind, precedence: &[CatalogSourceKind]) -> usize {
@@ -813,7 +1428,14 @@ fn precedence_index(kind: CatalogSourceKind, precedence: &[CatalogSourceKind]) -
         .unwrap_or(usize::MAX)
 }
 
-#[allow(dead_code)]
+/// Normalizes a user-supplied template id to lowercase `[a-z0-9._-]`.
+pub fn sanitize_template_id(raw: &str) -> String {
+    let sanitized = sanitize_filename(&raw.trim().to_ascii_lowercase());
+    sanitized
+        .trim_matches(|ch| matches!(ch, '_' | '-' | '.'))
+        .to_string()
+}
+
 fn sanitize_filename(raw: &str) -> String {
     let mut output = String::with_capacity(raw.len());
     for ch in raw.chars() {
@@ -834,9 +1456,9 @@ fn sanitize_filename(raw: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_dir;
     use crate::ui::runtime::UiRuntime;
     use std::fs;
-    use std::time::{SystemTime, UNIX_EPOCH};
 
     struct MemoryCatalogProvider {
         source: CatalogSource,
@@ -882,14 +1504,6 @@ mod tests {
         }
     }
 
-    fn temp_dir(prefix: &str) -> PathBuf {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("time should be monotonic")
-            .as_nanos();
-        std::env::temp_dir().join(format!("brownie_{prefix}_{}_{}", std::process::id(), nanos))
-    }
-
     fn sample_template_json(
         template_id: &str,
         primary: &str,
@@ -1024,6 +1638,33 @@ mod tests {
         let _ = fs::remove_dir_all(root);
     }
 
+    #[test]
+    fn provider_health_counts_loaded_and_rejected_templates() {
+        let valid = sample_template_json("user.code_review", "code_review", &["approve"], &[]);
+        let providers: Vec<Box<dyn CatalogProvider>> = vec![
+            Box::new(MemoryCatalogProvider::new(
+                CatalogSourceKind::User,
+                "user",
+                vec![valid, "{ not json".to_string()],
+            )),
+            Box::new(MemoryCatalogProvider::new(
+                CatalogSourceKind::Org,
+                "org",
+                Vec::new(),
+            )),
+        ];
+        let manager = CatalogManager::new(providers, true);
+
+        let health = manager.provider_health();
+        assert_eq!(health.len(), 2);
+        assert_eq!(health[0].provider_id, "user");
+        assert_eq!(health[0].kind, CatalogSourceKind::User);
+        assert_eq!((health[0].loaded, health[0].rejected), (1, 1));
+        assert!(health[0].rejections[0].starts_with("mem:1: "));
+        assert_eq!(health[1].provider_id, "org");
+        assert_eq!((health[1].loaded, health[1].rejected), (0, 0));
+    }
+
     #[test]
     fn resolver_prefers_user_over_builtin_when_org_disabled() {
         let user_template = sample_template_json(
@@ -1183,10 +1824,16 @@ mod tests {
             vec!["files".to_string(), "workspace".to_string()],
         );
         let result = manager.resolve(&intent);
+        assert_eq!(
+            result.trace.selected_template_id.as_deref(),
+            Some("builtin.file_listing.default")
+        );
+        assert!(result.trace.no_match_reasons.is_empty());
         let selected = result
             .selected
             .expect("a builtin file listing template should match");
         assert_eq!(selected.template_id(), "builtin.file_listing.default");
+        assert_eq!(selected.source.kind, CatalogSourceKind::Builtin);
 
         let mut runtime = UiRuntime::new();
         runtime
@@ -1195,4 +1842,626 @@ mod tests {
         assert!(runtime.has_schema());
         assert!(runtime.runtime_error().is_none());
     }
+
+    #[test]
+    fn preview_validates_builtin_template() {
+        let providers: Vec<Box<dyn CatalogProvider>> =
+            vec![Box::new(BuiltinCatalogProvider::default())];
+        let manager = CatalogManager::new(providers, false);
+
+        let schema = manager
+            .preview("builtin.compare.default")
+            .expect("builtin template should validate");
+        assert!(!schema.components.is_empty());
+        assert!(manager
+            .preview("builtin.missing")
+            .is_err_and(|err| err.contains("not in the catalog")));
+    }
+
+    fn resolve_builtin_prompt(prompt: &str, primary: &str) -> ResolutionResult {
+        let providers: Vec<Box<dyn CatalogProvider>> =
+            vec![Box::new(BuiltinCatalogProvider::default())];
+        let manager = CatalogManager::new(providers, false);
+        let intent =
+            crate::ui::intent::intent_from_text(prompt).expect("intent should be detected");
+        assert_eq!(intent.primary, primary);
+        manager.resolve(&intent)
+    }
+
+    #[test]
+    fn resolver_selects_builtin_compare_template() {
+        let result =
+            resolve_builtin_prompt("compare the old and new config side by side", "compare");
+        let selected = result
+            .selected
+            .expect("a builtin compare template should match");
+        assert_eq!(selected.template_id(), "builtin.compare.default");
+
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(selected.schema_value())
+            .expect("compare schema should validate and load");
+        assert!(runtime.runtime_error().is_none());
+    }
+
+    #[test]
+    fn resolver_selects_builtin_dashboard_template() {
+        let result = resolve_builtin_prompt("give me a summary of the project", "dashboard");
+        let selected = result
+            .selected
+            .expect("a builtin dashboard template should match");
+        assert_eq!(selected.template_id(), "builtin.dashboard.default");
+
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(selected.schema_value())
+            .expect("dashboard schema should validate and load");
+        assert!(runtime.runtime_error().is_none());
+    }
+
+    #[test]
+    fn resolver_selects_builtin_ui_design_review_template() {
+        let providers: Vec<Box<dyn CatalogProvider>> =
+            vec![Box::new(BuiltinCatalogProvider::default())];
+        let manager = CatalogManager::new(providers, false);
+        let intent = crate::ui::intent::intent_from_text("review this UI design")
+            .expect("ui design intent should be detected");
+        assert_eq!(intent.primary, "ui_design_review");
+
+        let result = manager.resolve(&intent);
+        let selected = result
+            .selected
+            .expect("a builtin ui design review template should match");
+        assert_eq!(selected.template_id(), "builtin.ui_design_review.default");
+        assert_eq!(selected.source.kind, CatalogSourceKind::Builtin);
+
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(selected.schema_value())
+            .expect("selected ui design review schema should validate and load");
+        assert!(runtime.has_schema());
+        assert!(runtime.runtime_error().is_none());
+    }
+
+    #[test]
+    fn manager_lists_templates_and_finds_them_by_id() {
+        let providers: Vec<Box<dyn CatalogProvider>> = vec![
+            Box::new(MemoryCatalogProvider::new(
+                CatalogSourceKind::User,
+                "user-mem",
+                vec![
+                    sample_template_json("user.alpha", "code_review", &["approve"], &[]),
+                    sample_template_json("user.beta", "plan_review", &["revise"], &[]),
+                ],
+            )),
+            Box::new(BuiltinCatalogProvider::default()),
+        ];
+        let builtin_count = BuiltinCatalogProvider::default()
+            .load_templates()
+            .expect("builtin load should succeed")
+            .templates
+            .len();
+        let manager = CatalogManager::new(providers, false);
+
+        assert_eq!(manager.templates().len(), builtin_count + 2);
+
+        let beta = manager
+            .find_by_id("user.beta")
+            .expect("user template should be found by id");
+        assert_eq!(beta.source.provider_id, "user-mem");
+        assert_eq!(beta.document.match_rules.primary, "plan_review");
+
+        let builtin = manager
+            .find_by_id("builtin.code_review.default")
+            .expect("builtin template should be found by id");
+        assert_eq!(builtin.source.kind, CatalogSourceKind::Builtin);
+
+        assert!(manager.find_by_id("missing.template").is_none());
+    }
+
+    #[test]
+    fn resolver_prefers_highest_semver_within_a_tier() {
+        let older = sample_template_json("a.review", "code_review", &["approve"], &["diff"]);
+        let newer = sample_template_json("b.review", "code_review", &["approve"], &["diff"])
+            .replace("\"version\": \"1.0.0\"", "\"version\": \"1.2.0\"");
+        assert!(newer.contains("1.2.0"));
+        let providers: Vec<Box<dyn CatalogProvider>> = vec![Box::new(MemoryCatalogProvider::new(
+            CatalogSourceKind::User,
+            "user-mem",
+            vec![older, newer],
+        ))];
+        let manager = CatalogManager::new(providers, false);
+        let intent = UiIntent::new(
+            "code_review",
+            vec!["approve".to_string()],
+            vec!["diff".to_string()],
+        );
+
+        let result = manager.resolve(&intent);
+        let selected = result.selected.expect("a template should be selected");
+        assert_eq!(selected.template_id(), "b.review");
+        assert_eq!(selected.document.meta.version, "1.2.0");
+    }
+
+    #[test]
+    fn semver_comparison_sorts_unparseable_versions_last() {
+        assert_eq!(parse_semver("1.10.0"), Some((1, 10, 0)));
+        assert_eq!(parse_semver("v2.0.1+build.7"), Some((2, 0, 1)));
+        assert_eq!(parse_semver("1.0.0-beta"), None);
+        assert_eq!(parse_semver("latest"), None);
+        assert_eq!(
+            compare_versions_descending("1.10.0", "1.9.0"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_versions_descending("latest", "1.9.0"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_versions_descending("1.9.0", "latest"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_versions_descending("beta", "latest"),
+            Ordering::Less
+        );
+
+        let mut versions = vec!["latest", "1.9.0", "beta", "1.10.0"];
+        versions.sort_by(|left, right| compare_versions_descending(left, right));
+        assert_eq!(versions, vec!["1.10.0", "1.9.0", "beta", "latest"]);
+    }
+
+    #[test]
+    fn reload_if_changed_picks_up_new_user_template_files() {
+        let root = temp_dir("catalog_reload");
+        fs::create_dir_all(&root).expect("catalog dir should be created");
+        let providers: Vec<Box<dyn CatalogProvider>> =
+            vec![Box::new(UserCatalogProvider::new("user-local", &root))];
+        let mut manager = CatalogManager::new(providers, false);
+        assert!(manager.templates().is_empty());
+        assert!(!manager.reload_if_changed());
+
+        fs::write(
+            root.join("fresh.json"),
+            sample_template_json("user.fresh", "code_review", &["approve"], &[]),
+        )
+        .expect("template file should be written");
+
+        assert!(manager.reload_if_changed());
+        assert!(manager.find_by_id("user.fresh").is_some());
+        assert!(!manager.reload_if_changed());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    struct MockTransport {
+        responses: BTreeMap<String, Result<String, String>>,
+        etag: Option<String>,
+    }
+
+    impl MockTransport {
+        fn new(responses: BTreeMap<String, Result<String, String>>) -> Self {
+            Self {
+                responses,
+                etag: None,
+            }
+        }
+
+        fn with_etag(mut self, etag: &str) -> Self {
+            self.etag = Some(etag.to_string());
+            self
+        }
+    }
+
+    impl CatalogTransport for MockTransport {
+        fn get(&self, url: &str, if_none_match: Option<&str>) -> Result<CatalogFetch, String> {
+            if if_none_match.is_some() && if_none_match == self.etag.as_deref() {
+                return Ok(CatalogFetch::NotModified);
+            }
+            let body = self
+                .responses
+                .get(url)
+                .cloned()
+                .unwrap_or_else(|| Err(format!("404 not found: {url}")))?;
+            Ok(CatalogFetch::Fresh {
+                body,
+                etag: self.etag.clone(),
+            })
+        }
+    }
+
+    fn org_responses() -> BTreeMap<String, Result<String, String>> {
+        BTreeMap::from([
+            (
+                "https://catalog.example.com/org/manifest.json".to_string(),
+                Ok(r#"{"templates":["review.json"]}"#.to_string()),
+            ),
+            (
+                "https://catalog.example.com/org/review.json".to_string(),
+                Ok(sample_template_json(
+                    "org.review",
+                    "code_review",
+                    &["approve"],
+                    &[],
+                )),
+            ),
+        ])
+    }
+
+    #[test]
+    fn org_http_provider_loads_manifest_templates_and_reports_bad_ones() {
+        let transport = MockTransport::new(BTreeMap::from([
+            (
+                "https://catalog.example.com/org/manifest.json".to_string(),
+                Ok(r#"{"templates":["review.json","broken.json","missing.json"]}"#.to_string()),
+            ),
+            (
+                "https://catalog.example.com/org/review.json".to_string(),
+                Ok(sample_template_json(
+                    "org.review",
+                    "code_review",
+                    &["approve"],
+                    &[],
+                )),
+            ),
+            (
+                "https://catalog.example.com/org/broken.json".to_string(),
+                Ok("{not json".to_string()),
+            ),
+        ]));
+        let provider = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org/",
+            Box::new(transport),
+        );
+
+        let loaded = provider
+            .load_templates()
+            .expect("org load should degrade instead of failing");
+        assert_eq!(loaded.templates.len(), 1);
+        assert_eq!(loaded.templates[0].template_id(), "org.review");
+        assert_eq!(loaded.templates[0].source.kind, CatalogSourceKind::Org);
+        assert_eq!(loaded.diagnostics.len(), 2);
+        assert_eq!(loaded.diagnostics[0].template_ref, "broken.json");
+        assert!(loaded.diagnostics[1].reason.contains("fetch failed"));
+
+        let template = loaded.templates[0].document.clone();
+        assert!(matches!(
+            provider.upsert_template(&template),
+            Err(CatalogError::ReadOnlyProvider { .. })
+        ));
+        assert!(matches!(
+            provider.delete_template("org.review"),
+            Err(CatalogError::ReadOnlyProvider { .. })
+        ));
+    }
+
+    #[test]
+    fn org_http_provider_only_fetches_absolute_entries_from_its_origin() {
+        let mut responses = org_responses();
+        responses.insert(
+            "https://catalog.example.com/org/manifest.json".to_string(),
+            Ok(r#"{"templates":[
+                "HTTPS://Catalog.Example.com/org/review.json",
+                "https://evil.example.net/org/review.json",
+                "https://catalog.example.com@evil.example.net/org/review.json",
+                "http://catalog.example.com/org/review.json"
+            ]}"#
+            .to_string()),
+        );
+        for url in [
+            "https://evil.example.net/org/review.json",
+            "https://catalog.example.com@evil.example.net/org/review.json",
+            "http://catalog.example.com/org/review.json",
+        ] {
+            responses.insert(
+                url.to_string(),
+                Ok(sample_template_json("evil.review", "code_review", &[], &[])),
+            );
+        }
+        responses.insert(
+            "HTTPS://Catalog.Example.com/org/review.json".to_string(),
+            responses["https://catalog.example.com/org/review.json"].clone(),
+        );
+        let provider = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(responses)),
+        );
+
+        let loaded = provider.load_templates().expect("org load");
+        let ids = loaded
+            .templates
+            .iter()
+            .map(|template| template.template_id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["org.review"]);
+        assert_eq!(loaded.diagnostics.len(), 3);
+        assert!(loaded
+            .diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.reason.contains("outside the catalog origin")));
+    }
+
+    #[test]
+    fn org_http_provider_degrades_to_empty_load_on_network_error() {
+        let provider = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(BTreeMap::new())),
+        );
+        let providers: Vec<Box<dyn CatalogProvider>> = vec![
+            Box::new(provider),
+            Box::new(BuiltinCatalogProvider::default()),
+        ];
+        let mut manager = CatalogManager::new(providers, true);
+        for refresh in manager.remote_refreshes() {
+            assert!(manager.apply_remote_load(refresh.fetch()));
+        }
+
+        assert!(manager
+            .templates()
+            .iter()
+            .all(|template| template.source.kind == CatalogSourceKind::Builtin));
+        assert_eq!(manager.load_diagnostics().len(), 1);
+        assert!(manager.load_diagnostics()[0]
+            .reason
+            .starts_with("manifest unavailable"));
+    }
+
+    #[test]
+    fn manager_loads_remote_providers_only_through_background_refresh() {
+        let provider = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(org_responses())),
+        );
+        let root = temp_dir("catalog_remote_refresh");
+        fs::create_dir_all(&root).expect("catalog dir should be created");
+        let providers: Vec<Box<dyn CatalogProvider>> = vec![
+            Box::new(provider),
+            Box::new(UserCatalogProvider::new("user-local", &root)),
+        ];
+        let mut manager = CatalogManager::new(providers, true);
+        assert!(manager.templates().is_empty());
+
+        let refreshes = manager.remote_refreshes();
+        assert_eq!(refreshes.len(), 1);
+        let loads = std::thread::spawn(move || {
+            refreshes
+                .into_iter()
+                .map(RemoteCatalogRefresh::fetch)
+                .collect::<Vec<_>>()
+        })
+        .join()
+        .expect("refresh thread should finish");
+        for load in loads {
+            assert!(manager.apply_remote_load(load));
+        }
+        assert!(manager.find_by_id("org.review").is_some());
+
+        // A user catalog change reloads the user provider and keeps the org load.
+        fs::write(
+            root.join("user_review.json"),
+            sample_template_json("user.review", "code_review", &["approve"], &[]),
+        )
+        .expect("user template should write");
+        assert!(manager.reload_if_changed());
+        assert!(manager.find_by_id("org.review").is_some());
+        assert!(manager.find_by_id("user.review").is_some());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    struct UnreachableTransport;
+
+    impl CatalogTransport for UnreachableTransport {
+        fn get(&self, url: &str, _if_none_match: Option<&str>) -> Result<CatalogFetch, String> {
+            panic!("manager must not fetch {url} inline");
+        }
+    }
+
+    #[test]
+    fn manager_serves_org_cache_before_background_revalidation() {
+        let cache_dir = temp_dir("org_cache_startup");
+        OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(org_responses())),
+        )
+        .with_cache_dir(&cache_dir)
+        .load_templates()
+        .expect("fresh org load should succeed");
+
+        let unreachable = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(UnreachableTransport),
+        )
+        .with_cache_dir(&cache_dir);
+        let providers: Vec<Box<dyn CatalogProvider>> = vec![Box::new(unreachable)];
+        let mut manager = CatalogManager::new(providers, true);
+        assert!(manager.find_by_id("org.review").is_some());
+        assert!(manager.load_diagnostics().is_empty());
+
+        manager.reload();
+        assert!(manager.find_by_id("org.review").is_some());
+
+        let _ = fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn org_http_provider_serves_cache_when_manifest_not_modified() {
+        let cache_dir = temp_dir("org_cache_304");
+        let first = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(org_responses()).with_etag("\"v1\"")),
+        )
+        .with_cache_dir(&cache_dir);
+        let loaded = first
+            .load_templates()
+            .expect("fresh org load should succeed");
+        assert_eq!(loaded.templates.len(), 1);
+        assert!(loaded.diagnostics.is_empty());
+
+        // The template body is gone upstream; only a 304 on the manifest can satisfy it.
+        let mut responses = org_responses();
+        responses.remove("https://catalog.example.com/org/review.json");
+        let second = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(responses).with_etag("\"v1\"")),
+        )
+        .with_cache_dir(&cache_dir);
+        let loaded = second
+            .load_templates()
+            .expect("cached org load should succeed");
+        assert_eq!(loaded.templates.len(), 1);
+        assert_eq!(loaded.templates[0].template_id(), "org.review");
+        assert_eq!(loaded.diagnostics.len(), 1);
+        assert!(loaded.diagnostics[0]
+            .reason
+            .contains("served from cache: manifest not modified"));
+
+        let _ = fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn org_http_provider_keeps_cached_templates_when_a_fetch_fails() {
+        let cache_dir = temp_dir("org_cache_partial");
+        OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(org_responses()).with_etag("\"v1\"")),
+        )
+        .with_cache_dir(&cache_dir)
+        .load_templates()
+        .expect("fresh org load should succeed");
+        let cached = fs::read_to_string(cache_dir.join("org-http.json")).expect("cache written");
+
+        // The manifest changed, but the review template fails to download this time.
+        let mut responses = org_responses();
+        responses.insert(
+            "https://catalog.example.com/org/manifest.json".to_string(),
+            Ok(r#"{"templates":["review.json","plan.json"]}"#.to_string()),
+        );
+        responses.insert(
+            "https://catalog.example.com/org/review.json".to_string(),
+            Err("503 service unavailable".to_string()),
+        );
+        responses.insert(
+            "https://catalog.example.com/org/plan.json".to_string(),
+            Ok(sample_template_json("org.plan", "plan_review", &[], &[])),
+        );
+        let partial = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(responses).with_etag("\"v2\"")),
+        )
+        .with_cache_dir(&cache_dir);
+        let loaded = partial
+            .load_templates()
+            .expect("partial org load should succeed");
+
+        let mut ids = loaded
+            .templates
+            .iter()
+            .map(|template| template.template_id().to_string())
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec!["org.plan", "org.review"]);
+        assert_eq!(loaded.diagnostics.len(), 1);
+        assert!(loaded.diagnostics[0]
+            .reason
+            .starts_with("served from cache: fetch failed: 503"));
+        assert_eq!(
+            fs::read_to_string(cache_dir.join("org-http.json")).expect("cache kept"),
+            cached,
+            "an incomplete download must not replace the cache or its etag"
+        );
+
+        let _ = fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn org_http_provider_falls_back_to_cache_on_network_failure() {
+        let cache_dir = temp_dir("org_cache_offline");
+        OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(org_responses())),
+        )
+        .with_cache_dir(&cache_dir)
+        .load_templates()
+        .expect("fresh org load should succeed");
+
+        let offline = OrgHttpCatalogProvider::with_transport(
+            "org-http",
+            "https://catalog.example.com/org",
+            Box::new(MockTransport::new(BTreeMap::new())),
+        )
+        .with_cache_dir(&cache_dir);
+        let loaded = offline
+            .load_templates()
+            .expect("offline org load should degrade to cache");
+        assert_eq!(loaded.templates.len(), 1);
+        assert_eq!(loaded.templates[0].source.kind, CatalogSourceKind::Org);
+        assert_eq!(loaded.diagnostics.len(), 1);
+        assert!(loaded.diagnostics[0]
+            .reason
+            .starts_with("served from cache"));
+
+        let _ = fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn deleting_user_template_removes_it_from_resolution() {
+        let root = temp_dir("catalog_delete");
+        let providers: Vec<Box<dyn CatalogProvider>> = vec![
+            Box::new(UserCatalogProvider::new("user-local", &root)),
+            Box::new(BuiltinCatalogProvider::default()),
+        ];
+        let mut manager = CatalogManager::new(providers, false);
+        let template = serde_json::from_str::<TemplateDocument>(&sample_template_json(
+            "user.code_review",
+            "code_review",
+            &["approve"],
+            &[],
+        ))
+        .expect("sample template should parse");
+        manager
+            .upsert_user_template(&template)
+            .expect("user template should save");
+
+        let intent = UiIntent::new("code_review", vec!["approve".to_string()], vec![]);
+        let before = manager.resolve(&intent);
+        assert_eq!(
+            before.trace.selected_template_id.as_deref(),
+            Some("user.code_review")
+        );
+
+        manager
+            .delete_user_template("user.code_review")
+            .expect("user template should delete");
+        assert!(manager.find_by_id("user.code_review").is_none());
+        let after = manager.resolve(&intent);
+        assert_eq!(
+            after.trace.selected_template_id.as_deref(),
+            Some("builtin.code_review.default")
+        );
+        assert!(after
+            .trace
+            .ranked_candidates
+            .iter()
+            .all(|candidate| candidate.template_id != "user.code_review"));
+
+        assert!(matches!(
+            manager.delete_user_template("builtin.code_review.default"),
+            Err(CatalogError::ReadOnlyProvider { .. })
+        ));
+        assert!(manager.find_by_id("builtin.code_review.default").is_some());
+
+        let _ = fs::remove_dir_all(root);
+    }
 }
@@ -1,14 +1,31 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{vec_deque, BTreeMap, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::ui::workspace::{CanvasBlockActionStatus, CanvasBlockActionType, CanvasBlockActor};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum UiFieldValue {
-    Text { value: String },
-    Number { value: f64 },
-    Select { value: String },
-    Checkbox { value: bool },
+    Text {
+        value: String,
+    },
+    Number {
+        value: f64,
+    },
+    Select {
+        value: String,
+    },
+    MultiSelect {
+        value: Vec<String>,
+    },
+    Checkbox {
+        value: bool,
+    },
+    /// ISO-8601 calendar date (`YYYY-MM-DD`), or empty when unset.
+    Date {
+        value: String,
+    },
 }
 
 impl UiFieldValue {
@@ -17,7 +34,21 @@ impl UiFieldValue {
             Self::Text { value } => value.clone(),
             Self::Number { value } => value.to_string(),
             Self::Select { value } => value.clone(),
+            Self::MultiSelect { value } => value.join(","),
             Self::Checkbox { value } => value.to_string(),
+            Self::Date { value } => value.clone(),
+        }
+    }
+
+    /// Whether the value should count as "not filled in" for required fields.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Text { value } | Self::Select { value } | Self::Date { value } => {
+                value.trim().is_empty()
+            }
+            Self::Number { .. } => false,
+            Self::MultiSelect { value } => value.is_empty(),
+            Self::Checkbox { value } => !value,
         }
     }
 }
@@ -28,6 +59,8 @@ pub enum UiEvent {
     ButtonClicked {
         component_id: String,
         output_event_id: String,
+        #[serde(default)]
+        form_values: BTreeMap<String, UiFieldValue>,
     },
     FormFieldCommitted {
         component_id: String,
@@ -35,6 +68,15 @@ pub enum UiEvent {
         field_id: String,
         value: UiFieldValue,
     },
+    TreeNodeClicked {
+        component_id: String,
+        path: String,
+    },
+    /// `tab_index` counts every child of the tabs component, hidden ones included.
+    TabSelected {
+        component_id: String,
+        tab_index: usize,
+    },
     CanvasBlockLifecycle {
         action: CanvasBlockActionType,
         actor: CanvasBlockActor,
@@ -51,8 +93,19 @@ impl UiEvent {
             Self::ButtonClicked {
                 component_id,
                 output_event_id,
+                form_values,
             } => {
-                format!("button_clicked component_id={component_id} output={output_event_id}")
+                let mut line =
+                    format!("button_clicked component_id={component_id} output={output_event_id}");
+                if !form_values.is_empty() {
+                    let values = form_values
+                        .iter()
+                        .map(|(key, value)| format!("{key}={}", value.display_value()))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    line.push_str(&format!(" values={{{values}}}"));
+                }
+                line
             }
             Self::FormFieldCommitted {
                 component_id,
@@ -63,6 +116,13 @@ impl UiEvent {
                 "form_field_committed component_id={component_id} form_id={form_id} field_id={field_id} value={}",
                 value.display_value()
             ),
+            Self::TreeNodeClicked { component_id, path } => {
+                format!("tree_node_clicked component_id={component_id} path={path}")
+            }
+            Self::TabSelected {
+                component_id,
+                tab_index,
+            } => format!("tab_selected component_id={component_id} tab_index={tab_index}"),
             Self::CanvasBlockLifecycle {
                 action,
                 actor,
@@ -84,25 +144,164 @@ impl UiEvent {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+/// A logged `UiEvent` with the unix millis at which it was pushed. Entries written
+/// before timestamps existed deserialize with `timestamp` 0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiEventEntry {
+    #[serde(default)]
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub event: UiEvent,
+}
+
+impl UiEventEntry {
+    pub fn to_log_line(&self) -> String {
+        format!("ts={} {}", self.timestamp, self.event.to_log_line())
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+pub const DEFAULT_EVENT_LOG_MAX_ENTRIES: usize = 1000;
+
+/// Bounded event log: once `max_entries` is reached the oldest events are evicted.
+/// `total_pushed` keeps counting across evictions so readers can track what they
+/// have already seen with `entries_since`.
+#[derive(Debug, Clone)]
 pub struct UiEventLog {
-    entries: Vec<UiEvent>,
+    entries: VecDeque<UiEventEntry>,
+    max_entries: usize,
+    total_pushed: usize,
+}
+
+impl Default for UiEventLog {
+    fn default() -> Self {
+        Self::with_max_entries(DEFAULT_EVENT_LOG_MAX_ENTRIES)
+    }
 }
 
 impl UiEventLog {
-    pub fn entries(&self) -> &[UiEvent] {
+    pub fn with_max_entries(max_entries: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_entries: max_entries.max(1),
+            total_pushed: 0,
+        }
+    }
+
+    pub fn entries(&self) -> &VecDeque<UiEventEntry> {
         &self.entries
     }
 
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Appends `event` stamped with the current time.
     pub fn push(&mut self, event: UiEvent) {
-        self.entries.push(event);
+        self.push_entry(UiEventEntry {
+            timestamp: unix_millis(),
+            event,
+        });
+    }
+
+    /// Appends an entry as is, keeping the time it was first logged elsewhere.
+    pub fn push_entry(&mut self, entry: UiEventEntry) {
+        if self.entries.len() == self.max_entries {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        self.total_pushed += 1;
+    }
+
+    /// Drops every event and restarts the count, keeping the configured cap.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.total_pushed = 0;
+    }
+
+    /// Number of events ever pushed, including evicted ones.
+    pub fn total_pushed(&self) -> usize {
+        self.total_pushed
+    }
+
+    /// Retained events pushed after the first `seen` events; evicted ones are skipped.
+    pub fn entries_since(&self, seen: usize) -> vec_deque::Iter<'_, UiEventEntry> {
+        let evicted = self.total_pushed - self.entries.len();
+        let start = seen.saturating_sub(evicted).min(self.entries.len());
+        self.entries.range(start..)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{UiEvent, UiEventLog};
+    use super::{UiEvent, UiEventEntry, UiEventLog, UiFieldValue};
     use crate::ui::workspace::{CanvasBlockActionStatus, CanvasBlockActionType, CanvasBlockActor};
+    use std::collections::BTreeMap;
+
+    fn clicked(path: &str) -> UiEvent {
+        UiEvent::TreeNodeClicked {
+            component_id: "tree".to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    fn events<'a>(entries: impl IntoIterator<Item = &'a UiEventEntry>) -> Vec<UiEvent> {
+        entries
+            .into_iter()
+            .map(|entry| entry.event.clone())
+            .collect()
+    }
+
+    #[test]
+    fn pushing_past_cap_keeps_most_recent_events_in_order() {
+        let mut log = UiEventLog::with_max_entries(3);
+        for index in 0..5 {
+            log.push(clicked(&index.to_string()));
+        }
+
+        assert_eq!(
+            events(log.entries()),
+            vec![clicked("2"), clicked("3"), clicked("4")]
+        );
+        assert_eq!(log.total_pushed(), 5);
+    }
+
+    #[test]
+    fn clear_keeps_the_configured_cap() {
+        let mut log = UiEventLog::with_max_entries(2);
+        log.push(clicked("a"));
+        log.clear();
+        assert!(log.entries().is_empty());
+        assert_eq!(log.total_pushed(), 0);
+
+        for index in 0..3 {
+            log.push(clicked(&index.to_string()));
+        }
+        assert_eq!(events(log.entries()), vec![clicked("1"), clicked("2")]);
+    }
+
+    #[test]
+    fn entries_since_survives_eviction() {
+        let mut log = UiEventLog::with_max_entries(2);
+        log.push(clicked("a"));
+        let seen = log.total_pushed();
+        log.push(clicked("b"));
+        log.push(clicked("c"));
+        log.push(clicked("d"));
+
+        // "b" was evicted before it was read; only the retained tail is returned.
+        assert_eq!(
+            events(log.entries_since(seen)),
+            vec![clicked("c"), clicked("d")]
+        );
+        assert_eq!(log.entries_since(log.total_pushed()).count(), 0);
+    }
 
     #[test]
     fn lifecycle_events_render_machine_readable_log_line() {
@@ -122,6 +321,42 @@ mod tests {
         assert!(line.contains("message=ok"));
     }
 
+    #[test]
+    fn button_click_log_line_includes_submitted_values() {
+        let event = UiEvent::ButtonClicked {
+            component_id: "approve_btn".to_string(),
+            output_event_id: "action.approve".to_string(),
+            form_values: BTreeMap::from([
+                (
+                    "review_form:decision".to_string(),
+                    UiFieldValue::Select {
+                        value: "approve".to_string(),
+                    },
+                ),
+                (
+                    "review_form:risk_score".to_string(),
+                    UiFieldValue::Number { value: 2.0 },
+                ),
+            ]),
+        };
+        assert_eq!(
+            event.to_log_line(),
+            "button_clicked component_id=approve_btn output=action.approve values={review_form:decision=approve,review_form:risk_score=2}"
+        );
+    }
+
+    #[test]
+    fn tree_node_click_log_line_includes_path() {
+        let event = UiEvent::TreeNodeClicked {
+            component_id: "workspace_tree".to_string(),
+            path: "src/main.rs".to_string(),
+        };
+        assert_eq!(
+            event.to_log_line(),
+            "tree_node_clicked component_id=workspace_tree path=src/main.rs"
+        );
+    }
+
     #[test]
     fn ui_event_log_is_append_only_and_ordered() {
         let mut log = UiEventLog::default();
@@ -143,7 +378,40 @@ mod tests {
         log.push(second.clone());
 
         assert_eq!(log.entries().len(), 2);
-        assert_eq!(log.entries()[0], first);
-        assert_eq!(log.entries()[1], second);
+        assert_eq!(log.entries()[0].event, first);
+        assert_eq!(log.entries()[1].event, second);
+    }
+
+    #[test]
+    fn pushed_events_are_timestamped_in_log_line() {
+        let mut log = UiEventLog::default();
+        log.push(clicked("src/lib.rs"));
+
+        let entry = &log.entries()[0];
+        assert!(entry.timestamp > 0);
+        assert_eq!(
+            entry.to_log_line(),
+            format!(
+                "ts={} tree_node_clicked component_id=tree path=src/lib.rs",
+                entry.timestamp
+            )
+        );
+    }
+
+    #[test]
+    fn entries_without_timestamp_still_deserialize() {
+        let entry: UiEventEntry = serde_json::from_value(serde_json::json!({
+            "event": "tree_node_clicked",
+            "component_id": "tree",
+            "path": "a.rs"
+        }))
+        .expect("pre-timestamp entry should deserialize");
+        assert_eq!(entry.timestamp, 0);
+        assert_eq!(entry.event, clicked("a.rs"));
+
+        let round_trip: UiEventEntry =
+            serde_json::from_value(serde_json::to_value(&entry).expect("serialize"))
+                .expect("deserialize");
+        assert_eq!(round_trip, entry);
     }
 }
@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+
+use crate::ui::event::UiFieldValue;
+use crate::ui::schema::{
+    field_key, AlertSeverity, DiffLineKind, TreeNode, ValidatedComponent, ValidatedFormField,
+    ValidatedSchema,
+};
+
+const EXPORT_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;color:#1f2328}\
+section.block{border:1px solid #d0d7de;border-radius:10px;padding:1rem 1.25rem;margin-bottom:1.5rem}\
+.template{color:#656d76;font-size:.85rem;margin-top:-.5rem}\
+pre{background:#f6f8fa;border-radius:6px;padding:.75rem;overflow-x:auto}\
+.diff .added{background:#dafbe1;color:#116329}.diff .removed{background:#ffebe9;color:#82071e}\
+.diff span{display:block}\
+table{border-collapse:collapse}th,td{border:1px solid #d0d7de;padding:.3rem .6rem;text-align:left}\
+.alert{border-radius:6px;padding:.6rem .8rem;margin:.5rem 0}\
+.alert-info{background:#ddf4ff}.alert-warning{background:#fff8c5}.alert-danger{background:#ffebe9}.alert-success{background:#dafbe1}\
+.row{display:flex;gap:1rem}.row>*{flex:1;min-width:0}\
+.bar{background:#0969da;height:.8rem;border-radius:3px}\
+dl{display:grid;grid-template-columns:max-content auto;gap:.25rem 1rem}dt{font-weight:600}dd{margin:0}";
+
+/// One canvas block as it should appear in an export.
+pub struct ExportBlock<'a> {
+    pub title: &'a str,
+    pub template_id: &'a str,
+    pub schema: &'a ValidatedSchema,
+    pub form_state: &'a BTreeMap<String, UiFieldValue>,
+}
+
+/// Self-contained HTML page for `blocks`: inline styles, no scripts, forms read-only.
+/// Hidden components (`visible_when` not met) are left out, as on the canvas.
+pub fn canvas_html(blocks: &[ExportBlock]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Brownie Canvas</title>\n<style>",
+    );
+    out.push_str(EXPORT_STYLE);
+    out.push_str("</style>\n</head>\n<body>\n<h1>Brownie Canvas</h1>\n");
+    if blocks.is_empty() {
+        out.push_str("<p>No open canvas blocks.</p>\n");
+    }
+    for block in blocks {
+        out.push_str("<section class=\"block\">\n");
+        out.push_str(&format!("<h2>{}</h2>\n", escape_html(block.title)));
+        out.push_str(&format!(
+            "<p class=\"template\">{}</p>\n",
+            escape_html(block.template_id)
+        ));
+        for component in &block.schema.components {
+            component_html(&mut out, component, block.form_state);
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn component_html(
+    out: &mut String,
+    component: &ValidatedComponent,
+    form_state: &BTreeMap<String, UiFieldValue>,
+) {
+    if !component.is_visible(form_state) {
+        return;
+    }
+    match component {
+        ValidatedComponent::Markdown(markdown) => out.push_str(&markdown_html(&markdown.text)),
+        ValidatedComponent::Form(form) => {
+            out.push_str("<form>\n");
+            if let Some(title) = &form.title {
+                out.push_str(&format!("<h3>{}</h3>\n", escape_html(title)));
+            }
+            for field in &form.fields {
+                let value = form_state
+                    .get(&field_key(&form.id, field.id()))
+                    .cloned()
+                    .unwrap_or_else(|| field.default_value());
+                out.push_str(&format!(
+                    "<p><label>{}{} {}</label></p>\n",
+                    escape_html(field.label()),
+                    if field.is_required() { " *" } else { "" },
+                    field_html(field, &value)
+                ));
+            }
+            out.push_str("</form>\n");
+        }
+        ValidatedComponent::Code(code) => {
+            let class = code
+                .language
+                .as_deref()
+                .map(|language| format!(" class=\"language-{}\"", escape_html(language)))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<pre><code{class}>{}</code></pre>\n",
+                escape_html(&code.code)
+            ));
+        }
+        ValidatedComponent::Diff(diff) => {
+            out.push_str("<pre class=\"diff\">");
+            for line in &diff.lines {
+                let class = match line.kind {
+                    DiffLineKind::Added => "added",
+                    DiffLineKind::Removed => "removed",
+                    DiffLineKind::Context => "context",
+                };
+                out.push_str(&format!(
+                    "<span class=\"{class}\">{}</span>",
+                    escape_html(&line.text)
+                ));
+            }
+            out.push_str("</pre>\n");
+        }
+        ValidatedComponent::Button(button) => {
+            out.push_str(&format!(
+                "<p><button type=\"button\" disabled>{}</button></p>\n",
+                escape_html(&button.label)
+            ));
+        }
+        ValidatedComponent::Table(table) => {
+            out.push_str("<table>\n<thead><tr>");
+            for column in &table.columns {
+                out.push_str(&format!("<th>{}</th>", escape_html(column)));
+            }
+            out.push_str("</tr></thead>\n<tbody>\n");
+            for row in &table.rows {
+                out.push_str("<tr>");
+                for cell in row {
+                    out.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str("</tbody>\n</table>\n");
+        }
+        ValidatedComponent::Tree(tree) => tree_html(out, &tree.nodes),
+        ValidatedComponent::Progress(progress) => {
+            let label = progress.label.as_deref().unwrap_or("Progress");
+            out.push_str(&format!(
+                "<p><label>{} <progress value=\"{:.2}\" max=\"1\"></progress> {:.0}%</label></p>\n",
+                escape_html(label),
+                progress.value,
+                progress.value * 100.0
+            ));
+        }
+        ValidatedComponent::Tabs(tabs) => {
+            for (index, child) in tabs.children.iter().enumerate() {
+                if !child.is_visible(form_state) {
+                    continue;
+                }
+                let title = tabs.titles.get(index).map(String::as_str).unwrap_or("Tab");
+                out.push_str(&format!("<h3>{}</h3>\n", escape_html(title)));
+                component_html(out, child, form_state);
+            }
+            return;
+        }
+        ValidatedComponent::Alert(alert) => {
+            let class = match &alert.severity {
+                AlertSeverity::Info | AlertSeverity::Unknown(_) => "info",
+                AlertSeverity::Warning => "warning",
+                AlertSeverity::Danger => "danger",
+                AlertSeverity::Success => "success",
+            };
+            out.push_str(&format!(
+                "<div class=\"alert alert-{class}\">{}</div>\n",
+                escape_html(&alert.text)
+            ));
+        }
+        ValidatedComponent::Divider(_) => out.push_str("<hr>\n"),
+        ValidatedComponent::Spacer(spacer) => {
+            out.push_str(&format!(
+                "<div style=\"height:{}px\"></div>\n",
+                spacer.size.unwrap_or(16.0)
+            ));
+        }
+        ValidatedComponent::KeyValue(key_value) => {
+            out.push_str("<dl>\n");
+            for (key, value) in &key_value.pairs {
+                out.push_str(&format!(
+                    "<dt>{}</dt><dd>{}</dd>\n",
+                    escape_html(key),
+                    escape_html(value)
+                ));
+            }
+            out.push_str("</dl>\n");
+        }
+        ValidatedComponent::Chart(chart) => {
+            if let Some(title) = &chart.title {
+                out.push_str(&format!("<h3>{}</h3>\n", escape_html(title)));
+            }
+            let max = chart.max_value();
+            out.push_str("<table class=\"chart\">\n");
+            for point in &chart.series {
+                let width = if max > 0.0 {
+                    point.value / max * 100.0
+                } else {
+                    0.0
+                };
+                out.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td style=\"width:60%\"><div class=\"bar\" style=\"width:{width:.1}%\"></div></td></tr>\n",
+                    escape_html(&point.label),
+                    point.value
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        ValidatedComponent::Row(row) => {
+            out.push_str("<div class=\"row\">\n");
+            for child in &row.children {
+                out.push_str("<div>\n");
+                component_html(out, child, form_state);
+                out.push_str("</div>\n");
+            }
+            out.push_str("</div>\n");
+            return;
+        }
+    }
+    for child in component.children() {
+        component_html(out, child, form_state);
+    }
+}
+
+fn field_html(field: &ValidatedFormField, value: &UiFieldValue) -> String {
+    match (field, value) {
+        (ValidatedFormField::TextArea(area), _) => format!(
+            "<textarea rows=\"{}\" readonly>{}</textarea>",
+            area.rows,
+            escape_html(&value.display_value())
+        ),
+        (_, UiFieldValue::Date { value }) => format!(
+            "<input type=\"date\" value=\"{}\" readonly>",
+            escape_html(value)
+        ),
+        (_, UiFieldValue::Checkbox { value }) => format!(
+            "<input type=\"checkbox\" disabled{}>",
+            if *value { " checked" } else { "" }
+        ),
+        _ => format!(
+            "<input type=\"text\" value=\"{}\" readonly>",
+            escape_html(&value.display_value())
+        ),
+    }
+}
+
+fn tree_html(out: &mut String, nodes: &[TreeNode]) {
+    if nodes.is_empty() {
+        return;
+    }
+    out.push_str("<ul>\n");
+    for node in nodes {
+        let name = escape_html(&node.name);
+        out.push_str(&format!(
+            "<li>{}",
+            if node.is_dir {
+                format!("{name}/")
+            } else {
+                name
+            }
+        ));
+        tree_html(out, &node.children);
+        out.push_str("</li>\n");
+    }
+    out.push_str("</ul>\n");
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// The markdown subset templates use: headings, bullet lists, fenced code, and
+/// paragraphs, with `code` and **bold** inline.
+fn markdown_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_list = false;
+    let mut fence: Option<Vec<&str>> = None;
+
+    let flush_paragraph = |out: &mut String, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            out.push_str(&format!("<p>{}</p>\n", inline_html(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some(code) = fence.as_mut() {
+            if trimmed.starts_with("```") {
+                out.push_str(&format!(
+                    "<pre><code>{}</code></pre>\n",
+                    escape_html(&code.join("\n"))
+                ));
+                fence = None;
+            } else {
+                code.push(line);
+            }
+            continue;
+        }
+
+        let item = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "));
+        if in_list && item.is_none() {
+            out.push_str("</ul>\n");
+            in_list = false;
+        }
+
+        if trimmed.starts_with("```") {
+            flush_paragraph(&mut out, &mut paragraph);
+            fence = Some(Vec::new());
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut out, &mut paragraph);
+        } else if let Some(item) = item {
+            flush_paragraph(&mut out, &mut paragraph);
+            if !in_list {
+                out.push_str("<ul>\n");
+                in_list = true;
+            }
+            out.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+        } else if let Some((level, heading)) = heading(trimmed) {
+            flush_paragraph(&mut out, &mut paragraph);
+            out.push_str(&format!("<h{level}>{}</h{level}>\n", inline_html(heading)));
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+
+    if let Some(code) = fence {
+        out.push_str(&format!(
+            "<pre><code>{}</code></pre>\n",
+            escape_html(&code.join("\n"))
+        ));
+    }
+    if in_list {
+        out.push_str("</ul>\n");
+    }
+    flush_paragraph(&mut out, &mut paragraph);
+    out
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|ch| *ch == '#').count();
+    let rest = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, rest.trim()))
+}
+
+fn inline_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut bold = false;
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                out.push_str(&format!("<code>{}</code>", escape_html(&after[..end])));
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            out.push_str(if bold { "</strong>" } else { "<strong>" });
+            bold = !bold;
+            rest = after;
+            continue;
+        }
+        let next = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, ch)| matches!(ch, '`' | '*'))
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+        out.push_str(&escape_html(&rest[..next]));
+        rest = &rest[next..];
+    }
+    if bold {
+        out.push_str("</strong>");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canvas_html, markdown_html, ExportBlock};
+    use crate::ui::runtime::UiRuntime;
+    use serde_json::json;
+
+    #[test]
+    fn exported_html_contains_block_title_and_diff_lines() {
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&json!({
+                "schema_version": 1,
+                "outputs": [],
+                "components": [
+                    { "id": "intro", "kind": "markdown", "text": "### Review\nCheck the **new** path." },
+                    {
+                        "id": "changes",
+                        "kind": "diff",
+                        "lines": [
+                            { "kind": "removed", "text": "- let x = 1;" },
+                            { "kind": "added", "text": "+ let x = a < b;" }
+                        ]
+                    }
+                ]
+            }))
+            .expect("schema should load");
+        let schema = runtime.validated_schema().expect("validated schema");
+        let form_state = runtime.form_state_snapshot();
+
+        let html = canvas_html(&[ExportBlock {
+            title: "Release <review>",
+            template_id: "builtin.code_review.default",
+            schema,
+            form_state: &form_state,
+        }]);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2>Release &lt;review&gt;</h2>"));
+        assert!(html.contains("<span class=\"added\">+ let x = a &lt; b;</span>"));
+        assert!(html.contains("<span class=\"removed\">- let x = 1;</span>"));
+        assert!(html.contains("<h3>Review</h3>"));
+        assert!(html.contains("Check the <strong>new</strong> path."));
+    }
+
+    #[test]
+    fn markdown_subset_renders_lists_and_code() {
+        let html = markdown_html("Intro line\n\n- one `a<b`\n- two\n\n```\nfn main() {}\n```");
+        assert_eq!(
+            html,
+            "<p>Intro line</p>\n<ul>\n<li>one <code>a&lt;b</code></li>\n<li>two</li>\n</ul>\n<pre><code>fn main() {}</code></pre>\n"
+        );
+    }
+}
@@ -1,122 +1,551 @@
 use crate::ui::catalog::UiIntent;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-pub fn intent_from_text(text: &str) -> Option<UiIntent> {
-    let lowered = text.to_ascii_lowercase();
-    let tokens = token_set(&lowered);
-    let has = |term: &str| tokens.contains(term);
-    let has_any_phrase = |phrases: &[&str]| phrases.iter().any(|phrase| lowered.contains(phrase));
-
-    let mentions_files = has("file") || has("files");
-    let mentions_workspace = has("workspace");
-    let asks_file_visibility = has("show")
-        || has("list")
-        || has("display")
-        || has("browse")
-        || has("view")
-        || lowered.starts_with("what files");
-
-    let primary = if has_any_phrase(&[
-        "list files",
-        "listing of files",
-        "file tree",
-        "directory tree",
-        "show files",
-        "show me files",
-        "show the files",
-        "all the files",
-        "all files",
-        "workspace files",
-    ]) || (mentions_files && has("canvas"))
-        || (mentions_files && mentions_workspace && asks_file_visibility)
-    {
-        "file_listing".to_string()
-    } else if has("plan") || has("roadmap") || has("milestone") {
-        "plan_review".to_string()
-    } else if has("ui") && has("design") {
-        "ui_design_review".to_string()
-    } else if has("review")
-        || has("approve")
-        || has("reject")
-        || has("decline")
-        || has("spec")
-        || has("diff")
-        || has("patch")
-        || has("security")
-    {
-        "code_review".to_string()
-    } else {
-        return None;
-    };
+static INSTALLED_RULES: OnceLock<IntentRules> = OnceLock::new();
+
+/// Words that, placed right before a trigger in the same clause, cancel it.
+const NEGATORS: [&str; 10] = [
+    "don't", "dont", "don’t", "no", "not", "never", "without", "stop", "skip", "ignore",
+];
+/// Verbs that flip a negator right before them back to a request: "don't miss security
+/// issues", "don't skip the file tree".
+const NEGATIVE_VERBS: [&str; 5] = ["miss", "forget", "ignore", "overlook", "skip"];
+/// Words that start a new clause, so a negator before them no longer applies.
+const CLAUSE_WORDS: [&str; 5] = ["but", "and", "then", "instead", "just"];
+/// Words skipped when counting the negation window.
+const FILLER_WORDS: [&str; 10] = [
+    "a", "an", "the", "me", "us", "my", "our", "this", "that", "any",
+];
+/// How many non-filler words before a term a negator may sit.
+const NEGATION_WINDOW: usize = 3;
+
+/// Keyword rules that map free text to a `UiIntent`.
+///
+/// A term containing a space matches as a phrase anywhere in the lowercased text; any
+/// other term matches a whole token. `a|b` matches when either alternative does. An
+/// occurrence directly preceded by a negator ("don't show the files") does not count.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntentRules {
+    /// Candidate primaries, in priority order: the first one that matches wins.
+    pub primaries: Vec<PrimaryRule>,
+    pub operations: Vec<TermRule>,
+    pub tags: Vec<TermRule>,
+}
 
-    let mut operations = BTreeSet::new();
-    if has("approve") {
-        operations.insert("approve".to_string());
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrimaryRule {
+    pub primary: String,
+    /// Any matching phrase adds `match_weight` once.
+    #[serde(default)]
+    pub phrases: Vec<String>,
+    /// A combination whose terms all match adds `match_weight` once.
+    #[serde(default)]
+    pub combos: Vec<Vec<String>>,
+    #[serde(default)]
+    pub match_weight: f32,
+    /// Each matching keyword adds `keyword_weight`.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub keyword_weight: f32,
+    /// Operations used when no operation rule matched.
+    #[serde(default)]
+    pub default_operations: Vec<String>,
+    /// Tags always attached to this primary.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Adds `value` as an operation or tag when any of `terms` matches. A non-empty
+/// `primaries` limits the rule to those primaries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermRule {
+    pub value: String,
+    pub terms: Vec<String>,
+    #[serde(default)]
+    pub primaries: Vec<String>,
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn term_rule(value: &str, terms: &[&str], primaries: &[&str]) -> TermRule {
+    TermRule {
+        value: value.to_string(),
+        terms: strings(terms),
+        primaries: strings(primaries),
     }
-    if has("reject") || has("decline") {
-        operations.insert("reject".to_string());
+}
+
+impl Default for IntentRules {
+    fn default() -> Self {
+        let file_listing = PrimaryRule {
+            primary: "file_listing".to_string(),
+            phrases: strings(&[
+                "list files",
+                "listing of files",
+                "file tree",
+                "directory tree",
+                "show files",
+                "show me files",
+                "show the files",
+                "all the files",
+                "all files",
+                "workspace files",
+            ]),
+            combos: vec![
+                strings(&["file|files", "canvas"]),
+                strings(&[
+                    "file|files",
+                    "workspace",
+                    "show|list|display|browse|view|what files",
+                ]),
+            ],
+            match_weight: 3.0,
+            keywords: Vec::new(),
+            keyword_weight: 0.0,
+            default_operations: strings(&["list"]),
+            tags: strings(&["files"]),
+        };
+        let plan_review = PrimaryRule {
+            primary: "plan_review".to_string(),
+            phrases: Vec::new(),
+            combos: Vec::new(),
+            match_weight: 0.0,
+            keywords: strings(&["plan", "roadmap", "milestone"]),
+            keyword_weight: 2.0,
+            default_operations: Vec::new(),
+            tags: Vec::new(),
+        };
+        let ui_design_review = PrimaryRule {
+            primary: "ui_design_review".to_string(),
+            phrases: Vec::new(),
+            combos: vec![strings(&["ui", "design"])],
+            match_weight: 2.5,
+            keywords: Vec::new(),
+            keyword_weight: 0.0,
+            default_operations: Vec::new(),
+            tags: Vec::new(),
+        };
+        let code_review = PrimaryRule {
+            primary: "code_review".to_string(),
+            phrases: Vec::new(),
+            combos: Vec::new(),
+            match_weight: 0.0,
+            keywords: strings(&[
+                "review", "approve", "reject", "decline", "spec", "diff", "patch", "security",
+            ]),
+            keyword_weight: 1.0,
+            default_operations: strings(&["review"]),
+            tags: Vec::new(),
+        };
+
+        let compare = PrimaryRule {
+            primary: "compare".to_string(),
+            phrases: strings(&["side by side", "side-by-side", "diff * and"]),
+            combos: vec![strings(&["difference|differences", "between"])],
+            match_weight: 2.0,
+            keywords: strings(&["compare", "comparison", "versus", "vs"]),
+            keyword_weight: 2.0,
+            default_operations: strings(&["compare"]),
+            tags: strings(&["compare"]),
+        };
+        let dashboard = PrimaryRule {
+            primary: "dashboard".to_string(),
+            phrases: strings(&[
+                "summary of the project",
+                "project summary",
+                "project status",
+                "project overview",
+            ]),
+            combos: Vec::new(),
+            match_weight: 3.0,
+            keywords: strings(&["status", "overview", "dashboard"]),
+            keyword_weight: 1.5,
+            default_operations: strings(&["view"]),
+            tags: strings(&["status"]),
+        };
+
+        Self {
+            primaries: vec![
+                file_listing,
+                plan_review,
+                ui_design_review,
+                compare,
+                code_review,
+                dashboard,
+            ],
+            operations: vec![
+                term_rule("approve", &["approve"], &[]),
+                term_rule("reject", &["reject", "decline"], &[]),
+                term_rule("revise", &["revise", "change"], &[]),
+                term_rule("browse", &["browse"], &["file_listing"]),
+                term_rule("view", &["view"], &["file_listing"]),
+                term_rule("list", &["show", "list", "display"], &["file_listing"]),
+            ],
+            tags: vec![
+                term_rule("spec", &["spec"], &[]),
+                term_rule("diff", &["diff", "patch"], &[]),
+                term_rule("security", &["security"], &[]),
+                term_rule("plan", &["plan", "roadmap"], &[]),
+                term_rule("workspace", &["workspace"], &["file_listing"]),
+                term_rule("tree", &["tree", "directory"], &["file_listing"]),
+            ],
+        }
     }
-    if has("revise") || has("change") {
-        operations.insert("revise".to_string());
+}
+
+pub fn rules_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".brownie")
+        .join("intents.json")
+}
+
+/// Rules stored at `path`. A missing file yields the built-in rules silently; an
+/// unreadable or invalid one yields them with a warning to surface in diagnostics.
+pub fn load_rules(path: &Path) -> (IntentRules, Option<String>) {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return (IntentRules::default(), None)
+        }
+        Err(err) => {
+            return (
+                IntentRules::default(),
+                Some(format!(
+                    "failed to read intent rules {}: {err}; using built-in rules",
+                    path.display()
+                )),
+            )
+        }
+    };
+    match serde_json::from_slice::<IntentRules>(&data) {
+        Ok(rules) => (rules, None),
+        Err(err) => (
+            IntentRules::default(),
+            Some(format!(
+                "invalid intent rules {}: {err}; using built-in rules",
+                path.display()
+            )),
+        ),
     }
-    if primary == "file_listing" {
-        if has("browse") {
-            operations.insert("browse".to_string());
+}
+
+/// Why a primary was picked: the rule that fired and the terms behind each part.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntentTrace {
+    /// Phrase, or combo terms joined with " + ", that matched the primary.
+    pub matched_rule: Option<String>,
+    /// Keywords that added to the primary's score.
+    pub keywords: Vec<String>,
+    /// `(operation, term)` pairs; empty when the primary's default operations applied.
+    pub operations: Vec<(String, String)>,
+    /// `(tag, term)` pairs from tag rules; the primary's own tags are not listed.
+    pub tags: Vec<(String, String)>,
+}
+
+impl IntentTrace {
+    /// One line per contributing part, for diagnostics.
+    pub fn lines(&self) -> Vec<String> {
+        let pairs = |pairs: &[(String, String)]| {
+            pairs
+                .iter()
+                .map(|(value, term)| format!("{value} <- \"{term}\""))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut lines = Vec::new();
+        if let Some(rule) = &self.matched_rule {
+            lines.push(format!("matched: \"{rule}\""));
         }
-        if has("view") {
-            operations.insert("view".to_string());
+        if !self.keywords.is_empty() {
+            lines.push(format!("keywords: {}", self.keywords.join(", ")));
         }
-        if has("show") || has("list") || has("display") {
-            operations.insert("list".to_string());
+        if self.operations.is_empty() {
+            lines.push("operations: primary defaults".to_string());
+        } else {
+            lines.push(format!("operations: {}", pairs(&self.operations)));
         }
-    }
-    if operations.is_empty() {
-        if primary == "file_listing" {
-            operations.insert("list".to_string());
-        } else if primary == "code_review" {
-            operations.insert("review".to_string());
+        if !self.tags.is_empty() {
+            lines.push(format!("tags: {}", pairs(&self.tags)));
         }
+        lines
     }
+}
 
-    let mut tags = BTreeSet::new();
-    if has("spec") {
-        tags.insert("spec".to_string());
-    }
-    if has("diff") || has("patch") {
-        tags.insert("diff".to_string());
-    }
-    if has("security") {
-        tags.insert("security".to_string());
+/// A detected intent with its confidence and the trace explaining it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntentMatch {
+    pub intent: UiIntent,
+    pub confidence: f32,
+    pub trace: IntentTrace,
+}
+
+/// Makes `rules` the ones `intent_from_text` consults. Only the first call takes effect;
+/// without one the built-in rules apply.
+pub fn install_rules(rules: IntentRules) {
+    let _ = INSTALLED_RULES.set(rules);
+}
+
+fn installed_rules() -> &'static IntentRules {
+    INSTALLED_RULES.get_or_init(IntentRules::default)
+}
+
+/// Best intent for `text` under the installed rules, if any primary scored at all.
+/// `explain_intent` returns the same intent with the trace of why it won.
+pub fn intent_from_text(text: &str) -> Option<UiIntent> {
+    intent_candidates_from_text(text)
+        .into_iter()
+        .next()
+        .map(|(intent, _)| intent)
+}
+
+/// `intent_candidates_with_rules` under the installed rules.
+pub fn intent_candidates_from_text(text: &str) -> Vec<(UiIntent, f32)> {
+    intent_candidates_with_rules(installed_rules(), text)
+}
+
+/// Every primary the text hints at, best first, with its share of the total keyword
+/// weight as confidence (0.0..=1.0). The best is the first matching primary in rule
+/// order; the alternatives follow by score. A prompt matching a single primary scores 1.0.
+pub fn intent_candidates_with_rules(rules: &IntentRules, text: &str) -> Vec<(UiIntent, f32)> {
+    intent_matches_with_rules(rules, text)
+        .into_iter()
+        .map(|candidate| (candidate.intent, candidate.confidence))
+        .collect()
+}
+
+/// Best intent for `text` under the installed rules, with the trace of why it won.
+pub fn explain_intent(text: &str) -> Option<IntentMatch> {
+    intent_matches_from_text(text).into_iter().next()
+}
+
+/// `intent_matches_with_rules` under the installed rules.
+pub fn intent_matches_from_text(text: &str) -> Vec<IntentMatch> {
+    intent_matches_with_rules(installed_rules(), text)
+}
+
+/// Like `intent_candidates_with_rules`, but each candidate carries its `IntentTrace`.
+pub fn intent_matches_with_rules(rules: &IntentRules, text: &str) -> Vec<IntentMatch> {
+    let lowered = text.to_ascii_lowercase();
+    let tokens = token_spans(&lowered);
+    let matched = |term: &str| matched_alternative(term, &lowered, &tokens);
+
+    let scored = rules
+        .primaries
+        .iter()
+        .map(|rule| {
+            let matched_rule = rule
+                .phrases
+                .iter()
+                .find_map(|phrase| matched(phrase))
+                .or_else(|| {
+                    rule.combos.iter().find_map(|combo| {
+                        let parts = combo
+                            .iter()
+                            .map(|term| matched(term))
+                            .collect::<Option<Vec<_>>>()?;
+                        (!parts.is_empty()).then(|| parts.join(" + "))
+                    })
+                });
+            let keywords = rule
+                .keywords
+                .iter()
+                .filter_map(|term| matched(term))
+                .collect::<Vec<_>>();
+            let mut score = keywords.len() as f32 * rule.keyword_weight;
+            if matched_rule.is_some() {
+                score += rule.match_weight;
+            }
+            let trace = IntentTrace {
+                matched_rule,
+                keywords,
+                ..IntentTrace::default()
+            };
+            (rule, score, trace)
+        })
+        .collect::<Vec<_>>();
+    let total: f32 = scored.iter().map(|(_, score, _)| score).sum();
+    if total <= 0.0 {
+        return Vec::new();
     }
-    if has("plan") || has("roadmap") {
-        tags.insert("plan".to_string());
+
+    let mut ranked = scored
+        .into_iter()
+        .filter(|(_, score, _)| *score > 0.0)
+        .collect::<Vec<_>>();
+    // Scores rank only the alternatives, so a prompt mentioning several primaries
+    // still resolves by rule priority. Stable sort keeps rule order for equal scores.
+    ranked[1..].sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranked
+        .into_iter()
+        .map(|(rule, score, trace)| build_intent(rules, rule, &matched, trace, score / total))
+        .collect()
+}
+
+fn build_intent(
+    rules: &IntentRules,
+    primary: &PrimaryRule,
+    matched: &impl Fn(&str) -> Option<String>,
+    mut trace: IntentTrace,
+    confidence: f32,
+) -> IntentMatch {
+    let contributions = |term_rules: &[TermRule]| {
+        term_rules
+            .iter()
+            .filter(|rule| rule.primaries.is_empty() || rule.primaries.contains(&primary.primary))
+            .filter_map(|rule| {
+                let term = rule.terms.iter().find_map(|term| matched(term))?;
+                Some((rule.value.clone(), term))
+            })
+            .collect::<Vec<_>>()
+    };
+    trace.operations = contributions(&rules.operations);
+    trace.tags = contributions(&rules.tags);
+
+    let mut operations = trace
+        .operations
+        .iter()
+        .map(|(value, _)| value.clone())
+        .collect::<BTreeSet<_>>();
+    if operations.is_empty() {
+        operations.extend(primary.default_operations.iter().cloned());
     }
-    if primary == "file_listing" {
-        tags.insert("files".to_string());
-        if mentions_workspace {
-            tags.insert("workspace".to_string());
-        }
-        if has("tree") || has("directory") {
-            tags.insert("tree".to_string());
-        }
+
+    let mut tags = primary.tags.iter().cloned().collect::<BTreeSet<_>>();
+    tags.extend(trace.tags.iter().map(|(value, _)| value.clone()));
+
+    IntentMatch {
+        intent: UiIntent::new(
+            primary.primary.clone(),
+            operations.into_iter().collect(),
+            tags.into_iter().collect(),
+        ),
+        confidence,
+        trace,
     }
+}
 
-    Some(UiIntent::new(
-        primary,
-        operations.into_iter().collect(),
-        tags.into_iter().collect(),
-    ))
+/// The alternative of `term` that occurs un-negated in the text, if any.
+fn matched_alternative(term: &str, lowered: &str, tokens: &[(usize, &str)]) -> Option<String> {
+    term.split('|').find_map(|alternative| {
+        let alternative = alternative.trim().to_ascii_lowercase();
+        let found = if alternative.is_empty() {
+            false
+        } else if alternative.split_whitespace().any(|word| word == "*") {
+            wildcard_match_starts(&alternative, lowered)
+                .into_iter()
+                .any(|start| !negated_at(lowered, start))
+        } else if alternative.contains(' ') {
+            lowered
+                .match_indices(alternative.as_str())
+                .any(|(start, _)| !negated_at(lowered, start))
+        } else {
+            tokens
+                .iter()
+                .any(|(start, token)| *token == alternative && !negated_at(lowered, *start))
+        };
+        found.then_some(alternative)
+    })
 }
 
-fn token_set(text: &str) -> BTreeSet<&str> {
-    text.split(|ch: char| !ch.is_ascii_alphanumeric())
-        .filter(|token| !token.is_empty())
+/// Byte offsets where `pattern` matches word by word; each `*` stands for exactly
+/// one word, so "diff * and" matches "diff a.txt and b.txt" but not "diff and tell".
+fn wildcard_match_starts(pattern: &str, lowered: &str) -> Vec<usize> {
+    let pattern = pattern.split_whitespace().collect::<Vec<_>>();
+    let words = lowered
+        .split_whitespace()
+        .map(|word| {
+            let start = word.as_ptr() as usize - lowered.as_ptr() as usize;
+            (
+                start,
+                word.trim_matches(|ch: char| !ch.is_ascii_alphanumeric()),
+            )
+        })
+        .collect::<Vec<_>>();
+    words
+        .windows(pattern.len())
+        .filter(|window| {
+            window
+                .iter()
+                .zip(&pattern)
+                .all(|((_, word), expected)| *expected == "*" || word == expected)
+        })
+        .map(|window| window[0].0)
         .collect()
 }
 
+/// Whether a negator governs the text at byte `start`: one of the last few
+/// non-filler words of the same clause, so "don't show me the workspace files" and
+/// "skip the file tree" are negated. A negative verb directly after a negator cancels
+/// it, so "don't miss security issues" keeps "security".
+fn negated_at(lowered: &str, start: usize) -> bool {
+    let clause = lowered[..start]
+        .rsplit([',', '.', ';', ':', '!', '?'])
+        .next()
+        .unwrap_or("");
+    let words = clause
+        .split_whitespace()
+        .rev()
+        .map(|word| {
+            word.trim_matches(|ch: char| !ch.is_ascii_alphanumeric() && ch != '\'' && ch != '’')
+        })
+        .filter(|word| !word.is_empty() && !FILLER_WORDS.contains(word))
+        .take_while(|word| !CLAUSE_WORDS.contains(word))
+        .collect::<Vec<_>>();
+    let Some(index) = words
+        .iter()
+        .take(NEGATION_WINDOW)
+        .position(|word| NEGATORS.contains(word) || NEGATIVE_VERBS.contains(word))
+    else {
+        return false;
+    };
+    let word = words[index];
+    if NEGATIVE_VERBS.contains(&word)
+        && words
+            .get(index + 1)
+            .is_some_and(|previous| NEGATORS.contains(previous))
+    {
+        return false;
+    }
+    NEGATORS.contains(&word)
+}
+
+/// Alphanumeric tokens of `text` with their byte offsets.
+fn token_spans(text: &str) -> Vec<(usize, &str)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (index, ch) in text.char_indices() {
+        match (ch.is_ascii_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(begin)) => {
+                spans.push((begin, &text[begin..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(begin) = start {
+        spans.push((begin, &text[begin..]));
+    }
+    spans
+}
+
 #[cfg(test)]
 mod tests {
-    use super::intent_from_text;
+    use super::{
+        intent_candidates_from_text, intent_candidates_with_rules, intent_from_text,
+        intent_matches_with_rules, load_rules, IntentRules,
+    };
+    use std::fs;
 
     #[test]
     fn detects_workspace_file_request_with_articles() {
@@ -144,6 +573,180 @@ mod tests {
         assert!(intent.tags.contains(&"security".to_string()));
     }
 
+    #[test]
+    fn ambiguous_prompt_ranks_several_candidates() {
+        let candidates = intent_candidates_from_text("review the plan diff");
+        let primaries = candidates
+            .iter()
+            .map(|(intent, _)| intent.primary.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(primaries, vec!["plan_review", "code_review"]);
+        assert!(candidates[0].1 >= candidates[1].1);
+        assert!(candidates[0].1 < 1.0);
+        let total: f32 = candidates.iter().map(|(_, confidence)| confidence).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mixed_prompts_resolve_by_rule_priority() {
+        for (prompt, primary) in [
+            ("review and approve this roadmap patch", "plan_review"),
+            ("review the ui design for security", "ui_design_review"),
+            ("plan the ui design", "plan_review"),
+            ("review and approve the ui design patch", "ui_design_review"),
+        ] {
+            let candidates = intent_candidates_from_text(prompt);
+            assert_eq!(candidates[0].0.primary, primary, "prompt: {prompt}");
+            assert_eq!(candidates.len(), 2, "prompt: {prompt}");
+        }
+    }
+
+    #[test]
+    fn clear_prompt_yields_single_confident_candidate() {
+        let candidates = intent_candidates_from_text("Show the files in the workspace");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].0.primary, "file_listing");
+        assert!((candidates[0].1 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn custom_rule_file_detects_new_primary() {
+        let text = "open the incident timeline";
+        assert!(intent_candidates_with_rules(&IntentRules::default(), text).is_empty());
+
+        let path =
+            std::env::temp_dir().join(format!("brownie_intents_{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"{
+                "primaries": [
+                    {
+                        "primary": "incident_timeline",
+                        "phrases": ["incident timeline"],
+                        "match_weight": 3.0,
+                        "default_operations": ["view"],
+                        "tags": ["incident"]
+                    }
+                ]
+            }"#,
+        )
+        .expect("rules fixture should write");
+        let (rules, warning) = load_rules(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(warning, None);
+
+        let candidates = intent_candidates_with_rules(&rules, text);
+        assert_eq!(candidates.len(), 1);
+        let intent = &candidates[0].0;
+        assert_eq!(intent.primary, "incident_timeline");
+        assert_eq!(intent.operations, vec!["view".to_string()]);
+        assert_eq!(intent.tags, vec!["incident".to_string()]);
+    }
+
+    #[test]
+    fn invalid_rule_file_falls_back_with_warning() {
+        let path = std::env::temp_dir().join(format!(
+            "brownie_intents_invalid_{}.json",
+            std::process::id()
+        ));
+        fs::write(&path, "{ not json").expect("rules fixture should write");
+        let (rules, warning) = load_rules(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(rules, IntentRules::default());
+        assert!(warning.is_some_and(|warning| warning.contains("using built-in rules")));
+    }
+
+    #[test]
+    fn detects_compare_triggers() {
+        for prompt in [
+            "compare main and the release branch",
+            "diff config.old and config.new",
+            "put both versions side by side",
+        ] {
+            let intent = intent_from_text(prompt).expect("compare intent should be detected");
+            assert_eq!(intent.primary, "compare", "prompt: {prompt}");
+            assert!(intent.operations.contains(&"compare".to_string()));
+        }
+
+        let intent = intent_from_text("review this diff and approve it")
+            .expect("review intent should be detected");
+        assert_eq!(intent.primary, "code_review");
+
+        let intent = intent_from_text("check this diff and tell me if it's safe")
+            .expect("review intent should be detected");
+        assert_eq!(intent.primary, "code_review");
+    }
+
+    #[test]
+    fn detects_dashboard_triggers() {
+        for prompt in [
+            "what's the status?",
+            "show me an overview",
+            "give me a summary of the project",
+        ] {
+            let intent = intent_from_text(prompt).expect("dashboard intent should be detected");
+            assert_eq!(intent.primary, "dashboard", "prompt: {prompt}");
+            assert!(intent.tags.contains(&"status".to_string()));
+        }
+    }
+
+    #[test]
+    fn trace_records_firing_phrase_for_file_listing() {
+        let rules = IntentRules::default();
+        let best = intent_matches_with_rules(&rules, "Show the files in the workspace")
+            .into_iter()
+            .next()
+            .expect("file listing should match");
+        assert_eq!(best.intent.primary, "file_listing");
+        assert_eq!(best.trace.matched_rule.as_deref(), Some("show the files"));
+        assert_eq!(
+            best.trace.operations,
+            vec![("list".to_string(), "show".to_string())]
+        );
+        assert!(best
+            .trace
+            .lines()
+            .contains(&"matched: \"show the files\"".to_string()));
+
+        let combo = intent_matches_with_rules(&rules, "list the files across the workspace")
+            .into_iter()
+            .next()
+            .expect("combo should match");
+        assert_eq!(
+            combo.trace.matched_rule.as_deref(),
+            Some("files + workspace + list")
+        );
+    }
+
+    #[test]
+    fn negated_file_request_is_suppressed() {
+        assert!(intent_from_text("Don't show the files, just summarize").is_none());
+        assert!(intent_from_text("please do not list files in the workspace").is_none());
+        assert!(intent_from_text("don't show me the workspace files").is_none());
+        assert!(intent_from_text("skip the file tree").is_none());
+        assert!(intent_from_text("ignore the workspace files").is_none());
+    }
+
+    #[test]
+    fn negation_elsewhere_does_not_suppress() {
+        let intent = intent_from_text("Show the files, don't summarize")
+            .expect("file request should still be detected");
+        assert_eq!(intent.primary, "file_listing");
+
+        let intent = intent_from_text("review this, don't miss security issues")
+            .expect("review request should still be detected");
+        assert_eq!(intent.primary, "code_review");
+        assert!(intent.tags.contains(&"security".to_string()));
+
+        let intent = intent_from_text("don't skip the file tree")
+            .expect("a negated negative verb should still request the tree");
+        assert_eq!(intent.primary, "file_listing");
+
+        let intent = intent_from_text("don't summarize, but show me the workspace files")
+            .expect("file request after the negated clause should be detected");
+        assert_eq!(intent.primary, "file_listing");
+    }
+
     #[test]
     fn returns_none_for_non_ui_prompt() {
         assert!(intent_from_text("hello there").is_none());
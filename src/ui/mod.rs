@@ -1,5 +1,6 @@
 pub mod catalog;
 pub mod event;
+pub mod export;
 pub mod intent;
 pub mod registry;
 pub mod runtime;
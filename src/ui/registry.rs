@@ -1,22 +1,110 @@
+use crate::session::format_timestamp;
 use crate::theme::Theme;
 use crate::ui::event::{UiEvent, UiFieldValue};
 use crate::ui::schema::{
-    field_key, ButtonStyle, ComponentKind, DiffLineKind, FormFieldKind, SchemaRegistry,
-    ValidatedComponent, ValidatedFormField,
+    confirm_state_key, field_key, is_iso_date, tabs_state_key, AlertSeverity, ButtonComponent,
+    ButtonStyle, ComponentKind, DiffComponent, DiffLineKind, FormFieldKind, SchemaRegistry,
+    TabsComponent, TreeNode, ValidatedComponent, ValidatedFormField,
 };
 use eframe::egui::{self, RichText};
+use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+use egui_extras::syntax_highlighting::{self, CodeTheme};
+use egui_extras::{Column, TableBuilder};
+use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet};
 
+pub const MAX_DISPLAY_LINE_CHARS: usize = 2_000;
+const CODE_FONT_SIZE: f32 = 13.0;
+const CHART_LABEL_WIDTH: f32 = 120.0;
+const CHART_VALUE_WIDTH: f32 = 56.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncatedText {
+    pub full: String,
+    pub display: String,
+    pub truncated: bool,
+}
+
+pub fn truncate_long_lines(text: &str, max_chars: usize) -> TruncatedText {
+    let mut truncated = false;
+    let display = text
+        .split('\n')
+        .map(|line| {
+            if line.chars().count() <= max_chars {
+                line.to_string()
+            } else {
+                truncated = true;
+                let head = line.chars().take(max_chars).collect::<String>();
+                format!("{head}… (truncated)")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    TruncatedText {
+        full: text.to_string(),
+        display,
+        truncated,
+    }
+}
+
+/// Maps a code component `language` onto one the built-in highlighter understands.
+fn highlight_language(language: Option<&str>) -> Option<&'static str> {
+    match language?.trim().to_ascii_lowercase().as_str() {
+        "rust" | "rs" => Some("rs"),
+        "python" | "py" => Some("py"),
+        "c" | "h" => Some("c"),
+        "cpp" | "c++" | "hpp" => Some("cpp"),
+        "toml" => Some("toml"),
+        _ => None,
+    }
+}
+
+fn code_theme(theme: &Theme) -> CodeTheme {
+    if egui::Rgba::from(theme.surface_0).intensity() < 0.5 {
+        CodeTheme::dark(theme.text_size(CODE_FONT_SIZE))
+    } else {
+        CodeTheme::light(theme.text_size(CODE_FONT_SIZE))
+    }
+}
+
 pub struct ComponentRegistry {
     allowed_components: BTreeSet<&'static str>,
     allowed_field_kinds: BTreeSet<&'static str>,
+    // Parsed markdown/layout cache shared across frames; rendering only borrows `&self`.
+    markdown_cache: RefCell<CommonMarkCache>,
 }
 
 impl ComponentRegistry {
     pub fn new() -> Self {
         Self {
-            allowed_components: BTreeSet::from(["markdown", "form", "code", "diff", "button"]),
-            allowed_field_kinds: BTreeSet::from(["text", "number", "select", "checkbox"]),
+            allowed_components: BTreeSet::from([
+                "markdown",
+                "form",
+                "code",
+                "diff",
+                "button",
+                "table",
+                "tree",
+                "progress",
+                "tabs",
+                "alert",
+                "divider",
+                "spacer",
+                "key_value",
+                "chart",
+                "row",
+            ]),
+            allowed_field_kinds: BTreeSet::from([
+                "text",
+                "textarea",
+                "number",
+                "select",
+                "multiselect",
+                "checkbox",
+                "date",
+            ]),
+            markdown_cache: RefCell::new(CommonMarkCache::default()),
         }
     }
 
@@ -26,6 +114,7 @@ impl ComponentRegistry {
         ui: &mut egui::Ui,
         theme: &Theme,
         form_state: &mut BTreeMap<String, UiFieldValue>,
+        validation_errors: &BTreeMap<String, String>,
         emit: &mut dyn FnMut(UiEvent),
     ) {
         match component {
@@ -35,40 +124,50 @@ impl ComponentRegistry {
                     ui.label(
                         RichText::new(format!("id: {}", markdown.id))
                             .color(theme.text_muted)
-                            .size(12.0),
+                            .size(theme.text_size(12.0)),
                     );
                     ui.add_space(theme.spacing_4);
-                    ui.label(
-                        RichText::new(&markdown.text)
-                            .color(theme.text_primary)
-                            .size(14.0),
-                    );
+                    let text = truncate_long_lines(&markdown.text, MAX_DISPLAY_LINE_CHARS);
+                    ui.scope(|ui| {
+                        ui.visuals_mut().override_text_color = Some(theme.text_primary);
+                        CommonMarkViewer::new().show(
+                            ui,
+                            &mut self.markdown_cache.borrow_mut(),
+                            &text.display,
+                        );
+                    });
+                    render_copy_full_action(ui, theme, &text);
                 });
-                self.render_children(component, ui, theme, form_state, emit);
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
             }
             ValidatedComponent::Form(form) => {
                 let frame = theme.card_frame();
                 frame.show(ui, |ui| {
                     if let Some(title) = &form.title {
-                        ui.label(RichText::new(title).color(theme.text_primary).size(13.0));
+                        ui.label(
+                            RichText::new(title)
+                                .color(theme.text_primary)
+                                .size(theme.text_size(13.0)),
+                        );
                         ui.add_space(theme.spacing_8);
                     }
 
                     ui.vertical(|ui| {
                         ui.spacing_mut().item_spacing.y = theme.spacing_12;
                         for field in &form.fields {
-                            self.render_form_field(
+                            Self::render_form_field(
                                 form.id.as_str(),
                                 field,
                                 ui,
                                 theme,
                                 form_state,
+                                validation_errors,
                                 emit,
                             );
                         }
                     });
                 });
-                self.render_children(component, ui, theme, form_state, emit);
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
             }
             ValidatedComponent::Code(code) => {
                 let frame = theme.card_frame();
@@ -76,20 +175,52 @@ impl ComponentRegistry {
                     ui.label(
                         RichText::new(format!("id: {}", code.id))
                             .color(theme.text_muted)
-                            .size(12.0),
+                            .size(theme.text_size(12.0)),
                     );
                     ui.add_space(theme.spacing_4);
                     let language = code.language.as_deref().unwrap_or("code");
-                    ui.label(RichText::new(language).color(theme.text_muted).size(12.0));
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(language)
+                                .color(theme.text_muted)
+                                .size(theme.text_size(12.0)),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            render_copy_button(ui, theme, &code.code);
+                        });
+                    });
                     ui.add_space(theme.spacing_8);
-                    ui.label(
-                        RichText::new(code.code.as_str())
-                            .color(theme.text_primary)
-                            .size(13.0)
-                            .monospace(),
-                    );
+                    let text = truncate_long_lines(&code.code, MAX_DISPLAY_LINE_CHARS);
+                    egui::ScrollArea::horizontal()
+                        .id_salt(("code_scroll", code.id.as_str()))
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            match highlight_language(code.language.as_deref()) {
+                                Some(language) => {
+                                    let job = syntax_highlighting::highlight(
+                                        ui.ctx(),
+                                        ui.style(),
+                                        &code_theme(theme),
+                                        &text.display,
+                                        language,
+                                    );
+                                    ui.add(egui::Label::new(job).extend());
+                                }
+                                None => {
+                                    ui.add(
+                                        egui::Label::new(
+                                            RichText::new(&text.display)
+                                                .color(theme.text_primary)
+                                                .size(theme.text_size(CODE_FONT_SIZE))
+                                                .monospace(),
+                                        )
+                                        .extend(),
+                                    );
+                                }
+                            }
+                        });
                 });
-                self.render_children(component, ui, theme, form_state, emit);
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
             }
             ValidatedComponent::Diff(diff) => {
                 let frame = theme.card_frame();
@@ -97,9 +228,20 @@ impl ComponentRegistry {
                     ui.label(
                         RichText::new(format!("id: {}", diff.id))
                             .color(theme.text_muted)
-                            .size(12.0),
+                            .size(theme.text_size(12.0)),
                     );
                     ui.add_space(theme.spacing_4);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(format!("{} lines", diff.lines.len()))
+                                .color(theme.text_muted)
+                                .size(theme.text_size(12.0)),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            render_copy_button(ui, theme, &diff_plain_text(diff));
+                        });
+                    });
+                    ui.add_space(theme.spacing_4);
                     for line in &diff.lines {
                         let (fill, accent) = match line.kind {
                             DiffLineKind::Added => (theme.diff_added_tint, theme.success),
@@ -120,14 +262,331 @@ impl ComponentRegistry {
                                     ui.label(
                                         RichText::new(&line.text)
                                             .color(theme.text_primary)
-                                            .size(13.0)
+                                            .size(theme.text_size(13.0))
                                             .monospace(),
                                     );
                                 });
                             });
                     }
                 });
-                self.render_children(component, ui, theme, form_state, emit);
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Table(table) => {
+                let frame = theme.card_frame();
+                frame.show(ui, |ui| {
+                    ui.label(
+                        RichText::new(format!("id: {}", table.id))
+                            .color(theme.text_muted)
+                            .size(theme.text_size(12.0)),
+                    );
+                    ui.add_space(theme.spacing_4);
+                    ui.push_id(("table", table.id.as_str()), |ui| {
+                        TableBuilder::new(ui)
+                            .striped(true)
+                            .columns(Column::auto().resizable(true), table.columns.len())
+                            .header(20.0, |mut header| {
+                                for column in &table.columns {
+                                    header.col(|ui| {
+                                        ui.label(
+                                            RichText::new(column)
+                                                .color(theme.text_muted)
+                                                .size(theme.text_size(12.0))
+                                                .strong(),
+                                        );
+                                    });
+                                }
+                            })
+                            .body(|mut body| {
+                                for cells in &table.rows {
+                                    body.row(20.0, |mut row| {
+                                        for cell in cells {
+                                            row.col(|ui| {
+                                                ui.label(
+                                                    RichText::new(cell)
+                                                        .color(theme.text_primary)
+                                                        .size(theme.text_size(13.0)),
+                                                );
+                                            });
+                                        }
+                                    });
+                                }
+                            });
+                    });
+                });
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Tree(tree) => {
+                let frame = theme.card_frame();
+                frame.show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(format!("id: {}", tree.id))
+                                .color(theme.text_muted)
+                                .size(theme.text_size(12.0)),
+                        );
+                        if !tree.nodes.is_empty() {
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    render_copy_button(ui, theme, &tree_plain_text(&tree.nodes));
+                                },
+                            );
+                        }
+                    });
+                    ui.add_space(theme.spacing_4);
+                    if tree.nodes.is_empty() {
+                        ui.label(
+                            RichText::new("(empty)")
+                                .color(theme.text_muted)
+                                .size(theme.text_size(13.0)),
+                        );
+                    }
+                    Self::render_tree_nodes(
+                        &tree.id,
+                        &tree.nodes,
+                        0,
+                        tree.show_metadata,
+                        ui,
+                        theme,
+                        emit,
+                    );
+                });
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Progress(progress) => {
+                let frame = theme.card_frame();
+                frame.show(ui, |ui| {
+                    ui.label(
+                        RichText::new(format!("id: {}", progress.id))
+                            .color(theme.text_muted)
+                            .size(theme.text_size(12.0)),
+                    );
+                    ui.add_space(theme.spacing_4);
+                    if let Some(label) = &progress.label {
+                        ui.label(
+                            RichText::new(label)
+                                .color(theme.text_primary)
+                                .size(theme.text_size(13.0)),
+                        );
+                        ui.add_space(theme.spacing_4);
+                    }
+                    ui.add(
+                        egui::ProgressBar::new(progress.value)
+                            .fill(theme.accent_primary)
+                            .show_percentage(),
+                    );
+                });
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Tabs(tabs) => {
+                let Some(active) = active_tab_index(form_state, tabs) else {
+                    return;
+                };
+                let visible = tabs
+                    .children
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, child)| child.is_visible(form_state))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                ui.horizontal_wrapped(|ui| {
+                    for index in visible {
+                        let Some(title) = tabs.titles.get(index) else {
+                            continue;
+                        };
+                        let selected = index == active;
+                        let color = if selected {
+                            theme.text_primary
+                        } else {
+                            theme.text_muted
+                        };
+                        if ui
+                            .selectable_label(
+                                selected,
+                                RichText::new(title)
+                                    .color(color)
+                                    .size(theme.text_size(13.0)),
+                            )
+                            .clicked()
+                            && !selected
+                        {
+                            form_state.insert(
+                                tabs_state_key(&tabs.id),
+                                UiFieldValue::Number {
+                                    value: index as f64,
+                                },
+                            );
+                            emit(UiEvent::TabSelected {
+                                component_id: tabs.id.clone(),
+                                tab_index: index,
+                            });
+                        }
+                    }
+                });
+                if let Some(child) = tabs.children.get(active) {
+                    ui.add_space(theme.spacing_8);
+                    self.render_component(child, ui, theme, form_state, validation_errors, emit);
+                }
+            }
+            ValidatedComponent::Alert(alert) => {
+                let (accent, title) = match alert.severity {
+                    AlertSeverity::Warning => (theme.warning, "Warning"),
+                    AlertSeverity::Danger => (theme.danger, "Blocker"),
+                    AlertSeverity::Success => (theme.success, "Success"),
+                    AlertSeverity::Info | AlertSeverity::Unknown(_) => {
+                        (theme.accent_primary, "Note")
+                    }
+                };
+                egui::Frame::new()
+                    .fill(accent.gamma_multiply(0.18))
+                    .stroke(egui::Stroke::new(1.0, accent))
+                    .corner_radius(egui::CornerRadius::same(theme.radius_8))
+                    .inner_margin(egui::Margin::same(theme.spacing_12 as i8))
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                RichText::new(title)
+                                    .color(accent)
+                                    .size(theme.text_size(13.0))
+                                    .strong(),
+                            );
+                            ui.label(
+                                RichText::new(format!("id: {}", alert.id))
+                                    .color(theme.text_muted)
+                                    .size(theme.text_size(12.0)),
+                            );
+                        });
+                        ui.add_space(theme.spacing_4);
+                        ui.add(
+                            egui::Label::new(
+                                RichText::new(&alert.text)
+                                    .color(theme.text_primary)
+                                    .size(theme.text_size(13.0)),
+                            )
+                            .wrap(),
+                        );
+                    });
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Divider(_) => {
+                ui.separator();
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Spacer(spacer) => {
+                ui.add_space(spacer.size.unwrap_or(theme.spacing_16));
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::KeyValue(key_value) => {
+                let frame = theme.card_frame();
+                frame.show(ui, |ui| {
+                    ui.label(
+                        RichText::new(format!("id: {}", key_value.id))
+                            .color(theme.text_muted)
+                            .size(theme.text_size(12.0)),
+                    );
+                    ui.add_space(theme.spacing_4);
+                    egui::Grid::new(("key_value", key_value.id.as_str()))
+                        .num_columns(2)
+                        .spacing(egui::vec2(theme.spacing_16, theme.spacing_4))
+                        .show(ui, |ui| {
+                            for (key, value) in &key_value.pairs {
+                                ui.label(
+                                    RichText::new(key)
+                                        .color(theme.text_muted)
+                                        .size(theme.text_size(13.0)),
+                                );
+                                ui.label(
+                                    RichText::new(value)
+                                        .color(theme.text_primary)
+                                        .size(theme.text_size(13.0)),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Chart(chart) => {
+                let frame = theme.card_frame();
+                frame.show(ui, |ui| {
+                    ui.label(
+                        RichText::new(format!("id: {}", chart.id))
+                            .color(theme.text_muted)
+                            .size(theme.text_size(12.0)),
+                    );
+                    if let Some(title) = &chart.title {
+                        ui.label(
+                            RichText::new(title)
+                                .color(theme.text_primary)
+                                .size(theme.text_size(14.0)),
+                        );
+                    }
+                    ui.add_space(theme.spacing_4);
+                    let max = chart.max_value();
+                    let radius = egui::CornerRadius::same(theme.radius_8 / 2);
+                    for point in &chart.series {
+                        ui.horizontal(|ui| {
+                            ui.add_sized(
+                                [CHART_LABEL_WIDTH, 18.0],
+                                egui::Label::new(
+                                    RichText::new(&point.label)
+                                        .color(theme.text_muted)
+                                        .size(theme.text_size(12.0)),
+                                )
+                                .truncate(),
+                            );
+                            let bar_width =
+                                (ui.available_width() - CHART_VALUE_WIDTH).max(theme.spacing_16);
+                            let (track, _) = ui.allocate_exact_size(
+                                egui::vec2(bar_width, 12.0),
+                                egui::Sense::hover(),
+                            );
+                            ui.painter().rect_filled(track, radius, theme.surface_3);
+                            let fraction = if max > 0.0 {
+                                (point.value / max) as f32
+                            } else {
+                                0.0
+                            };
+                            let bar = egui::Rect::from_min_size(
+                                track.min,
+                                egui::vec2(track.width() * fraction, track.height()),
+                            );
+                            ui.painter().rect_filled(bar, radius, theme.accent_primary);
+                            ui.label(
+                                RichText::new(point.value.to_string())
+                                    .color(theme.text_primary)
+                                    .size(theme.text_size(12.0))
+                                    .monospace(),
+                            );
+                        });
+                    }
+                });
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
+            }
+            ValidatedComponent::Row(row) => {
+                let visible = row
+                    .children
+                    .iter()
+                    .filter(|child| child.is_visible(form_state))
+                    .collect::<Vec<_>>();
+                if visible.is_empty() {
+                    return;
+                }
+                ui.push_id(("row", row.id.as_str()), |ui| {
+                    ui.columns(visible.len(), |columns| {
+                        for (column, child) in columns.iter_mut().zip(visible) {
+                            self.render_component(
+                                child,
+                                column,
+                                theme,
+                                form_state,
+                                validation_errors,
+                                emit,
+                            );
+                        }
+                    });
+                });
             }
             ValidatedComponent::Button(button) => {
                 let (fill, stroke, text_color) = match button.variant {
@@ -141,22 +600,41 @@ impl ComponentRegistry {
                         theme.subtle_button_stroke(),
                         theme.text_primary,
                     ),
+                    ButtonStyle::Danger => (
+                        theme.danger,
+                        theme.primary_button_stroke(),
+                        theme.text_on_accent,
+                    ),
                 };
-                let button_widget =
-                    egui::Button::new(RichText::new(&button.label).color(text_color).size(13.0))
-                        .fill(fill)
-                        .stroke(stroke)
-                        .corner_radius(egui::CornerRadius::same(theme.radius_8))
-                        .min_size(egui::vec2(0.0, theme.button_height));
-
-                if ui.add(button_widget).clicked() {
-                    emit(UiEvent::ButtonClicked {
-                        component_id: button.id.clone(),
-                        output_event_id: button.output_event_id.clone(),
-                    });
+                let button_widget = egui::Button::new(
+                    RichText::new(&button.label)
+                        .color(text_color)
+                        .size(theme.text_size(13.0)),
+                )
+                .fill(fill)
+                .stroke(stroke)
+                .corner_radius(egui::CornerRadius::same(theme.radius_8))
+                .min_size(egui::vec2(0.0, theme.button_height));
+
+                if ui.add_enabled(!button.disabled, button_widget).clicked() {
+                    if let Some(event) = request_button_click(form_state, button) {
+                        emit(event);
+                    }
+                }
+                if let Some(prompt) = button
+                    .confirm
+                    .as_deref()
+                    .filter(|_| form_state.contains_key(&confirm_state_key(&button.id)))
+                {
+                    if let Some(confirmed) = Self::render_confirm_dialog(ui, theme, button, prompt)
+                    {
+                        if let Some(event) = resolve_button_confirm(form_state, button, confirmed) {
+                            emit(event);
+                        }
+                    }
                 }
 
-                self.render_children(component, ui, theme, form_state, emit);
+                self.render_children(component, ui, theme, form_state, validation_errors, emit);
             }
         }
     }
@@ -167,21 +645,174 @@ impl ComponentRegistry {
         ui: &mut egui::Ui,
         theme: &Theme,
         form_state: &mut BTreeMap<String, UiFieldValue>,
+        validation_errors: &BTreeMap<String, String>,
         emit: &mut dyn FnMut(UiEvent),
     ) {
         for child in component.children() {
+            if !child.is_visible(form_state) {
+                continue;
+            }
             ui.add_space(theme.spacing_8);
-            self.render_component(child, ui, theme, form_state, emit);
+            self.render_component(child, ui, theme, form_state, validation_errors, emit);
+        }
+    }
+
+    /// Returns `Some(true)` on Confirm, `Some(false)` on Cancel/dismiss, `None` while open.
+    fn render_confirm_dialog(
+        ui: &mut egui::Ui,
+        theme: &Theme,
+        button: &ButtonComponent,
+        prompt: &str,
+    ) -> Option<bool> {
+        let mut choice = None;
+        let modal = egui::Modal::new(egui::Id::new(("button_confirm", button.id.as_str()))).show(
+            ui.ctx(),
+            |ui| {
+                ui.set_max_width(360.0);
+                ui.label(
+                    RichText::new(&button.label)
+                        .color(theme.text_primary)
+                        .size(theme.text_size(14.0))
+                        .strong(),
+                );
+                ui.add_space(theme.spacing_8);
+                ui.add(
+                    egui::Label::new(
+                        RichText::new(prompt)
+                            .color(theme.text_muted)
+                            .size(theme.text_size(13.0)),
+                    )
+                    .wrap(),
+                );
+                ui.add_space(theme.spacing_12);
+                ui.horizontal(|ui| {
+                    let confirm_fill = if button.variant == ButtonStyle::Danger {
+                        theme.danger
+                    } else {
+                        theme.accent_primary
+                    };
+                    let confirm = egui::Button::new(
+                        RichText::new("Confirm")
+                            .color(theme.text_on_accent)
+                            .size(theme.text_size(13.0)),
+                    )
+                    .fill(confirm_fill)
+                    .stroke(theme.primary_button_stroke())
+                    .corner_radius(egui::CornerRadius::same(theme.radius_8))
+                    .min_size(egui::vec2(0.0, theme.button_height));
+                    if ui.add(confirm).clicked() {
+                        choice = Some(true);
+                    }
+                    let cancel = egui::Button::new(
+                        RichText::new("Cancel")
+                            .color(theme.text_primary)
+                            .size(theme.text_size(13.0)),
+                    )
+                    .fill(theme.surface_2)
+                    .stroke(theme.subtle_button_stroke())
+                    .corner_radius(egui::CornerRadius::same(theme.radius_8))
+                    .min_size(egui::vec2(0.0, theme.button_height));
+                    if ui.add(cancel).clicked() {
+                        choice = Some(false);
+                    }
+                });
+            },
+        );
+        if choice.is_none() && modal.should_close() {
+            choice = Some(false);
+        }
+        choice
+    }
+
+    /// Directories render as collapsible headers (top level open by default);
+    /// clicking a file emits `TreeNodeClicked` with its path. Pathless nodes
+    /// (such as a truncation marker) are shown muted and are not clickable.
+    fn render_tree_nodes(
+        tree_id: &str,
+        nodes: &[TreeNode],
+        depth: usize,
+        show_metadata: bool,
+        ui: &mut egui::Ui,
+        theme: &Theme,
+        emit: &mut dyn FnMut(UiEvent),
+    ) {
+        for (index, node) in nodes.iter().enumerate() {
+            let branch = tree_branch(index + 1 == nodes.len());
+            let metadata = if show_metadata && !node.path.is_empty() {
+                format!("  {}", tree_node_metadata_label(node))
+            } else {
+                String::new()
+            };
+            if node.is_dir {
+                egui::CollapsingHeader::new(
+                    RichText::new(format!("{branch}{}/{metadata}", node.name))
+                        .color(theme.text_primary)
+                        .size(theme.text_size(13.0))
+                        .monospace(),
+                )
+                .id_salt((tree_id, node.path.as_str()))
+                .default_open(depth == 0)
+                .show(ui, |ui| {
+                    Self::render_tree_nodes(
+                        tree_id,
+                        &node.children,
+                        depth + 1,
+                        show_metadata,
+                        ui,
+                        theme,
+                        emit,
+                    );
+                });
+                continue;
+            }
+
+            if node.path.is_empty() {
+                ui.label(
+                    RichText::new(format!("{branch}{}", node.name))
+                        .color(theme.text_muted)
+                        .size(theme.text_size(13.0))
+                        .monospace(),
+                );
+                continue;
+            }
+
+            let label = egui::Label::new(
+                RichText::new(format!("{branch}{}", node.name))
+                    .color(theme.text_primary)
+                    .size(theme.text_size(13.0))
+                    .monospace(),
+            )
+            .sense(egui::Sense::click());
+            let response = ui
+                .horizontal(|ui| {
+                    let response = ui.add(label);
+                    if !metadata.is_empty() {
+                        ui.label(
+                            RichText::new(metadata.trim_start())
+                                .color(theme.text_muted)
+                                .size(theme.text_size(12.0))
+                                .monospace(),
+                        );
+                    }
+                    response
+                })
+                .inner;
+            if response.on_hover_text(&node.path).clicked() {
+                emit(UiEvent::TreeNodeClicked {
+                    component_id: tree_id.to_string(),
+                    path: node.path.clone(),
+                });
+            }
         }
     }
 
     fn render_form_field(
-        &self,
         form_id: &str,
         field: &ValidatedFormField,
         ui: &mut egui::Ui,
         theme: &Theme,
         form_state: &mut BTreeMap<String, UiFieldValue>,
+        validation_errors: &BTreeMap<String, String>,
         emit: &mut dyn FnMut(UiEvent),
     ) {
         let field_id = field.id().to_string();
@@ -190,6 +821,7 @@ impl ComponentRegistry {
             .entry(state_key.clone())
             .or_insert_with(|| field.default_value())
             .clone();
+        let validation_error = validation_errors.get(&state_key).cloned();
 
         match field {
             ValidatedFormField::Text(text_field) => {
@@ -200,7 +832,7 @@ impl ComponentRegistry {
                 ui.label(
                     RichText::new(&text_field.label)
                         .color(theme.text_muted)
-                        .size(12.0),
+                        .size(theme.text_size(12.0)),
                 );
                 let response = ui.add(
                     egui::TextEdit::singleline(&mut value)
@@ -220,6 +852,35 @@ impl ComponentRegistry {
                     form_state.insert(state_key, UiFieldValue::Text { value });
                 }
             }
+            ValidatedFormField::TextArea(text_area_field) => {
+                let mut value = match current {
+                    UiFieldValue::Text { value } => value,
+                    _ => text_area_field.default.clone(),
+                };
+                ui.label(
+                    RichText::new(&text_area_field.label)
+                        .color(theme.text_muted)
+                        .size(theme.text_size(12.0)),
+                );
+                let response = ui.add(
+                    egui::TextEdit::multiline(&mut value)
+                        .desired_rows(text_area_field.rows)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("text"),
+                );
+                if response.lost_focus() && response.changed() {
+                    let value = UiFieldValue::Text { value };
+                    form_state.insert(state_key, value.clone());
+                    emit(UiEvent::FormFieldCommitted {
+                        component_id: form_id.to_string(),
+                        form_id: form_id.to_string(),
+                        field_id,
+                        value,
+                    });
+                } else {
+                    form_state.insert(state_key, UiFieldValue::Text { value });
+                }
+            }
             ValidatedFormField::Number(number_field) => {
                 let mut value = match current {
                     UiFieldValue::Number { value } => value,
@@ -228,10 +889,24 @@ impl ComponentRegistry {
                 ui.label(
                     RichText::new(&number_field.label)
                         .color(theme.text_muted)
-                        .size(12.0),
+                        .size(theme.text_size(12.0)),
                 );
-                let response = ui.add(egui::DragValue::new(&mut value).speed(0.1));
-                if response.changed() {
+                let response = match number_field.slider_range() {
+                    Some(range) => {
+                        let mut slider = egui::Slider::new(&mut value, range);
+                        if let Some(step) = number_field.step {
+                            slider = slider.step_by(step);
+                        }
+                        ui.add(slider)
+                    }
+                    None => ui.add(egui::DragValue::new(&mut value).speed(0.1)),
+                };
+                let committed = if number_field.slider_range().is_some() {
+                    response.drag_stopped() || (response.changed() && !response.dragged())
+                } else {
+                    response.changed()
+                };
+                if committed {
                     let value = UiFieldValue::Number { value };
                     form_state.insert(state_key, value.clone());
                     emit(UiEvent::FormFieldCommitted {
@@ -240,6 +915,8 @@ impl ComponentRegistry {
                         field_id,
                         value,
                     });
+                } else if response.changed() {
+                    form_state.insert(state_key, UiFieldValue::Number { value });
                 }
             }
             ValidatedFormField::Select(select_field) => {
@@ -250,7 +927,7 @@ impl ComponentRegistry {
                 ui.label(
                     RichText::new(&select_field.label)
                         .color(theme.text_muted)
-                        .size(12.0),
+                        .size(theme.text_size(12.0)),
                 );
                 let mut changed = false;
                 egui::ComboBox::from_id_salt(state_key.clone())
@@ -276,6 +953,55 @@ impl ComponentRegistry {
                     });
                 }
             }
+            ValidatedFormField::MultiSelect(multi_select_field) => {
+                let mut selected = match current {
+                    UiFieldValue::MultiSelect { value } => value,
+                    _ => multi_select_field.default.clone(),
+                };
+                ui.label(
+                    RichText::new(&multi_select_field.label)
+                        .color(theme.text_muted)
+                        .size(theme.text_size(12.0)),
+                );
+                let mut changed = false;
+                ui.horizontal_wrapped(|ui| {
+                    for option in &multi_select_field.options {
+                        let mut checked = selected.contains(option);
+                        if ui
+                            .checkbox(
+                                &mut checked,
+                                RichText::new(option)
+                                    .color(theme.text_primary)
+                                    .size(theme.text_size(13.0)),
+                            )
+                            .changed()
+                        {
+                            changed = true;
+                            if checked {
+                                selected.push(option.clone());
+                            } else {
+                                selected.retain(|value| value != option);
+                            }
+                        }
+                    }
+                });
+                if changed {
+                    selected.sort_by_key(|value| {
+                        multi_select_field
+                            .options
+                            .iter()
+                            .position(|option| option == value)
+                    });
+                    let value = UiFieldValue::MultiSelect { value: selected };
+                    form_state.insert(state_key, value.clone());
+                    emit(UiEvent::FormFieldCommitted {
+                        component_id: form_id.to_string(),
+                        form_id: form_id.to_string(),
+                        field_id,
+                        value,
+                    });
+                }
+            }
             ValidatedFormField::Checkbox(checkbox_field) => {
                 let mut checked = match current {
                     UiFieldValue::Checkbox { value } => value,
@@ -286,7 +1012,7 @@ impl ComponentRegistry {
                         &mut checked,
                         RichText::new(&checkbox_field.label)
                             .color(theme.text_primary)
-                            .size(13.0),
+                            .size(theme.text_size(13.0)),
                     )
                     .changed()
                 {
@@ -300,8 +1026,221 @@ impl ComponentRegistry {
                     });
                 }
             }
+            ValidatedFormField::Date(date_field) => {
+                let value = match current {
+                    UiFieldValue::Date { value } => value,
+                    _ => date_field.default.clone(),
+                };
+                ui.label(
+                    RichText::new(&date_field.label)
+                        .color(theme.text_muted)
+                        .size(theme.text_size(12.0)),
+                );
+                // Form state only ever holds committed dates; an unfinished or
+                // invalid entry lives in egui memory until it parses.
+                let draft_id = egui::Id::new(("date_draft", state_key.as_str()));
+                let mut draft = ui
+                    .data(|data| data.get_temp::<String>(draft_id))
+                    .unwrap_or_else(|| value.clone());
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut draft)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("YYYY-MM-DD"),
+                );
+                let trimmed = draft.trim().to_string();
+                let valid = trimmed.is_empty() || is_iso_date(&trimmed);
+                if response.lost_focus() && valid && trimmed != value {
+                    ui.data_mut(|data| data.remove::<String>(draft_id));
+                    let value = UiFieldValue::Date { value: trimmed };
+                    form_state.insert(state_key, value.clone());
+                    emit(UiEvent::FormFieldCommitted {
+                        component_id: form_id.to_string(),
+                        form_id: form_id.to_string(),
+                        field_id,
+                        value,
+                    });
+                } else if draft == value {
+                    ui.data_mut(|data| data.remove::<String>(draft_id));
+                } else {
+                    ui.data_mut(|data| data.insert_temp(draft_id, draft));
+                }
+                if !valid {
+                    ui.label(
+                        RichText::new("Enter a date as YYYY-MM-DD")
+                            .color(theme.danger)
+                            .size(theme.text_size(12.0)),
+                    );
+                }
+            }
+        }
+
+        if let Some(message) = validation_error {
+            ui.label(
+                RichText::new(message)
+                    .color(theme.danger)
+                    .size(theme.text_size(12.0)),
+            );
+        }
+    }
+}
+
+/// A click on a button with a `confirm` prompt only opens the dialog;
+/// otherwise the click event is returned for emission. Disabled buttons never emit.
+pub fn request_button_click(
+    form_state: &mut BTreeMap<String, UiFieldValue>,
+    button: &ButtonComponent,
+) -> Option<UiEvent> {
+    if button.disabled {
+        return None;
+    }
+    if button.confirm.is_some() {
+        form_state.insert(
+            confirm_state_key(&button.id),
+            UiFieldValue::Checkbox { value: true },
+        );
+        return None;
+    }
+    Some(button_clicked_event(button))
+}
+
+/// Closes a pending confirm dialog, returning the click event only when confirmed.
+pub fn resolve_button_confirm(
+    form_state: &mut BTreeMap<String, UiFieldValue>,
+    button: &ButtonComponent,
+    confirmed: bool,
+) -> Option<UiEvent> {
+    let was_pending = form_state.remove(&confirm_state_key(&button.id)).is_some();
+    (was_pending && confirmed).then(|| button_clicked_event(button))
+}
+
+fn button_clicked_event(button: &ButtonComponent) -> UiEvent {
+    UiEvent::ButtonClicked {
+        component_id: button.id.clone(),
+        output_event_id: button.output_event_id.clone(),
+        form_values: BTreeMap::new(),
+    }
+}
+
+/// Active tab from `form_state`, clamping stale indices. A hidden selection falls back to
+/// the first visible tab; `None` when every tab is hidden.
+pub fn active_tab_index(
+    form_state: &BTreeMap<String, UiFieldValue>,
+    tabs: &TabsComponent,
+) -> Option<usize> {
+    let index = match form_state.get(&tabs_state_key(&tabs.id)) {
+        Some(UiFieldValue::Number { value }) if *value >= 0.0 => *value as usize,
+        _ => 0,
+    };
+    let stored = index.min(tabs.children.len().saturating_sub(1));
+    let visible = |index: &usize| {
+        tabs.children
+            .get(*index)
+            .is_some_and(|child| child.is_visible(form_state))
+    };
+    Some(stored)
+        .filter(visible)
+        .or_else(|| (0..tabs.children.len()).find(visible))
+}
+
+/// Small themed "Copy" button that puts `content` on the clipboard.
+pub fn render_copy_button(ui: &mut egui::Ui, theme: &Theme, content: &str) {
+    let button = egui::Button::new(
+        RichText::new("⧉ Copy")
+            .color(theme.text_muted)
+            .size(theme.text_size(12.0)),
+    )
+    .fill(theme.surface_2)
+    .stroke(theme.subtle_button_stroke())
+    .corner_radius(egui::CornerRadius::same(theme.radius_8));
+    if ui.add(button).on_hover_text("Copy to clipboard").clicked() {
+        ui.ctx().copy_text(content.to_string());
+    }
+}
+
+/// Diff text as plain lines, without the rendered gutter markers.
+fn diff_plain_text(diff: &DiffComponent) -> String {
+    diff.lines
+        .iter()
+        .map(|line| line.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Human-readable size using binary units, e.g. `512 B`, `1.5 KB`, `2.0 MB`.
+fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Size (or entry count for directories) and modified time, with `-` for anything unknown.
+fn tree_node_metadata_label(node: &TreeNode) -> String {
+    let amount = if node.is_dir {
+        node.entry_count.map(|count| match count {
+            1 => "1 item".to_string(),
+            count => format!("{count} items"),
+        })
+    } else {
+        node.size.map(format_file_size)
+    };
+    let modified = node.modified.as_deref().map(format_timestamp);
+    format!(
+        "{} · {}",
+        amount.as_deref().unwrap_or("-"),
+        modified.as_deref().unwrap_or("-")
+    )
+}
+
+fn tree_branch(is_last: bool) -> &'static str {
+    if is_last {
+        "└── "
+    } else {
+        "├── "
+    }
+}
+
+/// Tree as an indented text listing using `├──`/`└──` branches, directories suffixed `/`.
+fn tree_plain_text(nodes: &[TreeNode]) -> String {
+    fn push_lines(nodes: &[TreeNode], indent: &str, lines: &mut Vec<String>) {
+        for (index, node) in nodes.iter().enumerate() {
+            let is_last = index + 1 == nodes.len();
+            let suffix = if node.is_dir { "/" } else { "" };
+            lines.push(format!(
+                "{indent}{}{}{suffix}",
+                tree_branch(is_last),
+                node.name
+            ));
+            let child_indent = format!("{indent}{}", if is_last { "    " } else { "│   " });
+            push_lines(&node.children, &child_indent, lines);
         }
     }
+
+    let mut lines = Vec::new();
+    push_lines(nodes, "", &mut lines);
+    lines.join("\n")
+}
+
+fn render_copy_full_action(ui: &mut egui::Ui, theme: &Theme, text: &TruncatedText) {
+    if !text.truncated {
+        return;
+    }
+
+    ui.add_space(theme.spacing_4);
+    if ui
+        .small_button("Copy full")
+        .on_hover_text("Copy the untruncated content")
+        .clicked()
+    {
+        ui.ctx().copy_text(text.full.clone());
+    }
 }
 
 impl SchemaRegistry for ComponentRegistry {
@@ -313,3 +1252,125 @@ impl SchemaRegistry for ComponentRegistry {
         self.allowed_field_kinds.contains(kind.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        diff_plain_text, format_file_size, highlight_language, tree_node_metadata_label,
+        tree_plain_text, truncate_long_lines,
+    };
+    use crate::ui::schema::{DiffComponent, DiffLine, DiffLineKind, TreeNode};
+
+    #[test]
+    fn truncate_long_lines_caps_display_but_keeps_full_content() {
+        let long_line = "x".repeat(25);
+        let text = format!("short\n{long_line}\nend");
+        let truncated = truncate_long_lines(&text, 10);
+
+        assert!(truncated.truncated);
+        assert_eq!(truncated.full, text);
+        assert_eq!(
+            truncated.display,
+            format!("short\n{}… (truncated)\nend", "x".repeat(10))
+        );
+    }
+
+    #[test]
+    fn truncate_long_lines_leaves_short_text_untouched() {
+        let truncated = truncate_long_lines("fits\nfine", 10);
+        assert!(!truncated.truncated);
+        assert_eq!(truncated.display, "fits\nfine");
+    }
+
+    #[test]
+    fn highlight_language_falls_back_for_missing_or_unknown_languages() {
+        assert_eq!(highlight_language(Some("Rust")), Some("rs"));
+        assert_eq!(highlight_language(Some("python")), Some("py"));
+        assert_eq!(highlight_language(Some("brainfuck")), None);
+        assert_eq!(highlight_language(None), None);
+    }
+
+    #[test]
+    fn format_file_size_scales_through_binary_units() {
+        assert_eq!(format_file_size(0), "0 B");
+        assert_eq!(format_file_size(1023), "1023 B");
+        assert_eq!(format_file_size(1024), "1.0 KB");
+        assert_eq!(format_file_size(1536), "1.5 KB");
+        assert_eq!(format_file_size(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_file_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn tree_node_metadata_label_falls_back_to_dashes() {
+        let mut node = TreeNode {
+            name: "locked.bin".to_string(),
+            path: "locked.bin".to_string(),
+            is_dir: false,
+            children: Vec::new(),
+            size: None,
+            modified: None,
+            entry_count: None,
+        };
+        assert_eq!(tree_node_metadata_label(&node), "- · -");
+
+        node.size = Some(2048);
+        assert!(tree_node_metadata_label(&node).starts_with("2.0 KB · "));
+
+        node.is_dir = true;
+        node.entry_count = Some(3);
+        assert_eq!(tree_node_metadata_label(&node), "3 items · -");
+    }
+
+    #[test]
+    fn tree_plain_text_indents_nested_nodes_with_branches() {
+        fn node(name: &str, is_dir: bool, children: Vec<TreeNode>) -> TreeNode {
+            TreeNode {
+                name: name.to_string(),
+                path: name.to_string(),
+                is_dir,
+                children,
+                size: None,
+                modified: None,
+                entry_count: None,
+            }
+        }
+        let nodes = vec![
+            node(
+                "src",
+                true,
+                vec![
+                    node("ui", true, vec![node("mod.rs", false, Vec::new())]),
+                    node("main.rs", false, Vec::new()),
+                ],
+            ),
+            node("README.md", false, Vec::new()),
+        ];
+
+        assert_eq!(
+            tree_plain_text(&nodes),
+            "├── src/\n│   ├── ui/\n│   │   └── mod.rs\n│   └── main.rs\n└── README.md"
+        );
+    }
+
+    #[test]
+    fn diff_plain_text_joins_lines_without_gutter_markers() {
+        let diff = DiffComponent {
+            id: "diff".to_string(),
+            lines: vec![
+                DiffLine {
+                    kind: DiffLineKind::Removed,
+                    text: "- old".to_string(),
+                },
+                DiffLine {
+                    kind: DiffLineKind::Added,
+                    text: "+ new".to_string(),
+                },
+            ],
+            visible_when: None,
+            children: Vec::new(),
+        };
+        let text = diff_plain_text(&diff);
+        assert_eq!(text, "- old\n+ new");
+        assert!(!text.contains('▌'));
+    }
+}
@@ -1,12 +1,13 @@
 use crate::theme::Theme;
-use crate::ui::event::{UiEvent, UiEventLog, UiFieldValue};
+use crate::ui::event::{UiEvent, UiEventEntry, UiEventLog, UiFieldValue};
 use crate::ui::registry::ComponentRegistry;
 use crate::ui::schema::{
-    field_key, validate_schema, UiSchema, ValidatedComponent, ValidatedSchema,
+    field_key, is_synthetic_state_key, tabs_state_key, validate_schema_with_limits, UiSchema,
+    ValidatedComponent, ValidatedSchema, ValidationLimits,
 };
 use eframe::egui::{self, RichText};
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{vec_deque, BTreeMap};
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -28,28 +29,49 @@ impl std::error::Error for RuntimeError {}
 
 pub struct UiRuntime {
     registry: ComponentRegistry,
+    limits: ValidationLimits,
     validated_schema: Option<ValidatedSchema>,
+    loaded_schema: Option<Value>,
     runtime_error: Option<RuntimeError>,
     form_state: BTreeMap<String, UiFieldValue>,
+    validation_errors: BTreeMap<String, String>,
     event_log: UiEventLog,
 }
 
 impl UiRuntime {
     pub fn new() -> Self {
+        Self::with_limits(ValidationLimits::default())
+    }
+
+    /// Runtime that validates schemas against custom size limits.
+    pub fn with_limits(limits: ValidationLimits) -> Self {
         Self {
             registry: ComponentRegistry::new(),
+            limits,
             validated_schema: None,
+            loaded_schema: None,
             runtime_error: None,
             form_state: BTreeMap::new(),
+            validation_errors: BTreeMap::new(),
             event_log: UiEventLog::default(),
         }
     }
 
+    /// Runtime whose event log keeps at most `max_entries` events.
+    pub fn with_event_log_max_entries(max_entries: usize) -> Self {
+        Self {
+            event_log: UiEventLog::with_max_entries(max_entries),
+            ..Self::new()
+        }
+    }
+
     #[cfg(test)]
     pub fn load_schema_json(&mut self, raw_schema: &str) -> Result<(), RuntimeError> {
         self.validated_schema = None;
+        self.loaded_schema = None;
         self.runtime_error = None;
         self.form_state.clear();
+        self.validation_errors.clear();
 
         let parsed: UiSchema = match serde_json::from_str(raw_schema) {
             Ok(schema) => schema,
@@ -65,8 +87,10 @@ impl UiRuntime {
 
     pub fn load_schema_value(&mut self, raw_schema: &Value) -> Result<(), RuntimeError> {
         self.validated_schema = None;
+        self.loaded_schema = Some(raw_schema.clone());
         self.runtime_error = None;
         self.form_state.clear();
+        self.validation_errors.clear();
 
         let parsed: UiSchema = match serde_json::from_value(raw_schema.clone()) {
             Ok(schema) => schema,
@@ -80,7 +104,28 @@ impl UiRuntime {
         self.load_schema(parsed)
     }
 
-    #[cfg(test)]
+    /// Like `load_schema_value`, but keeps the validated schema and form state when
+    /// `raw_schema` equals the value last loaded. Returns whether it re-validated.
+    pub fn reload_schema_value(&mut self, raw_schema: &Value) -> Result<bool, RuntimeError> {
+        if self.loaded_schema.as_ref() == Some(raw_schema) {
+            return match &self.runtime_error {
+                Some(error) => Err(error.clone()),
+                None => Ok(false),
+            };
+        }
+        self.load_schema_value(raw_schema).map(|()| true)
+    }
+
+    /// Loads a schema that was already validated elsewhere, seeding form defaults.
+    pub fn load_validated(&mut self, validated: ValidatedSchema) {
+        self.loaded_schema = None;
+        self.runtime_error = None;
+        self.form_state.clear();
+        self.validation_errors.clear();
+        self.seed_form_state(&validated.components);
+        self.validated_schema = Some(validated);
+    }
+
     pub fn has_schema(&self) -> bool {
         self.validated_schema.is_some()
     }
@@ -90,8 +135,13 @@ impl UiRuntime {
         self.runtime_error.as_ref()
     }
 
+    #[cfg(test)]
+    pub fn validation_errors(&self) -> &BTreeMap<String, String> {
+        &self.validation_errors
+    }
+
     fn load_schema(&mut self, schema: UiSchema) -> Result<(), RuntimeError> {
-        let validated = match validate_schema(&schema, &self.registry) {
+        let validated = match validate_schema_with_limits(&schema, &self.registry, &self.limits) {
             Ok(validated) => validated,
             Err(err) => {
                 let error = RuntimeError::Validation(err.to_string());
@@ -105,8 +155,30 @@ impl UiRuntime {
         Ok(())
     }
 
-    pub fn event_log(&self) -> &[UiEvent] {
-        self.event_log.entries()
+    #[cfg(test)]
+    pub fn event_log(&self) -> Vec<&UiEvent> {
+        self.event_log
+            .entries()
+            .iter()
+            .map(|entry| &entry.event)
+            .collect()
+    }
+
+    /// Count of events emitted so far, including any evicted from the bounded log.
+    pub fn event_log_total(&self) -> usize {
+        self.event_log.total_pushed()
+    }
+
+    pub fn event_log_max_entries(&self) -> usize {
+        self.event_log.max_entries()
+    }
+
+    pub fn events_since(&self, seen: usize) -> vec_deque::Iter<'_, UiEventEntry> {
+        self.event_log.entries_since(seen)
+    }
+
+    pub fn validated_schema(&self) -> Option<&ValidatedSchema> {
+        self.validated_schema.as_ref()
     }
 
     pub fn form_state_snapshot(&self) -> BTreeMap<String, UiFieldValue> {
@@ -117,6 +189,53 @@ impl UiRuntime {
         self.form_state = state;
     }
 
+    /// Rebuilds form state from the schema defaults plus `events`, applying each
+    /// `FormFieldCommitted` and `TabSelected` in order and ignoring every other event.
+    /// A pending confirm is not recorded in events and starts fresh.
+    pub fn replay(&mut self, events: &[UiEvent]) {
+        self.form_state.clear();
+        if let Some(schema) = self.validated_schema.take() {
+            self.seed_form_state(&schema.components);
+            self.validated_schema = Some(schema);
+        }
+        for event in events {
+            match event {
+                UiEvent::FormFieldCommitted {
+                    form_id,
+                    field_id,
+                    value,
+                    ..
+                } => {
+                    self.form_state
+                        .insert(field_key(form_id, field_id), value.clone());
+                }
+                UiEvent::TabSelected {
+                    component_id,
+                    tab_index,
+                } => {
+                    self.form_state.insert(
+                        tabs_state_key(component_id),
+                        UiFieldValue::Number {
+                            value: *tab_index as f64,
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `replay` over the events this runtime has logged and still retains.
+    pub fn replay_event_log(&mut self) {
+        let events = self
+            .event_log
+            .entries()
+            .iter()
+            .map(|entry| entry.event.clone())
+            .collect::<Vec<_>>();
+        self.replay(&events);
+    }
+
     pub fn render_canvas(&mut self, ui: &mut egui::Ui, theme: &Theme) {
         if let Some(error) = &self.runtime_error {
             let frame = theme.card_frame();
@@ -124,13 +243,13 @@ impl UiRuntime {
                 ui.label(
                     RichText::new("Canvas validation failed")
                         .color(theme.danger)
-                        .size(13.0),
+                        .size(theme.text_size(13.0)),
                 );
                 ui.add_space(theme.spacing_8);
                 ui.label(
                     RichText::new(error.to_string())
                         .color(theme.text_muted)
-                        .size(12.0),
+                        .size(theme.text_size(12.0)),
                 );
             });
             return;
@@ -140,25 +259,105 @@ impl UiRuntime {
             return;
         };
         let _schema_version = schema.schema_version;
+        let mut emitted = Vec::new();
         for component in &schema.components {
+            if !component.is_visible(&self.form_state) {
+                continue;
+            }
             self.registry.render_component(
                 component,
                 ui,
                 theme,
                 &mut self.form_state,
-                &mut |event| self.event_log.push(event),
+                &self.validation_errors,
+                &mut |event| emitted.push(event),
             );
             ui.add_space(theme.spacing_12);
         }
+
+        for event in emitted {
+            self.handle_event(event);
+        }
+    }
+
+    /// Button clicks are blocked while any required field in the block is empty;
+    /// committing a field re-checks and clears errors that are now satisfied.
+    /// Accepted clicks carry a snapshot of the current form values.
+    fn handle_event(&mut self, mut event: UiEvent) {
+        match &mut event {
+            UiEvent::ButtonClicked { form_values, .. } => {
+                let missing = self.missing_required_fields();
+                if !missing.is_empty() {
+                    self.validation_errors = missing;
+                    return;
+                }
+                self.validation_errors.clear();
+                *form_values = self
+                    .form_state
+                    .iter()
+                    .filter(|(key, _)| !is_synthetic_state_key(key))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+            }
+            UiEvent::FormFieldCommitted { .. } if !self.validation_errors.is_empty() => {
+                let missing = self.missing_required_fields();
+                self.validation_errors
+                    .retain(|key, _| missing.contains_key(key));
+            }
+            _ => {}
+        }
+        self.event_log.push(event);
+    }
+
+    fn missing_required_fields(&self) -> BTreeMap<String, String> {
+        fn walk(
+            components: &[ValidatedComponent],
+            form_state: &BTreeMap<String, UiFieldValue>,
+            missing: &mut BTreeMap<String, String>,
+        ) {
+            for component in components {
+                if !component.is_visible(form_state) {
+                    continue;
+                }
+                if let ValidatedComponent::Form(form) = component {
+                    for field in form.fields.iter().filter(|field| field.is_required()) {
+                        let key = field_key(&form.id, field.id());
+                        let is_empty = form_state
+                            .get(&key)
+                            .map(UiFieldValue::is_empty)
+                            .unwrap_or(true);
+                        if is_empty {
+                            missing.insert(key, format!("{} is required", field.label()));
+                        }
+                    }
+                }
+                walk(component.children(), form_state, missing);
+            }
+        }
+
+        let mut missing = BTreeMap::new();
+        if let Some(schema) = &self.validated_schema {
+            walk(&schema.components, &self.form_state, &mut missing);
+        }
+        missing
     }
 
     fn seed_form_state(&mut self, components: &[ValidatedComponent]) {
         for component in components {
-            if let ValidatedComponent::Form(form) = component {
-                for field in &form.fields {
-                    self.form_state
-                        .insert(field_key(&form.id, field.id()), field.default_value());
+            match component {
+                ValidatedComponent::Form(form) => {
+                    for field in &form.fields {
+                        self.form_state
+                            .insert(field_key(&form.id, field.id()), field.default_value());
+                    }
+                }
+                ValidatedComponent::Tabs(tabs) => {
+                    self.form_state.insert(
+                        tabs_state_key(&tabs.id),
+                        UiFieldValue::Number { value: 0.0 },
+                    );
                 }
+                _ => {}
             }
             self.seed_form_state(component.children());
         }
@@ -167,10 +366,24 @@ impl UiRuntime {
     #[cfg(test)]
     pub fn simulate_button_click(&mut self, button_id: &str) {
         if let Some(button) = self.find_button(button_id) {
-            self.event_log.push(UiEvent::ButtonClicked {
-                component_id: button.id.clone(),
-                output_event_id: button.output_event_id.clone(),
-            });
+            if let Some(event) =
+                crate::ui::registry::request_button_click(&mut self.form_state, &button)
+            {
+                self.handle_event(event);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn simulate_button_confirm(&mut self, button_id: &str, confirmed: bool) {
+        if let Some(button) = self.find_button(button_id) {
+            if let Some(event) = crate::ui::registry::resolve_button_confirm(
+                &mut self.form_state,
+                &button,
+                confirmed,
+            ) {
+                self.handle_event(event);
+            }
         }
     }
 
@@ -178,7 +391,7 @@ impl UiRuntime {
     pub fn simulate_form_commit(&mut self, form_id: &str, field_id: &str, value: UiFieldValue) {
         self.form_state
             .insert(field_key(form_id, field_id), value.clone());
-        self.event_log.push(UiEvent::FormFieldCommitted {
+        self.handle_event(UiEvent::FormFieldCommitted {
             component_id: form_id.to_string(),
             form_id: form_id.to_string(),
             field_id: field_id.to_string(),
@@ -186,6 +399,20 @@ impl UiRuntime {
         });
     }
 
+    #[cfg(test)]
+    pub fn simulate_tab_select(&mut self, tabs_id: &str, tab_index: usize) {
+        self.form_state.insert(
+            tabs_state_key(tabs_id),
+            UiFieldValue::Number {
+                value: tab_index as f64,
+            },
+        );
+        self.handle_event(UiEvent::TabSelected {
+            component_id: tabs_id.to_string(),
+            tab_index,
+        });
+    }
+
     #[cfg(test)]
     fn find_button(&self, button_id: &str) -> Option<crate::ui::schema::ButtonComponent> {
         let schema = self.validated_schema.as_ref()?;
@@ -214,8 +441,17 @@ impl UiRuntime {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::registry::active_tab_index;
     use serde_json::json;
 
+    fn render_headless(runtime: &mut UiRuntime) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default()
+                .show(ctx, |ui| runtime.render_canvas(ui, &Theme::default()));
+        });
+    }
+
     #[test]
     fn deterministic_event_sequence_for_replayed_interactions() {
         let mut first = UiRuntime::new();
@@ -249,6 +485,631 @@ mod tests {
         assert_eq!(first.event_log(), second.event_log());
     }
 
+    #[test]
+    fn multiselect_form_state_round_trips_through_snapshot() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "triage_form",
+                "kind": "form",
+                "fields": [
+                    {"id": "summary", "label": "Summary", "kind": "text", "default": "ok"},
+                    {
+                        "id": "categories",
+                        "label": "Categories",
+                        "kind": "multiselect",
+                        "options": ["security", "perf", "style"]
+                    }
+                ]
+            }]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("multiselect schema should load");
+        runtime.simulate_form_commit(
+            "triage_form",
+            "categories",
+            UiFieldValue::MultiSelect {
+                value: vec!["security".to_string(), "perf".to_string()],
+            },
+        );
+
+        let persisted = serde_json::to_value(runtime.form_state_snapshot())
+            .expect("form state should serialize");
+        let restored: BTreeMap<String, UiFieldValue> =
+            serde_json::from_value(persisted).expect("form state should deserialize");
+
+        let mut reloaded = UiRuntime::new();
+        reloaded
+            .load_schema_value(&schema)
+            .expect("multiselect schema should reload");
+        reloaded.restore_form_state(restored);
+        assert_eq!(
+            reloaded.form_state_snapshot(),
+            runtime.form_state_snapshot()
+        );
+    }
+
+    #[test]
+    fn date_form_state_round_trips_through_snapshot() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "plan_form",
+                "kind": "form",
+                "fields": [
+                    {"id": "target", "label": "Target date", "kind": "date", "default": "2026-03-31"}
+                ]
+            }]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("date schema should load");
+        runtime.simulate_form_commit(
+            "plan_form",
+            "target",
+            UiFieldValue::Date {
+                value: "2026-04-15".to_string(),
+            },
+        );
+
+        let persisted = serde_json::to_value(runtime.form_state_snapshot())
+            .expect("form state should serialize");
+        assert_eq!(
+            persisted["plan_form:target"],
+            json!({"kind": "date", "value": "2026-04-15"})
+        );
+        let restored: BTreeMap<String, UiFieldValue> =
+            serde_json::from_value(persisted).expect("form state should deserialize");
+
+        let mut reloaded = UiRuntime::new();
+        reloaded
+            .load_schema_value(&schema)
+            .expect("date schema should reload");
+        reloaded.restore_form_state(restored);
+        assert_eq!(
+            reloaded.form_state_snapshot(),
+            runtime.form_state_snapshot()
+        );
+    }
+
+    #[test]
+    fn replaying_recorded_events_rebuilds_live_form_state() {
+        let mut live = UiRuntime::new();
+        live.load_schema_json(include_str!("fixture.json"))
+            .expect("fixture should load");
+        live.simulate_form_commit(
+            "review_form",
+            "decision",
+            UiFieldValue::Select {
+                value: "needs-changes".to_string(),
+            },
+        );
+        live.simulate_button_click("approve_btn");
+        live.simulate_form_commit(
+            "review_form",
+            "decision",
+            UiFieldValue::Select {
+                value: "approve".to_string(),
+            },
+        );
+        let recorded = live.event_log().into_iter().cloned().collect::<Vec<_>>();
+        assert!(recorded
+            .iter()
+            .any(|event| matches!(event, UiEvent::ButtonClicked { .. })));
+
+        let mut replayed = UiRuntime::new();
+        replayed
+            .load_schema_json(include_str!("fixture.json"))
+            .expect("fixture should load");
+        replayed.simulate_form_commit(
+            "review_form",
+            "decision",
+            UiFieldValue::Select {
+                value: "stale".to_string(),
+            },
+        );
+        replayed.replay(&recorded);
+
+        assert_eq!(replayed.form_state_snapshot(), live.form_state_snapshot());
+    }
+
+    #[test]
+    fn legacy_form_state_without_multiselect_still_deserializes() {
+        let legacy = json!({
+            "review_form:summary": {"kind": "text", "value": "hello"},
+            "review_form:notify_team": {"kind": "checkbox", "value": true}
+        });
+        let restored: BTreeMap<String, UiFieldValue> =
+            serde_json::from_value(legacy).expect("legacy form state should deserialize");
+        assert_eq!(restored.len(), 2);
+    }
+
+    #[test]
+    fn event_log_cap_bounds_retained_events() {
+        let mut runtime = UiRuntime::with_event_log_max_entries(2);
+        runtime
+            .load_schema_json(include_str!("fixture.json"))
+            .expect("fixture should load");
+        for _ in 0..3 {
+            runtime.simulate_button_click("reject_btn");
+        }
+
+        assert_eq!(runtime.event_log_max_entries(), 2);
+        assert_eq!(runtime.event_log().len(), 2);
+        assert_eq!(runtime.event_log_total(), 3);
+        assert_eq!(runtime.events_since(1).count(), 2);
+    }
+
+    #[test]
+    fn button_click_carries_form_value_snapshot() {
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_json(include_str!("fixture.json"))
+            .expect("fixture should load");
+        runtime.simulate_form_commit(
+            "review_form",
+            "decision",
+            UiFieldValue::Select {
+                value: "reject".to_string(),
+            },
+        );
+        runtime.simulate_button_click("reject_btn");
+
+        let Some(UiEvent::ButtonClicked { form_values, .. }) = runtime.event_log().last() else {
+            panic!("expected button click event");
+        };
+        assert_eq!(form_values, &runtime.form_state_snapshot());
+        assert_eq!(
+            form_values.get("review_form:decision"),
+            Some(&UiFieldValue::Select {
+                value: "reject".to_string()
+            })
+        );
+        assert_eq!(
+            form_values.get("review_form:notify_team"),
+            Some(&UiFieldValue::Checkbox { value: true })
+        );
+    }
+
+    #[test]
+    fn required_field_blocks_button_until_filled() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [{"component_id": "submit", "event_id": "notes.submit"}],
+            "components": [
+                {
+                    "id": "notes_form",
+                    "kind": "form",
+                    "fields": [
+                        {"id": "notes", "label": "Notes", "kind": "text", "required": true},
+                        {"id": "extra", "label": "Extra", "kind": "text"}
+                    ]
+                },
+                {"id": "submit", "kind": "button", "label": "Submit"}
+            ]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("required-field schema should load");
+
+        runtime.simulate_button_click("submit");
+        assert!(runtime.event_log().is_empty());
+        assert_eq!(
+            runtime
+                .validation_errors()
+                .get("notes_form:notes")
+                .map(String::as_str),
+            Some("Notes is required")
+        );
+        assert!(!runtime.validation_errors().contains_key("notes_form:extra"));
+
+        runtime.simulate_form_commit(
+            "notes_form",
+            "notes",
+            UiFieldValue::Text {
+                value: "looks good".to_string(),
+            },
+        );
+        assert!(runtime.validation_errors().is_empty());
+
+        runtime.simulate_button_click("submit");
+        assert!(matches!(
+            runtime.event_log().last(),
+            Some(UiEvent::ButtonClicked { component_id, .. }) if component_id == "submit"
+        ));
+    }
+
+    #[test]
+    fn markdown_with_headings_lists_and_links_loads() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "notes",
+                "kind": "markdown",
+                "text": "### Heading\n- first\n- second\n\nSee [docs](https://example.com) and `code`."
+            }]
+        });
+
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("markdown schema should load");
+        assert!(runtime.has_schema());
+        assert!(runtime.runtime_error().is_none());
+    }
+
+    #[test]
+    fn rust_code_component_loads() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "snippet",
+                "kind": "code",
+                "language": "rust",
+                "code": "fn main() {\n    println!(\"hi\");\n}"
+            }]
+        });
+
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("rust code schema should load");
+        assert!(runtime.has_schema());
+    }
+
+    #[test]
+    fn well_formed_table_loads() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "results",
+                "kind": "table",
+                "columns": ["test", "status"],
+                "rows": [["parses", "ok"], ["renders", "ok"]]
+            }]
+        });
+
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("table schema should load");
+        assert!(runtime.has_schema());
+    }
+
+    #[test]
+    fn tab_selection_persists_through_form_state_and_renders() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "review_tabs",
+                "kind": "tabs",
+                "children": [
+                    {"id": "summary", "kind": "markdown", "title": "Summary", "text": "ok"},
+                    {"id": "patch", "kind": "code", "title": "Diff", "code": "+ added"}
+                ]
+            }]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("tabs should load");
+
+        let active = |runtime: &UiRuntime| {
+            let Some(ValidatedComponent::Tabs(tabs)) = runtime
+                .validated_schema
+                .as_ref()
+                .and_then(|schema| schema.components.first())
+            else {
+                panic!("expected tabs component");
+            };
+            active_tab_index(&runtime.form_state, tabs)
+        };
+
+        let key = tabs_state_key("review_tabs");
+        render_headless(&mut runtime);
+        assert_eq!(active(&runtime), Some(0));
+        assert_eq!(
+            runtime.form_state_snapshot().get(&key),
+            Some(&UiFieldValue::Number { value: 0.0 })
+        );
+
+        let mut toggled = runtime.form_state_snapshot();
+        toggled.insert(key.clone(), UiFieldValue::Number { value: 1.0 });
+        runtime.restore_form_state(toggled);
+        render_headless(&mut runtime);
+        assert_eq!(active(&runtime), Some(1));
+
+        let mut stale = runtime.form_state_snapshot();
+        stale.insert(key, UiFieldValue::Number { value: 7.0 });
+        runtime.restore_form_state(stale);
+        render_headless(&mut runtime);
+        assert_eq!(active(&runtime), Some(1));
+    }
+
+    #[test]
+    fn hidden_tab_is_skipped_and_selection_is_logged() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [
+                {
+                    "id": "review_form",
+                    "kind": "form",
+                    "fields": [{
+                        "id": "decision",
+                        "label": "Decision",
+                        "kind": "select",
+                        "options": ["approve", "revise"],
+                        "default": "approve"
+                    }]
+                },
+                {
+                    "id": "review_tabs",
+                    "kind": "tabs",
+                    "children": [
+                        {"id": "summary", "kind": "markdown", "title": "Summary", "text": "ok"},
+                        {
+                            "id": "revision",
+                            "kind": "markdown",
+                            "title": "Revision",
+                            "text": "todo",
+                            "visible_when": {"field": "review_form.decision", "equals": "revise"}
+                        }
+                    ]
+                }
+            ]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("tabs should load");
+        let active = |runtime: &UiRuntime| {
+            let Some(ValidatedComponent::Tabs(tabs)) = runtime
+                .validated_schema
+                .as_ref()
+                .and_then(|schema| schema.components.get(1))
+            else {
+                panic!("expected tabs component");
+            };
+            active_tab_index(&runtime.form_state, tabs)
+        };
+
+        runtime.simulate_tab_select("review_tabs", 1);
+        assert_eq!(
+            runtime.event_log().last(),
+            Some(&&UiEvent::TabSelected {
+                component_id: "review_tabs".to_string(),
+                tab_index: 1,
+            })
+        );
+        // The selected tab is hidden, so the first visible one shows instead.
+        assert_eq!(active(&runtime), Some(0));
+        render_headless(&mut runtime);
+
+        runtime.simulate_form_commit(
+            "review_form",
+            "decision",
+            UiFieldValue::Select {
+                value: "revise".to_string(),
+            },
+        );
+        assert_eq!(active(&runtime), Some(1));
+    }
+
+    #[test]
+    fn empty_key_value_renders_without_panicking() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{"id": "meta", "kind": "key_value", "pairs": []}]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("empty key_value should load");
+        render_headless(&mut runtime);
+        assert!(runtime.runtime_error().is_none());
+    }
+
+    #[test]
+    fn danger_button_loads() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [{"component_id": "discard", "event_id": "action.discard"}],
+            "components": [{
+                "id": "discard",
+                "kind": "button",
+                "label": "Discard",
+                "variant": "danger"
+            }]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("danger button should load");
+        let button = runtime.find_button("discard").expect("button exists");
+        assert_eq!(button.variant, crate::ui::schema::ButtonStyle::Danger);
+    }
+
+    #[test]
+    fn confirm_button_emits_only_on_confirm() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [{"component_id": "discard", "event_id": "action.discard"}],
+            "components": [{
+                "id": "discard",
+                "kind": "button",
+                "label": "Discard",
+                "variant": "danger",
+                "confirm": "Discard all pending changes?"
+            }]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("schema should load");
+
+        runtime.simulate_button_click("discard");
+        assert!(runtime.event_log().is_empty());
+        runtime.simulate_button_confirm("discard", false);
+        assert!(runtime.event_log().is_empty());
+
+        runtime.simulate_button_click("discard");
+        runtime.simulate_button_confirm("discard", true);
+        assert_eq!(runtime.event_log().len(), 1);
+        let Some(UiEvent::ButtonClicked { form_values, .. }) = runtime.event_log().last() else {
+            panic!("expected a button click");
+        };
+        assert!(form_values.is_empty());
+
+        // A stray confirm without an open dialog does nothing.
+        runtime.simulate_button_confirm("discard", true);
+        assert_eq!(runtime.event_log().len(), 1);
+    }
+
+    #[test]
+    fn disabled_button_emits_no_event() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [{"component_id": "merge", "event_id": "action.merge"}],
+            "components": [{
+                "id": "merge",
+                "kind": "button",
+                "label": "Merge",
+                "disabled": true
+            }]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("schema should load");
+
+        runtime.simulate_button_click("merge");
+        assert!(runtime.event_log().is_empty());
+    }
+
+    #[test]
+    fn visible_when_follows_select_value() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [{"component_id": "submit", "event_id": "action.submit"}],
+            "components": [
+                {
+                    "id": "review_form",
+                    "kind": "form",
+                    "fields": [{
+                        "id": "decision",
+                        "label": "Decision",
+                        "kind": "select",
+                        "options": ["approve", "revise"],
+                        "default": "approve"
+                    }]
+                },
+                {
+                    "id": "revision_form",
+                    "kind": "form",
+                    "visible_when": {"field": "review_form.decision", "equals": "revise"},
+                    "fields": [{
+                        "id": "notes",
+                        "label": "Revision notes",
+                        "kind": "textarea",
+                        "required": true
+                    }]
+                },
+                {"id": "submit", "kind": "button", "label": "Submit"}
+            ]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime
+            .load_schema_value(&schema)
+            .expect("schema should load");
+        let revision_visible = |runtime: &UiRuntime| {
+            runtime
+                .validated_schema
+                .as_ref()
+                .expect("schema")
+                .components[1]
+                .is_visible(&runtime.form_state)
+        };
+
+        assert!(!revision_visible(&runtime));
+        // Hidden required fields do not gate the button.
+        runtime.simulate_button_click("submit");
+        assert_eq!(runtime.event_log().len(), 1);
+
+        runtime.simulate_form_commit(
+            "review_form",
+            "decision",
+            UiFieldValue::Select {
+                value: "revise".to_string(),
+            },
+        );
+        assert!(revision_visible(&runtime));
+        render_headless(&mut runtime);
+
+        runtime.simulate_button_click("submit");
+        assert!(runtime
+            .validation_errors()
+            .contains_key("revision_form:notes"));
+
+        runtime.simulate_form_commit(
+            "review_form",
+            "decision",
+            UiFieldValue::Select {
+                value: "approve".to_string(),
+            },
+        );
+        assert!(!revision_visible(&runtime));
+    }
+
+    #[test]
+    fn row_renders_children_side_by_side_headless() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{
+                "id": "side_by_side",
+                "kind": "row",
+                "children": [
+                    {"id": "left", "kind": "markdown", "text": "Left"},
+                    {"id": "right", "kind": "diff", "lines": [{"kind": "added", "text": "+ x"}]}
+                ]
+            }]
+        });
+        let mut runtime = UiRuntime::new();
+        runtime.load_schema_value(&schema).expect("row should load");
+        render_headless(&mut runtime);
+        assert!(runtime.runtime_error().is_none());
+    }
+
+    #[test]
+    fn reloading_identical_schema_validates_once() {
+        let schema = json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": [{"id": "note", "kind": "markdown", "text": "Hello"}]
+        });
+        let mut runtime = UiRuntime::new();
+
+        assert!(matches!(runtime.reload_schema_value(&schema), Ok(true)));
+        assert!(matches!(runtime.reload_schema_value(&schema), Ok(false)));
+
+        let mut changed = schema.clone();
+        changed["components"][0]["text"] = json!("Changed");
+        assert!(matches!(runtime.reload_schema_value(&changed), Ok(true)));
+        assert!(runtime.has_schema());
+    }
+
     #[test]
     fn malformed_schema_value_sets_runtime_error() {
         let mut runtime = UiRuntime::new();
@@ -6,6 +6,12 @@ use std::fmt;
 
 pub const MAX_COMPONENTS: usize = 64;
 pub const MAX_DEPTH: usize = 4;
+pub const MAX_TEXT_LEN: usize = 256 * 1024;
+/// Caps on the nodes one `tree` component may carry, counted over every level.
+pub const MAX_TREE_NODES: usize = 2048;
+pub const MAX_TREE_DEPTH: usize = 32;
+pub const MAX_SPACER_SIZE: f32 = 128.0;
+pub const DEFAULT_TEXTAREA_ROWS: usize = 4;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum ComponentKind {
@@ -14,6 +20,16 @@ pub enum ComponentKind {
     Code,
     Diff,
     Button,
+    Table,
+    Tree,
+    Progress,
+    Tabs,
+    Alert,
+    Divider,
+    Spacer,
+    KeyValue,
+    Chart,
+    Row,
     Unknown(String),
 }
 
@@ -25,6 +41,16 @@ impl ComponentKind {
             Self::Code => "code",
             Self::Diff => "diff",
             Self::Button => "button",
+            Self::Table => "table",
+            Self::Tree => "tree",
+            Self::Progress => "progress",
+            Self::Tabs => "tabs",
+            Self::Alert => "alert",
+            Self::Divider => "divider",
+            Self::Spacer => "spacer",
+            Self::KeyValue => "key_value",
+            Self::Chart => "chart",
+            Self::Row => "row",
             Self::Unknown(kind) => kind.as_str(),
         }
     }
@@ -46,6 +72,16 @@ impl<'de> Deserialize<'de> for ComponentKind {
             "code" => Self::Code,
             "diff" => Self::Diff,
             "button" => Self::Button,
+            "table" => Self::Table,
+            "tree" => Self::Tree,
+            "progress" => Self::Progress,
+            "tabs" => Self::Tabs,
+            "alert" => Self::Alert,
+            "divider" => Self::Divider,
+            "spacer" => Self::Spacer,
+            "key_value" => Self::KeyValue,
+            "chart" => Self::Chart,
+            "row" => Self::Row,
             _ => Self::Unknown(raw),
         })
     }
@@ -54,9 +90,12 @@ impl<'de> Deserialize<'de> for ComponentKind {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum FormFieldKind {
     Text,
+    TextArea,
     Number,
     Select,
+    MultiSelect,
     Checkbox,
+    Date,
     Unknown(String),
 }
 
@@ -64,9 +103,12 @@ impl FormFieldKind {
     pub fn as_str(&self) -> &str {
         match self {
             Self::Text => "text",
+            Self::TextArea => "textarea",
             Self::Number => "number",
             Self::Select => "select",
+            Self::MultiSelect => "multiselect",
             Self::Checkbox => "checkbox",
+            Self::Date => "date",
             Self::Unknown(kind) => kind.as_str(),
         }
     }
@@ -80,9 +122,38 @@ impl<'de> Deserialize<'de> for FormFieldKind {
         let raw = String::deserialize(deserializer)?;
         Ok(match raw.as_str() {
             "text" => Self::Text,
+            "textarea" => Self::TextArea,
             "number" => Self::Number,
             "select" => Self::Select,
+            "multiselect" => Self::MultiSelect,
             "checkbox" => Self::Checkbox,
+            "date" => Self::Date,
+            _ => Self::Unknown(raw),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Danger,
+    Success,
+    Unknown(String),
+}
+
+impl<'de> Deserialize<'de> for AlertSeverity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(match raw.as_str() {
+            "info" => Self::Info,
+            "warning" => Self::Warning,
+            "danger" => Self::Danger,
+            "success" => Self::Success,
             _ => Self::Unknown(raw),
         })
     }
@@ -93,6 +164,7 @@ impl<'de> Deserialize<'de> for FormFieldKind {
 pub enum ButtonStyle {
     Primary,
     Secondary,
+    Danger,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -132,6 +204,61 @@ pub struct DiffLine {
     pub text: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    #[serde(default)]
+    pub is_dir: bool,
+    #[serde(default)]
+    pub children: Vec<TreeNode>,
+    /// File size in bytes; `None` for directories or when metadata is unavailable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Last-modified time as RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// Number of direct entries, for directories.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_count: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyValuePair {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartPoint {
+    pub label: String,
+    pub value: f64,
+}
+
+/// Shows a component only while a form field equals a value. `field` is written
+/// as `form_id.field_id` and resolved through `field_key`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VisibilityCondition {
+    pub field: String,
+    pub equals: String,
+}
+
+impl VisibilityCondition {
+    pub fn state_key(&self) -> String {
+        match self.field.split_once('.') {
+            Some((form_id, field_id)) => field_key(form_id, field_id),
+            None => self.field.clone(),
+        }
+    }
+
+    pub fn is_satisfied(&self, form_state: &BTreeMap<String, UiFieldValue>) -> bool {
+        form_state
+            .get(&self.state_key())
+            .map(|value| value.display_value() == self.equals)
+            .unwrap_or(false)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawFormField {
     pub id: String,
@@ -141,6 +268,16 @@ pub struct RawFormField {
     pub options: Vec<String>,
     #[serde(default)]
     pub default: Value,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub rows: Option<usize>,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    #[serde(default)]
+    pub step: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -164,6 +301,30 @@ pub struct RawComponent {
     #[serde(default)]
     pub variant: Option<ButtonStyle>,
     #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub rows: Vec<Vec<String>>,
+    #[serde(default)]
+    pub nodes: Vec<TreeNode>,
+    #[serde(default)]
+    pub show_metadata: bool,
+    #[serde(default)]
+    pub value: Option<f64>,
+    #[serde(default)]
+    pub severity: Option<AlertSeverity>,
+    #[serde(default)]
+    pub size: Option<f32>,
+    #[serde(default)]
+    pub pairs: Vec<KeyValuePair>,
+    #[serde(default)]
+    pub series: Vec<ChartPoint>,
+    #[serde(default)]
+    pub confirm: Option<String>,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub visible_when: Option<VisibilityCondition>,
+    #[serde(default)]
     pub children: Vec<RawComponent>,
 }
 
@@ -181,6 +342,26 @@ fn default_schema_version() -> u32 {
     1
 }
 
+/// Size bounds applied while validating a schema. The defaults are the
+/// built-in `MAX_COMPONENTS`/`MAX_DEPTH`/`MAX_TEXT_LEN` caps; larger org dashboards can raise them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationLimits {
+    pub max_components: usize,
+    pub max_depth: usize,
+    /// Upper bound on the summed byte length of all markdown, code and diff text.
+    pub max_text_len: usize,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self {
+            max_components: MAX_COMPONENTS,
+            max_depth: MAX_DEPTH,
+            max_text_len: MAX_TEXT_LEN,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ValidatedSchema {
     pub schema_version: u32,
@@ -194,6 +375,16 @@ pub enum ValidatedComponent {
     Code(CodeComponent),
     Diff(DiffComponent),
     Button(ButtonComponent),
+    Table(TableComponent),
+    Tree(TreeComponent),
+    Progress(ProgressComponent),
+    Tabs(TabsComponent),
+    Alert(AlertComponent),
+    Divider(DividerComponent),
+    Spacer(SpacerComponent),
+    KeyValue(KeyValueComponent),
+    Chart(ChartComponent),
+    Row(RowComponent),
 }
 
 impl ValidatedComponent {
@@ -204,14 +395,52 @@ impl ValidatedComponent {
             Self::Code(component) => &component.children,
             Self::Diff(component) => &component.children,
             Self::Button(component) => &component.children,
+            Self::Table(component) => &component.children,
+            Self::Tree(component) => &component.children,
+            Self::Progress(component) => &component.children,
+            Self::Tabs(component) => &component.children,
+            Self::Alert(component) => &component.children,
+            Self::Divider(component) => &component.children,
+            Self::Spacer(component) => &component.children,
+            Self::KeyValue(component) => &component.children,
+            Self::Chart(component) => &component.children,
+            Self::Row(component) => &component.children,
+        }
+    }
+
+    pub fn visible_when(&self) -> Option<&VisibilityCondition> {
+        match self {
+            Self::Markdown(component) => component.visible_when.as_ref(),
+            Self::Form(component) => component.visible_when.as_ref(),
+            Self::Code(component) => component.visible_when.as_ref(),
+            Self::Diff(component) => component.visible_when.as_ref(),
+            Self::Button(component) => component.visible_when.as_ref(),
+            Self::Table(component) => component.visible_when.as_ref(),
+            Self::Tree(component) => component.visible_when.as_ref(),
+            Self::Progress(component) => component.visible_when.as_ref(),
+            Self::Tabs(component) => component.visible_when.as_ref(),
+            Self::Alert(component) => component.visible_when.as_ref(),
+            Self::Divider(component) => component.visible_when.as_ref(),
+            Self::Spacer(component) => component.visible_when.as_ref(),
+            Self::KeyValue(component) => component.visible_when.as_ref(),
+            Self::Chart(component) => component.visible_when.as_ref(),
+            Self::Row(component) => component.visible_when.as_ref(),
         }
     }
+
+    /// Hidden components are neither rendered nor checked for required fields.
+    pub fn is_visible(&self, form_state: &BTreeMap<String, UiFieldValue>) -> bool {
+        self.visible_when()
+            .map(|condition| condition.is_satisfied(form_state))
+            .unwrap_or(true)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct MarkdownComponent {
     pub id: String,
     pub text: String,
+    pub visible_when: Option<VisibilityCondition>,
     pub children: Vec<ValidatedComponent>,
 }
 
@@ -220,6 +449,7 @@ pub struct FormComponent {
     pub id: String,
     pub title: Option<String>,
     pub fields: Vec<ValidatedFormField>,
+    pub visible_when: Option<VisibilityCondition>,
     pub children: Vec<ValidatedComponent>,
 }
 
@@ -228,6 +458,7 @@ pub struct CodeComponent {
     pub id: String,
     pub language: Option<String>,
     pub code: String,
+    pub visible_when: Option<VisibilityCondition>,
     pub children: Vec<ValidatedComponent>,
 }
 
@@ -235,6 +466,7 @@ pub struct CodeComponent {
 pub struct DiffComponent {
     pub id: String,
     pub lines: Vec<DiffLine>,
+    pub visible_when: Option<VisibilityCondition>,
     pub children: Vec<ValidatedComponent>,
 }
 
@@ -244,24 +476,154 @@ pub struct ButtonComponent {
     pub label: String,
     pub output_event_id: String,
     pub variant: ButtonStyle,
+    /// Prompt shown in a confirm dialog before the click is emitted.
+    pub confirm: Option<String>,
+    pub disabled: bool,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableComponent {
+    pub id: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TreeComponent {
+    pub id: String,
+    pub nodes: Vec<TreeNode>,
+    /// Show size/entry count and modified time next to each node.
+    pub show_metadata: bool,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgressComponent {
+    pub id: String,
+    /// Completion fraction, clamped into `0.0..=1.0` during validation.
+    pub value: f32,
+    pub label: Option<String>,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+/// Each child is one tab panel; `titles[i]` labels `children[i]`.
+#[derive(Debug, Clone)]
+pub struct TabsComponent {
+    pub id: String,
+    pub titles: Vec<String>,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertComponent {
+    pub id: String,
+    pub severity: AlertSeverity,
+    pub text: String,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DividerComponent {
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpacerComponent {
+    /// Vertical space in pixels; `None` uses the theme's section spacing.
+    pub size: Option<f32>,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyValueComponent {
+    pub id: String,
+    pub pairs: Vec<(String, String)>,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChartComponent {
+    pub id: String,
+    pub title: Option<String>,
+    pub series: Vec<ChartPoint>,
+    pub visible_when: Option<VisibilityCondition>,
+    pub children: Vec<ValidatedComponent>,
+}
+
+impl ChartComponent {
+    /// Largest value in the series, used to scale bars; zero for an empty series.
+    pub fn max_value(&self) -> f64 {
+        self.series
+            .iter()
+            .map(|point| point.value)
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Lays its children out side by side, one column each.
+#[derive(Debug, Clone)]
+pub struct RowComponent {
+    pub id: String,
+    pub visible_when: Option<VisibilityCondition>,
     pub children: Vec<ValidatedComponent>,
 }
 
 #[derive(Debug, Clone)]
 pub enum ValidatedFormField {
     Text(TextField),
+    TextArea(TextAreaField),
     Number(NumberField),
     Select(SelectField),
+    MultiSelect(MultiSelectField),
     Checkbox(CheckboxField),
+    Date(DateField),
 }
 
 impl ValidatedFormField {
     pub fn id(&self) -> &str {
         match self {
             Self::Text(field) => &field.id,
+            Self::TextArea(field) => &field.id,
             Self::Number(field) => &field.id,
             Self::Select(field) => &field.id,
+            Self::MultiSelect(field) => &field.id,
             Self::Checkbox(field) => &field.id,
+            Self::Date(field) => &field.id,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Text(field) => &field.label,
+            Self::TextArea(field) => &field.label,
+            Self::Number(field) => &field.label,
+            Self::Select(field) => &field.label,
+            Self::MultiSelect(field) => &field.label,
+            Self::Checkbox(field) => &field.label,
+            Self::Date(field) => &field.label,
+        }
+    }
+
+    pub fn is_required(&self) -> bool {
+        match self {
+            Self::Text(field) => field.required,
+            Self::TextArea(field) => field.required,
+            Self::Number(field) => field.required,
+            Self::Select(field) => field.required,
+            Self::MultiSelect(field) => field.required,
+            Self::Checkbox(field) => field.required,
+            Self::Date(field) => field.required,
         }
     }
 
@@ -270,15 +632,24 @@ impl ValidatedFormField {
             Self::Text(field) => UiFieldValue::Text {
                 value: field.default.clone(),
             },
+            Self::TextArea(field) => UiFieldValue::Text {
+                value: field.default.clone(),
+            },
             Self::Number(field) => UiFieldValue::Number {
                 value: field.default,
             },
             Self::Select(field) => UiFieldValue::Select {
                 value: field.default.clone(),
             },
+            Self::MultiSelect(field) => UiFieldValue::MultiSelect {
+                value: field.default.clone(),
+            },
             Self::Checkbox(field) => UiFieldValue::Checkbox {
                 value: field.default,
             },
+            Self::Date(field) => UiFieldValue::Date {
+                value: field.default.clone(),
+            },
         }
     }
 }
@@ -287,37 +658,90 @@ impl ValidatedFormField {
 pub struct TextField {
     pub id: String,
     pub label: String,
+    pub required: bool,
+    pub default: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextAreaField {
+    pub id: String,
+    pub label: String,
+    pub required: bool,
     pub default: String,
+    pub rows: usize,
 }
 
 #[derive(Debug, Clone)]
 pub struct NumberField {
     pub id: String,
     pub label: String,
+    pub required: bool,
     pub default: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub step: Option<f64>,
+}
+
+impl NumberField {
+    /// Returns the slider range when both bounds are present and ordered.
+    pub fn slider_range(&self) -> Option<std::ops::RangeInclusive<f64>> {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min <= max => Some(min..=max),
+            _ => None,
+        }
+    }
+
+    pub fn clamp(&self, value: f64) -> f64 {
+        let value = self.min.map(|min| value.max(min)).unwrap_or(value);
+        self.max.map(|max| value.min(max)).unwrap_or(value)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SelectField {
     pub id: String,
     pub label: String,
+    pub required: bool,
     pub options: Vec<String>,
     pub default: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct MultiSelectField {
+    pub id: String,
+    pub label: String,
+    pub required: bool,
+    pub options: Vec<String>,
+    pub default: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CheckboxField {
     pub id: String,
     pub label: String,
+    pub required: bool,
     pub default: bool,
 }
 
+#[derive(Debug, Clone)]
+pub struct DateField {
+    pub id: String,
+    pub label: String,
+    pub required: bool,
+    pub default: String,
+}
+
+/// Whether `raw` is a real calendar date written exactly as `YYYY-MM-DD`.
+pub fn is_iso_date(raw: &str) -> bool {
+    raw.len() == 10 && chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d").is_ok()
+}
+
 pub trait SchemaRegistry {
     fn supports_component(&self, kind: &ComponentKind) -> bool;
     fn supports_field_kind(&self, kind: &FormFieldKind) -> bool;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ValidationError {
     UnknownComponent {
         component_id: String,
@@ -347,6 +771,45 @@ pub enum ValidationError {
     MissingButtonOutputContract {
         button_id: String,
     },
+    TableRowArity {
+        component_id: String,
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    UnknownAlertSeverity {
+        component_id: String,
+        severity: String,
+    },
+    NegativeChartValue {
+        component_id: String,
+        label: String,
+        value: f64,
+    },
+    NonFiniteChartValue {
+        component_id: String,
+        label: String,
+    },
+    InvalidNumberRange {
+        form_id: String,
+        field_id: String,
+        min: f64,
+        max: f64,
+    },
+    SchemaTextTooLarge {
+        actual: usize,
+        max: usize,
+    },
+    TreeTooLarge {
+        component_id: String,
+        actual: usize,
+        max: usize,
+    },
+    TreeTooDeep {
+        component_id: String,
+        actual: usize,
+        max: usize,
+    },
 }
 
 impl fmt::Display for ValidationError {
@@ -396,12 +859,127 @@ impl fmt::Display for ValidationError {
             Self::MissingButtonOutputContract { button_id } => {
                 write!(f, "button `{button_id}` missing output contract mapping")
             }
+            Self::TableRowArity {
+                component_id,
+                row,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "table `{component_id}` row {row} has {actual} cells, expected {expected}"
+                )
+            }
+            Self::UnknownAlertSeverity {
+                component_id,
+                severity,
+            } => {
+                write!(
+                    f,
+                    "unknown alert severity `{severity}` for component `{component_id}` (expected info, warning, danger or success)"
+                )
+            }
+            Self::NegativeChartValue {
+                component_id,
+                label,
+                value,
+            } => {
+                write!(
+                    f,
+                    "chart `{component_id}` point `{label}` has value {value}; values must be non-negative"
+                )
+            }
+            Self::NonFiniteChartValue {
+                component_id,
+                label,
+            } => {
+                write!(
+                    f,
+                    "chart `{component_id}` point `{label}` has a value that is not a finite number"
+                )
+            }
+            Self::InvalidNumberRange {
+                form_id,
+                field_id,
+                min,
+                max,
+            } => {
+                write!(
+                    f,
+                    "number field `{field_id}` in form `{form_id}` has min {min} above max {max}"
+                )
+            }
+            Self::SchemaTextTooLarge { actual, max } => {
+                write!(f, "schema text size {actual} bytes exceeds max {max}")
+            }
+            Self::TreeTooLarge {
+                component_id,
+                actual,
+                max,
+            } => {
+                write!(
+                    f,
+                    "tree `{component_id}` has {actual} nodes, exceeds max {max}"
+                )
+            }
+            Self::TreeTooDeep {
+                component_id,
+                actual,
+                max,
+            } => {
+                write!(
+                    f,
+                    "tree `{component_id}` nesting depth {actual} exceeds max {max}"
+                )
+            }
         }
     }
 }
 
 impl std::error::Error for ValidationError {}
 
+/// Every problem found in one validation pass, in schema order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn push(&mut self, error: ValidationError) {
+        self.errors.push(error);
+    }
+
+    pub fn extend(&mut self, other: ValidationReport) {
+        self.errors.extend(other.errors);
+    }
+}
+
+impl From<ValidationError> for ValidationReport {
+    fn from(error: ValidationError) -> Self {
+        Self {
+            errors: vec![error],
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // A single error displays exactly as it did before aggregation.
+        let messages = self
+            .errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
 fn as_string_or_default(value: &Value, default: &str) -> String {
     value
         .as_str()
@@ -417,187 +995,478 @@ fn as_bool_or_default(value: &Value, default: bool) -> bool {
     value.as_bool().unwrap_or(default)
 }
 
+fn as_option_list(value: &Value, options: &[String]) -> Vec<String> {
+    let selected = value
+        .as_array()
+        .map(|values| values.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+        .unwrap_or_default();
+    options
+        .iter()
+        .filter(|option| selected.contains(&option.as_str()))
+        .cloned()
+        .collect()
+}
+
 pub fn field_key(form_id: &str, field_id: &str) -> String {
     format!("{form_id}:{field_id}")
 }
 
-pub fn validate_schema<R: SchemaRegistry>(
-    schema: &UiSchema,
+const TABS_STATE_FIELD: &str = "__active_tab";
+const CONFIRM_STATE_FIELD: &str = "__confirm_pending";
+
+/// Synthetic `form_state` key holding the active tab index of a tabs component.
+pub fn tabs_state_key(tabs_id: &str) -> String {
+    field_key(tabs_id, TABS_STATE_FIELD)
+}
+
+/// Synthetic `form_state` key set while a button's confirm dialog is open.
+pub fn confirm_state_key(button_id: &str) -> String {
+    field_key(button_id, CONFIRM_STATE_FIELD)
+}
+
+pub fn is_synthetic_state_key(key: &str) -> bool {
+    [TABS_STATE_FIELD, CONFIRM_STATE_FIELD]
+        .iter()
+        .any(|field| key.ends_with(&format!(":{field}")))
+}
+
+pub fn validate_schema<R: SchemaRegistry>(
+    schema: &UiSchema,
     registry: &R,
-) -> Result<ValidatedSchema, ValidationError> {
+) -> Result<ValidatedSchema, ValidationReport> {
+    validate_schema_with_limits(schema, registry, &ValidationLimits::default())
+}
+
+pub fn validate_schema_with_limits<R: SchemaRegistry>(
+    schema: &UiSchema,
+    registry: &R,
+    limits: &ValidationLimits,
+) -> Result<ValidatedSchema, ValidationReport> {
     let output_map: BTreeMap<String, String> = schema
         .outputs
         .iter()
         .map(|output| (output.component_id.clone(), output.event_id.clone()))
         .collect();
-    let mut component_counter: usize = 0;
-    let mut actionable_ids = BTreeSet::new();
-
-    let components = validate_components(
-        &schema.components,
+    let mut context = ValidationContext {
         registry,
-        &output_map,
-        1,
-        &mut component_counter,
-        &mut actionable_ids,
-    )?;
+        output_map: &output_map,
+        limits,
+        component_counter: 0,
+        text_bytes: 0,
+        actionable_ids: BTreeSet::new(),
+        report: ValidationReport::default(),
+    };
+
+    let components = validate_components(&schema.components, 1, &mut context);
+    if context.text_bytes > limits.max_text_len {
+        context.report.push(ValidationError::SchemaTextTooLarge {
+            actual: context.text_bytes,
+            max: limits.max_text_len,
+        });
+    }
 
+    if !context.report.is_empty() {
+        return Err(context.report);
+    }
     Ok(ValidatedSchema {
         schema_version: schema.schema_version,
         components,
     })
 }
 
+struct ValidationContext<'a, R> {
+    registry: &'a R,
+    output_map: &'a BTreeMap<String, String>,
+    limits: &'a ValidationLimits,
+    component_counter: usize,
+    text_bytes: usize,
+    actionable_ids: BTreeSet<String>,
+    report: ValidationReport,
+}
+
 fn validate_components<R: SchemaRegistry>(
     raw_components: &[RawComponent],
-    registry: &R,
-    output_map: &BTreeMap<String, String>,
     depth: usize,
-    component_counter: &mut usize,
-    actionable_ids: &mut BTreeSet<String>,
-) -> Result<Vec<ValidatedComponent>, ValidationError> {
+    context: &mut ValidationContext<'_, R>,
+) -> Vec<ValidatedComponent> {
     let mut validated = Vec::with_capacity(raw_components.len());
+    let max_components = context.limits.max_components;
+    let max_depth = context.limits.max_depth;
 
     for raw in raw_components {
-        *component_counter += 1;
-        if *component_counter > MAX_COMPONENTS {
-            return Err(ValidationError::TooManyComponents {
-                max: MAX_COMPONENTS,
-                actual: *component_counter,
-            });
+        context.component_counter += 1;
+        if context.component_counter > max_components {
+            // Report the overflow once; anything past the cap is not worth validating.
+            if context.component_counter == max_components + 1 {
+                context.report.push(ValidationError::TooManyComponents {
+                    max: max_components,
+                    actual: context.component_counter,
+                });
+            }
+            continue;
         }
 
-        if depth > MAX_DEPTH {
-            return Err(ValidationError::NestingTooDeep {
-                max: MAX_DEPTH,
+        context.text_bytes += raw.text.as_deref().map_or(0, str::len)
+            + raw.code.as_deref().map_or(0, str::len)
+            + raw.lines.iter().map(|line| line.text.len()).sum::<usize>();
+
+        if depth > max_depth {
+            context.report.push(ValidationError::NestingTooDeep {
+                max: max_depth,
                 actual: depth,
                 component_id: raw.id.clone(),
             });
+            continue;
         }
 
-        if matches!(&raw.kind, ComponentKind::Unknown(_)) || !registry.supports_component(&raw.kind)
+        if matches!(&raw.kind, ComponentKind::Unknown(_))
+            || !context.registry.supports_component(&raw.kind)
         {
-            return Err(ValidationError::UnknownComponent {
+            context.report.push(ValidationError::UnknownComponent {
                 component_id: raw.id.clone(),
                 kind: raw.kind.as_str().to_string(),
             });
+            continue;
         }
 
-        if raw.kind.is_actionable() && !actionable_ids.insert(raw.id.clone()) {
-            return Err(ValidationError::DuplicateActionableId {
+        if raw.kind.is_actionable() && !context.actionable_ids.insert(raw.id.clone()) {
+            context.report.push(ValidationError::DuplicateActionableId {
                 component_id: raw.id.clone(),
             });
         }
 
-        let children = validate_components(
-            &raw.children,
-            registry,
-            output_map,
-            depth + 1,
-            component_counter,
-            actionable_ids,
-        )?;
+        let children = validate_components(&raw.children, depth + 1, context);
 
-        let component = match &raw.kind {
-            ComponentKind::Markdown => ValidatedComponent::Markdown(MarkdownComponent {
+        match build_component(raw, children, context.registry, context.output_map) {
+            Ok(component) => validated.push(component),
+            Err(errors) => context.report.extend(errors),
+        }
+    }
+
+    validated
+}
+
+/// Total node count and deepest level of `nodes`; a flat list has depth 1.
+fn tree_extent(nodes: &[TreeNode]) -> (usize, usize) {
+    nodes.iter().fold((0, 0), |(count, depth), node| {
+        let (child_count, child_depth) = tree_extent(&node.children);
+        (count + 1 + child_count, depth.max(1 + child_depth))
+    })
+}
+
+fn build_component<R: SchemaRegistry>(
+    raw: &RawComponent,
+    children: Vec<ValidatedComponent>,
+    registry: &R,
+    output_map: &BTreeMap<String, String>,
+) -> Result<ValidatedComponent, ValidationReport> {
+    let component = match &raw.kind {
+        ComponentKind::Markdown => ValidatedComponent::Markdown(MarkdownComponent {
+            id: raw.id.clone(),
+            text: raw
+                .text
+                .clone()
+                .ok_or(ValidationError::MissingRequiredField {
+                    component_id: raw.id.clone(),
+                    field: "text",
+                })?,
+            visible_when: raw.visible_when.clone(),
+            children,
+        }),
+        ComponentKind::Form => {
+            let fields = validate_form_fields(&raw.id, &raw.fields, registry)?;
+            ValidatedComponent::Form(FormComponent {
                 id: raw.id.clone(),
-                text: raw
-                    .text
+                title: raw.title.clone(),
+                fields,
+                visible_when: raw.visible_when.clone(),
+                children,
+            })
+        }
+        ComponentKind::Code => ValidatedComponent::Code(CodeComponent {
+            id: raw.id.clone(),
+            language: raw.language.clone(),
+            code: raw
+                .code
+                .clone()
+                .ok_or(ValidationError::MissingRequiredField {
+                    component_id: raw.id.clone(),
+                    field: "code",
+                })?,
+            visible_when: raw.visible_when.clone(),
+            children,
+        }),
+        ComponentKind::Diff => ValidatedComponent::Diff(DiffComponent {
+            id: raw.id.clone(),
+            lines: raw.lines.clone(),
+            visible_when: raw.visible_when.clone(),
+            children,
+        }),
+        ComponentKind::Button => {
+            let output_event_id = output_map.get(&raw.id).cloned().ok_or(
+                ValidationError::MissingButtonOutputContract {
+                    button_id: raw.id.clone(),
+                },
+            )?;
+            ValidatedComponent::Button(ButtonComponent {
+                id: raw.id.clone(),
+                label: raw
+                    .label
                     .clone()
                     .ok_or(ValidationError::MissingRequiredField {
                         component_id: raw.id.clone(),
-                        field: "text",
+                        field: "label",
                     })?,
+                output_event_id,
+                variant: raw.variant.clone().unwrap_or(ButtonStyle::Secondary),
+                confirm: raw
+                    .confirm
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+                    .map(ToString::to_string),
+                disabled: raw.disabled,
+                visible_when: raw.visible_when.clone(),
                 children,
-            }),
-            ComponentKind::Form => {
-                let fields = validate_form_fields(&raw.id, &raw.fields, registry)?;
-                ValidatedComponent::Form(FormComponent {
-                    id: raw.id.clone(),
-                    title: raw.title.clone(),
-                    fields,
-                    children,
-                })
+            })
+        }
+        ComponentKind::Table => {
+            if raw.columns.is_empty() {
+                return Err(ValidationError::MissingRequiredField {
+                    component_id: raw.id.clone(),
+                    field: "columns",
+                }
+                .into());
+            }
+            if let Some((row, cells)) = raw
+                .rows
+                .iter()
+                .enumerate()
+                .find(|(_, cells)| cells.len() != raw.columns.len())
+            {
+                return Err(ValidationError::TableRowArity {
+                    component_id: raw.id.clone(),
+                    row,
+                    expected: raw.columns.len(),
+                    actual: cells.len(),
+                }
+                .into());
             }
-            ComponentKind::Code => ValidatedComponent::Code(CodeComponent {
+            ValidatedComponent::Table(TableComponent {
                 id: raw.id.clone(),
-                language: raw.language.clone(),
-                code: raw
-                    .code
-                    .clone()
-                    .ok_or(ValidationError::MissingRequiredField {
-                        component_id: raw.id.clone(),
-                        field: "code",
-                    })?,
+                columns: raw.columns.clone(),
+                rows: raw.rows.clone(),
+                visible_when: raw.visible_when.clone(),
                 children,
-            }),
-            ComponentKind::Diff => ValidatedComponent::Diff(DiffComponent {
+            })
+        }
+        ComponentKind::Tree => {
+            let (count, depth) = tree_extent(&raw.nodes);
+            if count > MAX_TREE_NODES {
+                return Err(ValidationError::TreeTooLarge {
+                    component_id: raw.id.clone(),
+                    actual: count,
+                    max: MAX_TREE_NODES,
+                }
+                .into());
+            }
+            if depth > MAX_TREE_DEPTH {
+                return Err(ValidationError::TreeTooDeep {
+                    component_id: raw.id.clone(),
+                    actual: depth,
+                    max: MAX_TREE_DEPTH,
+                }
+                .into());
+            }
+            ValidatedComponent::Tree(TreeComponent {
                 id: raw.id.clone(),
-                lines: raw.lines.clone(),
+                nodes: raw.nodes.clone(),
+                show_metadata: raw.show_metadata,
+                visible_when: raw.visible_when.clone(),
                 children,
-            }),
-            ComponentKind::Button => {
-                let output_event_id = output_map.get(&raw.id).cloned().ok_or(
-                    ValidationError::MissingButtonOutputContract {
-                        button_id: raw.id.clone(),
-                    },
-                )?;
-                ValidatedComponent::Button(ButtonComponent {
-                    id: raw.id.clone(),
-                    label: raw
-                        .label
+            })
+        }
+        ComponentKind::Progress => {
+            let value = raw.value.ok_or(ValidationError::MissingRequiredField {
+                component_id: raw.id.clone(),
+                field: "value",
+            })?;
+            ValidatedComponent::Progress(ProgressComponent {
+                id: raw.id.clone(),
+                value: if value.is_nan() {
+                    0.0
+                } else {
+                    value.clamp(0.0, 1.0) as f32
+                },
+                label: raw.label.clone(),
+                visible_when: raw.visible_when.clone(),
+                children,
+            })
+        }
+        ComponentKind::Tabs => {
+            let titles = raw
+                .children
+                .iter()
+                .map(|child| {
+                    child
+                        .title
                         .clone()
                         .ok_or(ValidationError::MissingRequiredField {
-                            component_id: raw.id.clone(),
-                            field: "label",
-                        })?,
-                    output_event_id,
-                    variant: raw.variant.clone().unwrap_or(ButtonStyle::Secondary),
-                    children,
+                            component_id: child.id.clone(),
+                            field: "title",
+                        })
                 })
+                .collect::<Result<Vec<_>, _>>()?;
+            ValidatedComponent::Tabs(TabsComponent {
+                id: raw.id.clone(),
+                titles,
+                visible_when: raw.visible_when.clone(),
+                children,
+            })
+        }
+        ComponentKind::Alert => {
+            let severity = raw.severity.clone().unwrap_or(AlertSeverity::Info);
+            if let AlertSeverity::Unknown(severity) = severity {
+                return Err(ValidationError::UnknownAlertSeverity {
+                    component_id: raw.id.clone(),
+                    severity,
+                }
+                .into());
+            }
+            ValidatedComponent::Alert(AlertComponent {
+                id: raw.id.clone(),
+                severity,
+                text: raw
+                    .text
+                    .clone()
+                    .ok_or(ValidationError::MissingRequiredField {
+                        component_id: raw.id.clone(),
+                        field: "text",
+                    })?,
+                visible_when: raw.visible_when.clone(),
+                children,
+            })
+        }
+        ComponentKind::Divider => ValidatedComponent::Divider(DividerComponent {
+            visible_when: raw.visible_when.clone(),
+            children,
+        }),
+        ComponentKind::Spacer => ValidatedComponent::Spacer(SpacerComponent {
+            size: raw
+                .size
+                .filter(|size| size.is_finite())
+                .map(|size| size.clamp(0.0, MAX_SPACER_SIZE)),
+            visible_when: raw.visible_when.clone(),
+            children,
+        }),
+        ComponentKind::KeyValue => ValidatedComponent::KeyValue(KeyValueComponent {
+            id: raw.id.clone(),
+            pairs: raw
+                .pairs
+                .iter()
+                .map(|pair| (pair.key.clone(), pair.value.clone()))
+                .collect(),
+            visible_when: raw.visible_when.clone(),
+            children,
+        }),
+        ComponentKind::Chart => {
+            if let Some(point) = raw.series.iter().find(|point| !point.value.is_finite()) {
+                return Err(ValidationError::NonFiniteChartValue {
+                    component_id: raw.id.clone(),
+                    label: point.label.clone(),
+                }
+                .into());
             }
-            ComponentKind::Unknown(kind) => {
-                return Err(ValidationError::UnknownComponent {
+            if let Some(point) = raw.series.iter().find(|point| point.value < 0.0) {
+                return Err(ValidationError::NegativeChartValue {
                     component_id: raw.id.clone(),
-                    kind: kind.clone(),
-                });
+                    label: point.label.clone(),
+                    value: point.value,
+                }
+                .into());
             }
-        };
-
-        validated.push(component);
-    }
+            ValidatedComponent::Chart(ChartComponent {
+                id: raw.id.clone(),
+                title: raw.title.clone(),
+                series: raw.series.clone(),
+                visible_when: raw.visible_when.clone(),
+                children,
+            })
+        }
+        ComponentKind::Row => ValidatedComponent::Row(RowComponent {
+            id: raw.id.clone(),
+            visible_when: raw.visible_when.clone(),
+            children,
+        }),
+        ComponentKind::Unknown(kind) => {
+            return Err(ValidationError::UnknownComponent {
+                component_id: raw.id.clone(),
+                kind: kind.clone(),
+            }
+            .into());
+        }
+    };
 
-    Ok(validated)
+    Ok(component)
 }
 
 fn validate_form_fields<R: SchemaRegistry>(
     form_id: &str,
     raw_fields: &[RawFormField],
     registry: &R,
-) -> Result<Vec<ValidatedFormField>, ValidationError> {
+) -> Result<Vec<ValidatedFormField>, ValidationReport> {
     let mut fields = Vec::with_capacity(raw_fields.len());
+    let mut report = ValidationReport::default();
     for field in raw_fields {
         if matches!(&field.kind, FormFieldKind::Unknown(_))
             || !registry.supports_field_kind(&field.kind)
         {
-            return Err(ValidationError::UnsupportedFieldType {
+            report.push(ValidationError::UnsupportedFieldType {
                 form_id: form_id.to_string(),
                 field_id: field.id.clone(),
                 kind: field.kind.as_str().to_string(),
             });
+            continue;
         }
 
         let validated = match &field.kind {
             FormFieldKind::Text => ValidatedFormField::Text(TextField {
                 id: field.id.clone(),
                 label: field.label.clone(),
+                required: field.required,
                 default: as_string_or_default(&field.default, ""),
             }),
-            FormFieldKind::Number => ValidatedFormField::Number(NumberField {
+            FormFieldKind::TextArea => ValidatedFormField::TextArea(TextAreaField {
                 id: field.id.clone(),
                 label: field.label.clone(),
-                default: as_f64_or_default(&field.default, 0.0),
+                required: field.required,
+                default: as_string_or_default(&field.default, ""),
+                rows: field.rows.unwrap_or(DEFAULT_TEXTAREA_ROWS).max(1),
             }),
+            FormFieldKind::Number => {
+                if let (Some(min), Some(max)) = (field.min, field.max) {
+                    if min > max {
+                        report.push(ValidationError::InvalidNumberRange {
+                            form_id: form_id.to_string(),
+                            field_id: field.id.clone(),
+                            min,
+                            max,
+                        });
+                        continue;
+                    }
+                }
+                let mut number = NumberField {
+                    id: field.id.clone(),
+                    label: field.label.clone(),
+                    required: field.required,
+                    default: as_f64_or_default(&field.default, field.min.unwrap_or(0.0)),
+                    min: field.min,
+                    max: field.max,
+                    step: field.step.filter(|step| *step > 0.0),
+                };
+                number.default = number.clamp(number.default);
+                ValidatedFormField::Number(number)
+            }
             FormFieldKind::Select => {
                 let default = as_string_or_default(
                     &field.default,
@@ -610,27 +1479,53 @@ fn validate_form_fields<R: SchemaRegistry>(
                 ValidatedFormField::Select(SelectField {
                     id: field.id.clone(),
                     label: field.label.clone(),
+                    required: field.required,
                     options: field.options.clone(),
                     default,
                 })
             }
+            FormFieldKind::MultiSelect => ValidatedFormField::MultiSelect(MultiSelectField {
+                id: field.id.clone(),
+                label: field.label.clone(),
+                required: field.required,
+                options: field.options.clone(),
+                default: as_option_list(&field.default, &field.options),
+            }),
             FormFieldKind::Checkbox => ValidatedFormField::Checkbox(CheckboxField {
                 id: field.id.clone(),
                 label: field.label.clone(),
+                required: field.required,
                 default: as_bool_or_default(&field.default, false),
             }),
+            FormFieldKind::Date => {
+                let default = as_string_or_default(&field.default, "");
+                ValidatedFormField::Date(DateField {
+                    id: field.id.clone(),
+                    label: field.label.clone(),
+                    required: field.required,
+                    default: if is_iso_date(&default) {
+                        default
+                    } else {
+                        String::new()
+                    },
+                })
+            }
             FormFieldKind::Unknown(kind) => {
-                return Err(ValidationError::UnsupportedFieldType {
+                report.push(ValidationError::UnsupportedFieldType {
                     form_id: form_id.to_string(),
                     field_id: field.id.clone(),
                     kind: kind.clone(),
                 });
+                continue;
             }
         };
 
         fields.push(validated);
     }
 
+    if !report.is_empty() {
+        return Err(report);
+    }
     Ok(fields)
 }
 
@@ -640,6 +1535,10 @@ mod tests {
     use crate::ui::registry::ComponentRegistry;
 
     fn validate(json: &str) -> Result<ValidatedSchema, ValidationError> {
+        validate_all(json).map_err(|report| report.errors[0].clone())
+    }
+
+    fn validate_all(json: &str) -> Result<ValidatedSchema, ValidationReport> {
         let schema: UiSchema = serde_json::from_str(json).expect("schema should deserialize");
         let registry = ComponentRegistry::new();
         validate_schema(&schema, &registry)
@@ -681,6 +1580,159 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn textarea_field_validates() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"notes_form",
+            "kind":"form",
+            "fields":[{"id":"notes","label":"Notes","kind":"textarea","rows":6,"default":"draft"}]
+          }]
+        }"#;
+        let validated = validate(schema).expect("textarea field should validate");
+        let ValidatedComponent::Form(form) = &validated.components[0] else {
+            panic!("expected form component");
+        };
+        let ValidatedFormField::TextArea(field) = &form.fields[0] else {
+            panic!("expected textarea field");
+        };
+        assert_eq!(field.rows, 6);
+        assert_eq!(
+            form.fields[0].default_value(),
+            UiFieldValue::Text {
+                value: "draft".to_string()
+            }
+        );
+    }
+
+    fn number_field(json: &str) -> NumberField {
+        let validated = validate(json).expect("number field should validate");
+        let ValidatedComponent::Form(form) = &validated.components[0] else {
+            panic!("expected form component");
+        };
+        let ValidatedFormField::Number(field) = &form.fields[0] else {
+            panic!("expected number field");
+        };
+        field.clone()
+    }
+
+    #[test]
+    fn bounded_number_field_clamps_out_of_range_default() {
+        let field = number_field(
+            r#"{
+              "schema_version": 1,
+              "outputs": [],
+              "components": [{
+                "id":"f1",
+                "kind":"form",
+                "fields":[{"id":"confidence","label":"Confidence","kind":"number","min":0,"max":100,"step":5,"default":140}]
+              }]
+            }"#,
+        );
+        assert_eq!(field.default, 100.0);
+        assert_eq!(field.step, Some(5.0));
+        assert_eq!(field.slider_range(), Some(0.0..=100.0));
+    }
+
+    #[test]
+    fn number_field_without_max_falls_back_to_drag_value() {
+        let field = number_field(
+            r#"{
+              "schema_version": 1,
+              "outputs": [],
+              "components": [{
+                "id":"f1",
+                "kind":"form",
+                "fields":[{"id":"risk","label":"Risk","kind":"number","min":1,"default":-3}]
+              }]
+            }"#,
+        );
+        assert_eq!(field.default, 1.0);
+        assert!(field.slider_range().is_none());
+    }
+
+    #[test]
+    fn number_field_with_min_above_max_is_rejected() {
+        let report = validate_all(
+            r#"{
+              "schema_version": 1,
+              "outputs": [],
+              "components": [{
+                "id":"f1",
+                "kind":"form",
+                "fields":[{"id":"risk","label":"Risk","kind":"number","min":10,"max":1}]
+              }]
+            }"#,
+        )
+        .expect_err("inverted range should fail");
+        assert!(matches!(
+            &report.errors[..],
+            [ValidationError::InvalidNumberRange { form_id, field_id, min, max }]
+                if form_id == "f1" && field_id == "risk" && *min == 10.0 && *max == 1.0
+        ));
+    }
+
+    #[test]
+    fn multiselect_field_keeps_only_known_default_options() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"f1",
+            "kind":"form",
+            "fields":[{
+              "id":"categories",
+              "label":"Categories",
+              "kind":"multiselect",
+              "options":["security","perf","style"],
+              "default":["style","unknown","security"]
+            }]
+          }]
+        }"#;
+        let validated = validate(schema).expect("multiselect field should validate");
+        let ValidatedComponent::Form(form) = &validated.components[0] else {
+            panic!("expected form component");
+        };
+        assert_eq!(
+            form.fields[0].default_value(),
+            UiFieldValue::MultiSelect {
+                value: vec!["security".to_string(), "style".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn date_field_validates_and_drops_malformed_defaults() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"f1",
+            "kind":"form",
+            "fields":[
+              {"id":"target","label":"Target date","kind":"date","default":"2026-03-31"},
+              {"id":"bad","label":"Bad","kind":"date","default":"2026-02-30"},
+              {"id":"loose","label":"Loose","kind":"date","default":"2026-3-1"}
+            ]
+          }]
+        }"#;
+        let validated = validate(schema).expect("date fields should validate");
+        let ValidatedComponent::Form(form) = &validated.components[0] else {
+            panic!("expected form component");
+        };
+        assert!(matches!(form.fields[0], ValidatedFormField::Date(_)));
+        let defaults: Vec<_> = form
+            .fields
+            .iter()
+            .map(|field| field.default_value().display_value())
+            .collect();
+        assert_eq!(defaults, vec!["2026-03-31", "", ""]);
+        assert!(is_iso_date("2024-02-29"));
+        assert!(!is_iso_date("31/03/2026"));
+    }
+
     #[test]
     fn component_count_limit_enforced() {
         let mut components = Vec::new();
@@ -727,6 +1779,41 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn tree_node_limits_enforced() {
+        let tree_schema = |nodes: Vec<serde_json::Value>| {
+            serde_json::json!({
+                "schema_version": 1,
+                "outputs": [],
+                "components": [{"id": "t", "kind": "tree", "nodes": nodes}]
+            })
+            .to_string()
+        };
+        let leaf = |i: usize| serde_json::json!({"name": format!("f{i}"), "path": format!("f{i}")});
+
+        let wide = (0..=MAX_TREE_NODES).map(leaf).collect::<Vec<_>>();
+        assert!(matches!(
+            validate(&tree_schema(wide)),
+            Err(ValidationError::TreeTooLarge { .. })
+        ));
+
+        let mut deep = leaf(0);
+        for i in 1..=MAX_TREE_DEPTH {
+            deep = serde_json::json!({
+                "name": format!("d{i}"),
+                "path": format!("d{i}"),
+                "is_dir": true,
+                "children": [deep]
+            });
+        }
+        assert!(matches!(
+            validate(&tree_schema(vec![deep])),
+            Err(ValidationError::TreeTooDeep { .. })
+        ));
+
+        assert!(validate(&tree_schema((0..10).map(leaf).collect())).is_ok());
+    }
+
     #[test]
     fn missing_button_output_contract_fails_validation() {
         let schema = r#"{
@@ -739,4 +1826,354 @@ mod tests {
             Err(ValidationError::MissingButtonOutputContract { .. })
         ));
     }
+
+    #[test]
+    fn table_row_arity_mismatch_fails_validation() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"results",
+            "kind":"table",
+            "columns":["name","size"],
+            "rows":[["a.rs","12"],["b.rs"]]
+          }]
+        }"#;
+        assert_eq!(
+            validate(schema).err(),
+            Some(ValidationError::TableRowArity {
+                component_id: "results".to_string(),
+                row: 1,
+                expected: 2,
+                actual: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn progress_component_validates_with_label() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{"id":"build","kind":"progress","value":0.4,"label":"Building"}]
+        }"#;
+        let validated = validate(schema).expect("progress should validate");
+        let ValidatedComponent::Progress(progress) = &validated.components[0] else {
+            panic!("expected progress component");
+        };
+        assert!((progress.value - 0.4).abs() < f32::EPSILON);
+        assert_eq!(progress.label.as_deref(), Some("Building"));
+    }
+
+    #[test]
+    fn progress_value_above_one_is_clamped() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{"id":"build","kind":"progress","value":1.7}]
+        }"#;
+        let validated = validate(schema).expect("progress should validate");
+        let ValidatedComponent::Progress(progress) = &validated.components[0] else {
+            panic!("expected progress component");
+        };
+        assert_eq!(progress.value, 1.0);
+    }
+
+    #[test]
+    fn tabs_require_titled_children() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"review_tabs",
+            "kind":"tabs",
+            "children":[
+              {"id":"summary","kind":"markdown","title":"Summary","text":"ok"},
+              {"id":"patch","kind":"markdown","text":"no title"}
+            ]
+          }]
+        }"#;
+        assert_eq!(
+            validate(schema).err(),
+            Some(ValidationError::MissingRequiredField {
+                component_id: "patch".to_string(),
+                field: "title",
+            })
+        );
+
+        let schema = schema.replace(
+            r#""id":"patch","kind""#,
+            r#""id":"patch","title":"Diff","kind""#,
+        );
+        let validated = validate(&schema).expect("titled tabs should validate");
+        let ValidatedComponent::Tabs(tabs) = &validated.components[0] else {
+            panic!("expected tabs component");
+        };
+        assert_eq!(tabs.titles, vec!["Summary".to_string(), "Diff".to_string()]);
+    }
+
+    #[test]
+    fn alert_severity_is_validated() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{"id":"blocker","kind":"alert","severity":"warning","text":"Tests are failing"}]
+        }"#;
+        let validated = validate(schema).expect("warning alert should validate");
+        let ValidatedComponent::Alert(alert) = &validated.components[0] else {
+            panic!("expected alert component");
+        };
+        assert_eq!(alert.severity, AlertSeverity::Warning);
+
+        let schema = schema.replace(r#""severity":"warning""#, r#""severity":"critical""#);
+        assert_eq!(
+            validate(&schema).err(),
+            Some(ValidationError::UnknownAlertSeverity {
+                component_id: "blocker".to_string(),
+                severity: "critical".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn divider_and_spacer_need_no_text() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [
+            {"id":"intro","kind":"markdown","text":"Summary"},
+            {"id":"rule","kind":"divider"},
+            {"id":"gap","kind":"spacer","size":512}
+          ]
+        }"#;
+        let validated = validate(schema).expect("divider without text should validate");
+        assert!(matches!(
+            validated.components[1],
+            ValidatedComponent::Divider(_)
+        ));
+        let ValidatedComponent::Spacer(spacer) = &validated.components[2] else {
+            panic!("expected spacer component");
+        };
+        assert_eq!(spacer.size, Some(MAX_SPACER_SIZE));
+    }
+
+    #[test]
+    fn key_value_pairs_validate_in_order() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"meta",
+            "kind":"key_value",
+            "pairs":[{"key":"branch","value":"main"},{"key":"files changed","value":"3"}]
+          }]
+        }"#;
+        let validated = validate(schema).expect("key_value should validate");
+        let ValidatedComponent::KeyValue(meta) = &validated.components[0] else {
+            panic!("expected key_value component");
+        };
+        assert_eq!(
+            meta.pairs,
+            vec![
+                ("branch".to_string(), "main".to_string()),
+                ("files changed".to_string(), "3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn chart_series_validates() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"churn",
+            "kind":"chart",
+            "title":"Lines changed",
+            "series":[{"label":"added","value":120},{"label":"removed","value":45}]
+          }]
+        }"#;
+        let validated = validate(schema).expect("chart should validate");
+        let ValidatedComponent::Chart(chart) = &validated.components[0] else {
+            panic!("expected chart component");
+        };
+        assert_eq!(chart.series.len(), 2);
+        assert_eq!(chart.max_value(), 120.0);
+    }
+
+    #[test]
+    fn chart_rejects_negative_values() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"churn",
+            "kind":"chart",
+            "series":[{"label":"added","value":3},{"label":"delta","value":-2}]
+          }]
+        }"#;
+        assert_eq!(
+            validate(schema).err(),
+            Some(ValidationError::NegativeChartValue {
+                component_id: "churn".to_string(),
+                label: "delta".to_string(),
+                value: -2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn non_finite_chart_value_is_not_reported_as_negative() {
+        // JSON cannot carry NaN or infinity, so patch the parsed schema directly.
+        let mut schema: UiSchema = serde_json::from_str(
+            r#"{
+                "schema_version": 1,
+                "outputs": [],
+                "components": [
+                    {"id": "churn", "kind": "chart", "series": [
+                        {"label": "added", "value": 3},
+                        {"label": "delta", "value": 0}
+                    ]}
+                ]
+            }"#,
+        )
+        .expect("schema should deserialize");
+        let registry = ComponentRegistry::new();
+
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            schema.components[0].series[1].value = value;
+            let report = validate_schema(&schema, &registry).expect_err("non-finite value");
+            assert!(matches!(
+                &report.errors[..],
+                [ValidationError::NonFiniteChartValue { component_id, label }]
+                    if component_id == "churn" && label == "delta"
+            ));
+            assert_eq!(
+                report.errors[0].to_string(),
+                "chart `churn` point `delta` has a value that is not a finite number"
+            );
+        }
+    }
+
+    #[test]
+    fn validation_reports_every_error_in_one_pass() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [{"component_id":"approve","event_id":"action.approve"}],
+          "components": [
+            {"id":"approve","kind":"button","label":"Approve"},
+            {"id":"approve","kind":"button","label":"Approve again"},
+            {"id":"reject","kind":"button","label":"Reject"}
+          ]
+        }"#;
+        let report = validate_all(schema).expect_err("schema has two problems");
+        assert_eq!(
+            report.errors,
+            vec![
+                ValidationError::DuplicateActionableId {
+                    component_id: "approve".to_string(),
+                },
+                ValidationError::MissingButtonOutputContract {
+                    button_id: "reject".to_string(),
+                },
+            ]
+        );
+        let message = report.to_string();
+        assert!(message.contains("duplicate actionable component id `approve`"));
+        assert!(message.contains("button `reject` missing output contract mapping"));
+    }
+
+    #[test]
+    fn raised_limits_accept_larger_schemas() {
+        let components = (0..100)
+            .map(|i| serde_json::json!({"id": format!("m{i}"), "kind": "markdown", "text": "x"}))
+            .collect::<Vec<_>>();
+        let schema: UiSchema = serde_json::from_value(serde_json::json!({
+            "schema_version": 1,
+            "outputs": [],
+            "components": components
+        }))
+        .expect("schema should deserialize");
+        let registry = ComponentRegistry::new();
+
+        let report = validate_schema(&schema, &registry).expect_err("default cap is 64");
+        assert!(matches!(
+            report.errors.as_slice(),
+            [ValidationError::TooManyComponents {
+                max: MAX_COMPONENTS,
+                ..
+            }]
+        ));
+
+        let limits = ValidationLimits {
+            max_components: 128,
+            ..ValidationLimits::default()
+        };
+        let validated = validate_schema_with_limits(&schema, &registry, &limits)
+            .expect("raised limit should accept 100 components");
+        assert_eq!(validated.components.len(), 100);
+    }
+
+    #[test]
+    fn schema_text_size_is_bounded() {
+        let schema_with_text = |markdown_len: usize| {
+            serde_json::json!({
+                "schema_version": 1,
+                "outputs": [],
+                "components": [
+                    {"id": "notes", "kind": "markdown", "text": "m".repeat(markdown_len)},
+                    {"id": "snippet", "kind": "code", "code": "c".repeat(1024)},
+                    {"id": "patch", "kind": "diff", "lines": [{"kind": "added", "text": "d".repeat(1024)}]}
+                ]
+            })
+            .to_string()
+        };
+
+        let under = schema_with_text(MAX_TEXT_LEN - 2048);
+        assert!(validate(&under).is_ok());
+
+        let over = schema_with_text(MAX_TEXT_LEN - 2047);
+        assert_eq!(
+            validate(&over).err(),
+            Some(ValidationError::SchemaTextTooLarge {
+                actual: MAX_TEXT_LEN + 1,
+                max: MAX_TEXT_LEN,
+            })
+        );
+    }
+
+    #[test]
+    fn disabled_button_validates() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [{"component_id":"merge","event_id":"action.merge"}],
+          "components": [{"id":"merge","kind":"button","label":"Merge","disabled":true}]
+        }"#;
+        let validated = validate(schema).expect("disabled button should validate");
+        let ValidatedComponent::Button(button) = &validated.components[0] else {
+            panic!("expected button component");
+        };
+        assert!(button.disabled);
+    }
+
+    #[test]
+    fn row_with_two_markdown_children_validates() {
+        let schema = r#"{
+          "schema_version": 1,
+          "outputs": [],
+          "components": [{
+            "id":"side_by_side",
+            "kind":"row",
+            "children":[
+              {"id":"left","kind":"markdown","text":"Left"},
+              {"id":"right","kind":"markdown","text":"Right"}
+            ]
+          }]
+        }"#;
+        let validated = validate(schema).expect("row should validate");
+        let ValidatedComponent::Row(row) = &validated.components[0] else {
+            panic!("expected row component");
+        };
+        assert_eq!(row.children.len(), 2);
+    }
 }
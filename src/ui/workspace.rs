@@ -3,6 +3,7 @@ use crate::ui::event::UiFieldValue;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CanvasWorkspaceState {
@@ -27,6 +28,85 @@ pub struct CanvasBlockState {
     pub form_state: BTreeMap<String, UiFieldValue>,
 }
 
+/// What the assistant is told about an open canvas block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CanvasBlockSummary {
+    pub block_id: String,
+    pub template_id: String,
+    pub title: String,
+    pub minimized: bool,
+}
+
+impl From<&CanvasBlockState> for CanvasBlockSummary {
+    fn from(state: &CanvasBlockState) -> Self {
+        Self {
+            block_id: state.block_id.clone(),
+            template_id: state.template_id.clone(),
+            title: state.title.clone(),
+            minimized: state.minimized,
+        }
+    }
+}
+
+/// Open canvas blocks, published by the app and read by the `list_canvas_blocks` tool.
+pub type SharedCanvasSnapshot = Arc<RwLock<Vec<CanvasBlockSummary>>>;
+
+/// One line per open block, in canvas order, for the assistant's context.
+pub fn canvas_context_summary(blocks: &[CanvasBlockSummary]) -> String {
+    if blocks.is_empty() {
+        return "Open canvas blocks: none".to_string();
+    }
+    let mut summary = String::from("Open canvas blocks:");
+    for block in blocks {
+        summary.push_str(&format!(
+            "\n- {} \"{}\" ({}{})",
+            block.block_id,
+            block.title,
+            block.template_id,
+            if block.minimized { ", minimized" } else { "" }
+        ));
+    }
+    summary
+}
+
+/// How a block title resolves among the open blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TitleTarget {
+    /// Position of the only block with that title.
+    Unique(usize),
+    NotFound,
+    /// Ids of every block sharing the title, sorted.
+    Ambiguous(Vec<String>),
+}
+
+/// Resolves `title` against `(block_id, title)` pairs in canvas order, ignoring case
+/// and surrounding whitespace. The app and the `query_ui_catalog` tool both use it,
+/// so the tool reports exactly what the app will do.
+pub fn resolve_title_target<'a>(
+    blocks: impl IntoIterator<Item = (&'a str, &'a str)>,
+    title: &str,
+) -> TitleTarget {
+    let wanted = title.trim().to_lowercase();
+    let matches = blocks
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (_, block_title))| block_title.trim().to_lowercase() == wanted)
+        .collect::<Vec<_>>();
+
+    match matches.as_slice() {
+        [] => TitleTarget::NotFound,
+        [(index, _)] => TitleTarget::Unique(*index),
+        _ => {
+            let mut block_ids = matches
+                .into_iter()
+                .map(|(_, (block_id, _))| block_id.to_string())
+                .collect::<Vec<_>>();
+            block_ids.sort();
+            TitleTarget::Ambiguous(block_ids)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CanvasBlockActionType {
@@ -52,3 +132,37 @@ pub enum CanvasBlockActionStatus {
     Succeeded,
     Failed,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{canvas_context_summary, CanvasBlockSummary};
+
+    fn summary(
+        block_id: &str,
+        title: &str,
+        template_id: &str,
+        minimized: bool,
+    ) -> CanvasBlockSummary {
+        CanvasBlockSummary {
+            block_id: block_id.to_string(),
+            template_id: template_id.to_string(),
+            title: title.to_string(),
+            minimized,
+        }
+    }
+
+    #[test]
+    fn context_summary_lists_blocks_in_order() {
+        let blocks = vec![
+            summary("block-1", "Files", "builtin.file_listing.default", false),
+            summary("block-2", "Plan", "builtin.plan_review.default", true),
+        ];
+        assert_eq!(
+            canvas_context_summary(&blocks),
+            "Open canvas blocks:\n\
+             - block-1 \"Files\" (builtin.file_listing.default)\n\
+             - block-2 \"Plan\" (builtin.plan_review.default, minimized)"
+        );
+        assert_eq!(canvas_context_summary(&[]), "Open canvas blocks: none");
+    }
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_INNER_SIZE: [f32; 2] = [1280.0, 800.0];
+pub const MIN_INNER_SIZE: [f32; 2] = [1024.0, 640.0];
+/// Points of the window's top edge kept on the monitor so it can still be dragged.
+const VISIBLE_MARGIN: f32 = 64.0;
+
+/// Last window size and position, in logical points. Persisted in `Settings`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub inner_size: [f32; 2],
+    #[serde(default)]
+    pub position: Option<[f32; 2]>,
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self {
+            inner_size: DEFAULT_INNER_SIZE,
+            position: None,
+        }
+    }
+}
+
+impl WindowGeometry {
+    /// Raises each axis to at least `min`; non-finite sizes fall back to the default
+    /// and non-finite positions are dropped so the OS picks one.
+    pub fn clamped(self, min: [f32; 2]) -> Self {
+        let inner_size = if self.inner_size.iter().all(|value| value.is_finite()) {
+            [
+                self.inner_size[0].max(min[0]),
+                self.inner_size[1].max(min[1]),
+            ]
+        } else {
+            DEFAULT_INNER_SIZE
+        };
+        let position = self
+            .position
+            .filter(|position| position.iter().all(|value| value.is_finite()));
+        Self {
+            inner_size,
+            position,
+        }
+    }
+
+    /// Position moved so at least `VISIBLE_MARGIN` points of the window's top edge lie
+    /// on a monitor of `monitor_size` with its origin at (0, 0). `None` without a position.
+    pub fn position_on_monitor(&self, monitor_size: [f32; 2]) -> Option<[f32; 2]> {
+        let [x, y] = self.position?;
+        let max_x = (monitor_size[0] - VISIBLE_MARGIN).max(0.0);
+        let max_y = (monitor_size[1] - VISIBLE_MARGIN).max(0.0);
+        let min_x = (VISIBLE_MARGIN - self.inner_size[0]).min(max_x);
+        Some([x.clamp(min_x, max_x), y.clamp(0.0, max_y)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WindowGeometry, DEFAULT_INNER_SIZE, MIN_INNER_SIZE};
+
+    #[test]
+    fn clamp_raises_persisted_size_below_minimum() {
+        let tiny = WindowGeometry {
+            inner_size: [300.0, 900.0],
+            position: Some([40.0, 60.0]),
+        };
+        let clamped = tiny.clamped(MIN_INNER_SIZE);
+        assert_eq!(clamped.inner_size, [1024.0, 900.0]);
+        assert_eq!(clamped.position, Some([40.0, 60.0]));
+
+        let broken = WindowGeometry {
+            inner_size: [f32::NAN, 700.0],
+            position: Some([f32::INFINITY, 0.0]),
+        };
+        let clamped = broken.clamped(MIN_INNER_SIZE);
+        assert_eq!(clamped.inner_size, DEFAULT_INNER_SIZE);
+        assert_eq!(clamped.position, None);
+    }
+
+    #[test]
+    fn off_screen_position_is_moved_back_onto_the_monitor() {
+        let monitor = [1920.0, 1080.0];
+        let at = |position: [f32; 2]| WindowGeometry {
+            inner_size: [1280.0, 800.0],
+            position: Some(position),
+        };
+
+        assert_eq!(
+            at([100.0, 50.0]).position_on_monitor(monitor),
+            Some([100.0, 50.0])
+        );
+        assert_eq!(
+            at([3000.0, 2000.0]).position_on_monitor(monitor),
+            Some([1856.0, 1016.0])
+        );
+        assert_eq!(
+            at([-5000.0, -40.0]).position_on_monitor(monitor),
+            Some([-1216.0, 0.0])
+        );
+        assert_eq!(WindowGeometry::default().position_on_monitor(monitor), None);
+    }
+}